@@ -1,46 +1,60 @@
-use serde_json::json;
+use schemars::JsonSchema;
+use schemaui::SchemaUI;
+use serde::{Deserialize, Serialize};
+
+/// Edit service configuration interactively
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+struct ServiceConfig {
+    /// Server listening address
+    host: String,
+    /// Server port
+    port: u16,
+    /// Logging configuration
+    log: Logging,
+    /// Enabled feature flags
+    features: Vec<String>,
+    /// Expose service publicly
+    public: bool,
+    /// Optional TLS settings
+    tls: Option<Tls>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+enum Level {
+    Trace,
+    Debug,
+    Info,
+    Warn,
+    Error,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+struct Logging {
+    level: Level,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+struct Tls {
+    cert: String,
+    key: String,
+}
 
 fn main() -> anyhow::Result<()> {
-    let _schema = json!({
-        "$schema": "http://json-schema.org/draft-07/schema#",
-        "title": "Service Config",
-        "description": "Edit service configuration interactively",
-        "type": "object",
-        "required": ["host", "port"],
-        "properties": {
-            "host": {
-                "type": "string",
-                "description": "Server listening address",
-                "default": "127.0.0.1"
-            },
-            "port": {
-                "type": "integer",
-                "description": "Server port",
-                "default": 8080
-            },
-            "log": {
-                "type": "object",
-                "description": "Logging configuration",
-                "properties": {
-                    "level": {
-                        "type": "string",
-                        "enum": ["trace", "debug", "info", "warn", "error"],
-                        "default": "info"
-                    }
-                }
-            },
-            "features": {
-                "type": "array",
-                "description": "Enabled feature flags",
-                "items": { "type": "string" }
-            },
-            "public": {
-                "type": "boolean",
-                "description": "Expose service publicly",
-                "default": false
-            }
-        }
-    });
+    let current = ServiceConfig {
+        host: "127.0.0.1".into(),
+        port: 8080,
+        log: Logging { level: Level::Info },
+        features: Vec::new(),
+        public: false,
+        tls: None,
+    };
+
+    let config: ServiceConfig = SchemaUI::for_type::<ServiceConfig>()
+        .with_title("SchemaUI schemars Example")
+        .with_initial(&current)?
+        .run_typed()?;
+    println!("{config:#?}");
 
     Ok(())
 }
@@ -133,6 +133,12 @@ fn main() -> Result<()> {
     };
 
     let mut ui = SchemaUI::new(schema);
+    if let Some(spec) = schema_spec
+        && spec != "-"
+        && Path::new(spec).is_file()
+    {
+        ui = ui.with_schema_path(spec).map_err(Report::msg)?;
+    }
     if let Some(title) = cli.title.as_ref() {
         ui = ui.with_title(title.clone());
     }
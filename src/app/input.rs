@@ -14,6 +14,12 @@ pub enum KeyAction {
     ResetStatus,
     TogglePopup,
     EditComposite,
+    ToggleReveal,
+    ToggleAdvanced,
+    ToggleNull,
+    ToggleWarnings,
+    Undo,
+    Redo,
     FieldStep(i32),
     SectionStep(i32),
     RootStep(i32),
@@ -32,6 +38,12 @@ pub enum AppCommand {
     ResetStatus,
     TogglePopup,
     EditComposite,
+    ToggleReveal,
+    ToggleAdvanced,
+    ToggleNull,
+    ToggleWarnings,
+    Undo,
+    Redo,
     ListAddEntry,
     ListRemoveEntry,
     ListMove(i32),
@@ -58,6 +70,12 @@ enum KeyActionDiscriminant {
     ResetStatus,
     TogglePopup,
     EditComposite,
+    ToggleReveal,
+    ToggleAdvanced,
+    ToggleNull,
+    ToggleWarnings,
+    Undo,
+    Redo,
     FieldStepPos,
     FieldStepNeg,
     SectionStepPos,
@@ -105,6 +123,24 @@ impl KeyBindingMap {
             KeyActionDiscriminant::EditComposite,
             App(AppCommand::EditComposite),
         );
+        bindings.insert(
+            KeyActionDiscriminant::ToggleReveal,
+            App(AppCommand::ToggleReveal),
+        );
+        bindings.insert(
+            KeyActionDiscriminant::ToggleAdvanced,
+            App(AppCommand::ToggleAdvanced),
+        );
+        bindings.insert(
+            KeyActionDiscriminant::ToggleNull,
+            App(AppCommand::ToggleNull),
+        );
+        bindings.insert(
+            KeyActionDiscriminant::ToggleWarnings,
+            App(AppCommand::ToggleWarnings),
+        );
+        bindings.insert(KeyActionDiscriminant::Undo, App(AppCommand::Undo));
+        bindings.insert(KeyActionDiscriminant::Redo, App(AppCommand::Redo));
         bindings.insert(
             KeyActionDiscriminant::ResetStatus,
             App(AppCommand::ResetStatus),
@@ -163,6 +199,36 @@ impl KeyBindingMap {
                 .get(&KeyActionDiscriminant::EditComposite)
                 .cloned()
                 .unwrap_or(CommandDispatch::App(AppCommand::EditComposite)),
+            KeyAction::ToggleReveal => self
+                .bindings
+                .get(&KeyActionDiscriminant::ToggleReveal)
+                .cloned()
+                .unwrap_or(CommandDispatch::App(AppCommand::ToggleReveal)),
+            KeyAction::ToggleAdvanced => self
+                .bindings
+                .get(&KeyActionDiscriminant::ToggleAdvanced)
+                .cloned()
+                .unwrap_or(CommandDispatch::App(AppCommand::ToggleAdvanced)),
+            KeyAction::ToggleNull => self
+                .bindings
+                .get(&KeyActionDiscriminant::ToggleNull)
+                .cloned()
+                .unwrap_or(CommandDispatch::App(AppCommand::ToggleNull)),
+            KeyAction::ToggleWarnings => self
+                .bindings
+                .get(&KeyActionDiscriminant::ToggleWarnings)
+                .cloned()
+                .unwrap_or(CommandDispatch::App(AppCommand::ToggleWarnings)),
+            KeyAction::Undo => self
+                .bindings
+                .get(&KeyActionDiscriminant::Undo)
+                .cloned()
+                .unwrap_or(CommandDispatch::App(AppCommand::Undo)),
+            KeyAction::Redo => self
+                .bindings
+                .get(&KeyActionDiscriminant::Redo)
+                .cloned()
+                .unwrap_or(CommandDispatch::App(AppCommand::Redo)),
             KeyAction::FieldStep(delta) => {
                 let key = if delta >= 0 {
                     KeyActionDiscriminant::FieldStepPos
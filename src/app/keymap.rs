@@ -51,6 +51,12 @@ enum RawAction {
     ResetStatus,
     TogglePopup,
     EditComposite,
+    ToggleReveal,
+    ToggleAdvanced,
+    ToggleNull,
+    ToggleWarnings,
+    Undo,
+    Redo,
     FieldStep { delta: i32 },
     SectionStep { delta: i32 },
     RootStep { delta: i32 },
@@ -226,6 +232,12 @@ impl RawAction {
             RawAction::ResetStatus => KeyAction::ResetStatus,
             RawAction::TogglePopup => KeyAction::TogglePopup,
             RawAction::EditComposite => KeyAction::EditComposite,
+            RawAction::ToggleReveal => KeyAction::ToggleReveal,
+            RawAction::ToggleAdvanced => KeyAction::ToggleAdvanced,
+            RawAction::ToggleNull => KeyAction::ToggleNull,
+            RawAction::ToggleWarnings => KeyAction::ToggleWarnings,
+            RawAction::Undo => KeyAction::Undo,
+            RawAction::Redo => KeyAction::Redo,
             RawAction::FieldStep { delta } => KeyAction::FieldStep(delta),
             RawAction::SectionStep { delta } => KeyAction::SectionStep(delta),
             RawAction::RootStep { delta } => KeyAction::RootStep(delta),
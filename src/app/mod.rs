@@ -2,11 +2,12 @@ pub(crate) mod input;
 pub(crate) mod keymap;
 mod options;
 mod popup;
+pub(crate) mod review;
 mod runtime;
 mod schema_ui;
 mod status;
 mod terminal;
-mod validation;
+pub(crate) mod validation;
 
 pub use options::UiOptions;
 pub use schema_ui::SchemaUI;
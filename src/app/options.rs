@@ -11,6 +11,8 @@ pub struct UiOptions {
     pub tick_rate: Duration,
     pub auto_validate: bool,
     pub confirm_exit: bool,
+    /// List the changes for confirmation before a save is stored.
+    pub review_changes: bool,
     pub show_help: bool,
     pub keymap: KeyBindingMap,
     pub(crate) keymap_store: Arc<KeymapStore>,
@@ -22,6 +24,7 @@ impl Default for UiOptions {
             tick_rate: Duration::from_millis(250),
             auto_validate: true,
             confirm_exit: true,
+            review_changes: true,
             show_help: true,
             keymap: KeyBindingMap::default(),
             keymap_store: keymap::default_store(),
@@ -55,6 +58,11 @@ impl UiOptions {
         self
     }
 
+    pub fn with_review_changes(mut self, review: bool) -> Self {
+        self.review_changes = review;
+        self
+    }
+
     pub fn with_tick_rate(mut self, tick_rate: Duration) -> Self {
         self.tick_rate = tick_rate;
         self
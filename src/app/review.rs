@@ -0,0 +1,196 @@
+use serde_json::Value;
+
+use crate::{
+    form::FormState,
+    presentation::{ReviewRender, ReviewRow},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ChangeKind {
+    Added,
+    Removed,
+    Changed,
+}
+
+/// One difference between two JSON documents, keyed by JSON pointer.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct ValueChange {
+    pub pointer: String,
+    pub kind: ChangeKind,
+    pub old: Option<Value>,
+    pub new: Option<Value>,
+}
+
+/// Structural diff from `before` to `after`. Objects are compared key by key
+/// and arrays index by index; anything else that differs, including a change
+/// of type, is reported whole at its pointer.
+pub(crate) fn diff_values(before: &Value, after: &Value) -> Vec<ValueChange> {
+    let mut changes = Vec::new();
+    diff_into(String::new(), before, after, &mut changes);
+    changes
+}
+
+fn diff_into(pointer: String, before: &Value, after: &Value, changes: &mut Vec<ValueChange>) {
+    match (before, after) {
+        (Value::Object(old), Value::Object(new)) => {
+            let mut keys: Vec<&String> = old.keys().chain(new.keys()).collect();
+            keys.sort();
+            keys.dedup();
+            for key in keys {
+                let child = format!("{pointer}/{}", escape_token(key));
+                diff_entry(child, old.get(key), new.get(key), changes);
+            }
+        }
+        (Value::Array(old), Value::Array(new)) => {
+            for index in 0..old.len().max(new.len()) {
+                diff_entry(
+                    format!("{pointer}/{index}"),
+                    old.get(index),
+                    new.get(index),
+                    changes,
+                );
+            }
+        }
+        _ if before != after => changes.push(ValueChange {
+            pointer,
+            kind: ChangeKind::Changed,
+            old: Some(before.clone()),
+            new: Some(after.clone()),
+        }),
+        _ => {}
+    }
+}
+
+fn diff_entry(
+    pointer: String,
+    before: Option<&Value>,
+    after: Option<&Value>,
+    changes: &mut Vec<ValueChange>,
+) {
+    match (before, after) {
+        (Some(old), Some(new)) => diff_into(pointer, old, new, changes),
+        (None, Some(new)) => changes.push(ValueChange {
+            pointer,
+            kind: ChangeKind::Added,
+            old: None,
+            new: Some(new.clone()),
+        }),
+        (Some(old), None) => changes.push(ValueChange {
+            pointer,
+            kind: ChangeKind::Removed,
+            old: Some(old.clone()),
+            new: None,
+        }),
+        (None, None) => {}
+    }
+}
+
+fn escape_token(token: &str) -> String {
+    token.replace('~', "~0").replace('/', "~1")
+}
+
+const MAX_VALUE_WIDTH: usize = 40;
+
+/// The pre-save review: the validated value waiting to be stored and the
+/// changes it makes.
+pub(crate) struct ReviewState {
+    value: Value,
+    changes: Vec<ValueChange>,
+    rows: Vec<ReviewRow>,
+    selected: usize,
+}
+
+impl ReviewState {
+    /// Values of masked `writeOnly` fields are hidden in the rows.
+    pub(crate) fn new(value: Value, changes: Vec<ValueChange>, form: &FormState) -> Self {
+        let rows = changes
+            .iter()
+            .map(|change| {
+                let masked = form
+                    .field_for_pointer(&change.pointer)
+                    .is_some_and(|field| field.is_masked());
+                review_row(change, masked)
+            })
+            .collect();
+        Self {
+            value,
+            changes,
+            rows,
+            selected: 0,
+        }
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.changes.len()
+    }
+
+    pub(crate) fn select_previous(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    pub(crate) fn select_next(&mut self) {
+        if self.selected + 1 < self.changes.len() {
+            self.selected += 1;
+        }
+    }
+
+    pub(crate) fn selected_pointer(&self) -> Option<&str> {
+        self.changes
+            .get(self.selected)
+            .map(|change| change.pointer.as_str())
+    }
+
+    pub(crate) fn into_value(self) -> Value {
+        self.value
+    }
+
+    pub(crate) fn as_render(&self) -> ReviewRender<'_> {
+        ReviewRender {
+            rows: &self.rows,
+            selected: self.selected,
+        }
+    }
+}
+
+fn review_row(change: &ValueChange, masked: bool) -> ReviewRow {
+    let show = |value: &Option<Value>| {
+        value.as_ref().map(|value| {
+            if masked {
+                "••••••".to_string()
+            } else {
+                summarize(value)
+            }
+        })
+    };
+    let (symbol, detail) = match change.kind {
+        ChangeKind::Added => ('+', show(&change.new)),
+        ChangeKind::Removed => ('-', show(&change.old)),
+        ChangeKind::Changed => (
+            '~',
+            show(&change.old)
+                .zip(show(&change.new))
+                .map(|(old, new)| format!("{old} → {new}")),
+        ),
+    };
+    ReviewRow {
+        symbol,
+        pointer: if change.pointer.is_empty() {
+            "/".to_string()
+        } else {
+            change.pointer.clone()
+        },
+        detail: detail.unwrap_or_default(),
+    }
+}
+
+/// Compact JSON, cut short with an ellipsis.
+fn summarize(value: &Value) -> String {
+    let text = value.to_string();
+    if text.chars().count() > MAX_VALUE_WIDTH {
+        let mut short: String = text.chars().take(MAX_VALUE_WIDTH - 1).collect();
+        short.push('…');
+        short
+    } else {
+        text
+    }
+}
@@ -0,0 +1,36 @@
+use super::App;
+
+impl App {
+    /// Undoes (or redoes) the latest change of the form receiving input: the
+    /// open overlay's sub-form, otherwise the root form. Overlay commits are
+    /// steps of the form hosting the overlay.
+    pub(super) fn step_history(&mut self, redo: bool) {
+        let form = self.active_form_mut();
+        let stepped = if redo { form.redo() } else { form.undo() };
+        if !stepped {
+            self.status.set_raw(if redo {
+                "Nothing to redo"
+            } else {
+                "Nothing to undo"
+            });
+            return;
+        }
+        self.exit_armed = false;
+        self.popup = None;
+        if self.composite_editor.is_some() {
+            if self.sync_key_value_route() {
+                self.setup_overlay_validator();
+            }
+            self.run_overlay_validation();
+        } else if self.options.auto_validate {
+            self.run_validation(false);
+        } else {
+            self.validation_errors = self.form_state.error_count();
+        }
+        self.status.set_raw(if redo {
+            "Redid change (Ctrl+Z to undo)"
+        } else {
+            "Undid change (Ctrl+Y to redo)"
+        });
+    }
+}
@@ -1,7 +1,17 @@
 use super::App;
 
 impl App {
-    pub(super) fn list_field_pointer(&self) -> Option<String> {
+    /// The collection field list commands act on, and whether it is the field
+    /// the active overlay is editing (which then has to be closed and reopened
+    /// around the change).
+    pub(super) fn list_field_pointer(&self) -> Option<(String, bool)> {
+        if let Some(field) = self
+            .active_form()
+            .focused_field()
+            .filter(|field| field.is_composite_list())
+        {
+            return Some((field.schema.pointer.clone(), false));
+        }
         if let Some(editor) = &self.composite_editor
             && matches!(
                 editor.target,
@@ -10,28 +20,33 @@ impl App {
                     | super::overlay::CompositeOverlayTarget::ArrayEntry { .. }
             )
         {
-            return Some(editor.field_pointer.clone());
+            return Some((editor.field_pointer.clone(), true));
         }
-        self.form_state
-            .focused_field()
-            .filter(|field| field.is_composite_list())
-            .map(|field| field.schema.pointer.clone())
+        None
     }
 
     pub(super) fn handle_list_add_entry(&mut self) -> bool {
-        let Some(pointer) = self.list_field_pointer() else {
+        let Some((pointer, reopen)) = self.list_field_pointer() else {
             self.status
                 .set_raw("Focus a repeatable field before Ctrl+N add");
             return false;
         };
+        if let Some(reason) = self
+            .active_form()
+            .field_by_pointer(&pointer)
+            .and_then(|field| field.composite_list_add_blocked())
+        {
+            self.status.set_raw(reason);
+            return false;
+        }
 
-        let reopen = self.overlay_targets_pointer(&pointer);
         if reopen {
             self.close_composite_editor(true);
         }
 
+        let before = self.active_form().checkpoint();
         let selection_label = {
-            let Some(field) = self.form_state.field_mut_by_pointer(&pointer) else {
+            let Some(field) = self.active_form_mut().field_mut_by_pointer(&pointer) else {
                 return false;
             };
             if field.composite_list_add_entry() {
@@ -40,6 +55,7 @@ impl App {
                 return false;
             }
         };
+        self.active_form_mut().record_change(before, None);
         self.exit_armed = false;
         self.status.value_updated();
         if let Some(label) = selection_label {
@@ -59,19 +75,27 @@ impl App {
     }
 
     pub(super) fn handle_list_remove_entry(&mut self) -> bool {
-        let Some(pointer) = self.list_field_pointer() else {
+        let Some((pointer, reopen)) = self.list_field_pointer() else {
             self.status
                 .set_raw("Focus a repeatable field before Ctrl+D remove");
             return false;
         };
+        if let Some(reason) = self
+            .active_form()
+            .field_by_pointer(&pointer)
+            .and_then(|field| field.composite_list_remove_blocked())
+        {
+            self.status.set_raw(reason);
+            return false;
+        }
 
-        let reopen = self.overlay_targets_pointer(&pointer);
         if reopen {
             self.close_composite_editor(true);
         }
 
+        let before = self.active_form().checkpoint();
         let removed = {
-            let Some(field) = self.form_state.field_mut_by_pointer(&pointer) else {
+            let Some(field) = self.active_form_mut().field_mut_by_pointer(&pointer) else {
                 return false;
             };
             if field.composite_list_remove_entry() {
@@ -81,6 +105,7 @@ impl App {
                 return false;
             }
         };
+        self.active_form_mut().record_change(before, None);
         self.exit_armed = false;
         self.status.value_updated();
         if let Some(label) = removed {
@@ -101,19 +126,19 @@ impl App {
     }
 
     pub(super) fn handle_list_move_entry(&mut self, delta: i32) -> bool {
-        let Some(pointer) = self.list_field_pointer() else {
+        let Some((pointer, reopen)) = self.list_field_pointer() else {
             self.status
                 .set_raw("Focus a repeatable field before Ctrl+↑/↓ move");
             return false;
         };
 
-        let reopen = self.overlay_targets_pointer(&pointer);
         if reopen {
             self.close_composite_editor(true);
         }
 
+        let before = self.active_form().checkpoint();
         let moved_label = {
-            let Some(field) = self.form_state.field_mut_by_pointer(&pointer) else {
+            let Some(field) = self.active_form_mut().field_mut_by_pointer(&pointer) else {
                 return false;
             };
             if field.composite_list_move_entry(delta) {
@@ -123,6 +148,7 @@ impl App {
                 return false;
             }
         };
+        self.active_form_mut().record_change(before, None);
         self.exit_armed = false;
         self.status.value_updated();
         if let Some(label) = moved_label {
@@ -140,19 +166,18 @@ impl App {
     }
 
     pub(super) fn handle_list_select_entry(&mut self, delta: i32) -> bool {
-        let Some(pointer) = self.list_field_pointer() else {
+        let Some((pointer, reopen)) = self.list_field_pointer() else {
             self.status
                 .set_raw("Focus a repeatable field before Ctrl+←/→ select");
             return false;
         };
 
-        let reopen = self.overlay_targets_pointer(&pointer);
         if reopen {
             self.close_composite_editor(true);
         }
 
         let changed = {
-            let Some(field) = self.form_state.field_mut_by_pointer(&pointer) else {
+            let Some(field) = self.active_form_mut().field_mut_by_pointer(&pointer) else {
                 return false;
             };
             field.composite_list_select_entry(delta)
@@ -164,7 +189,7 @@ impl App {
             return false;
         }
 
-        if let Some(field) = self.form_state.field_by_pointer(&pointer)
+        if let Some(field) = self.active_form().field_by_pointer(&pointer)
             && let Some(label) = field.composite_list_selected_label()
         {
             self.status.set_raw(format!("Selected entry {}", label));
@@ -1,7 +1,7 @@
 use anyhow::{Result, anyhow};
 use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind};
 use jsonschema::Validator;
-use serde_json::Value;
+use serde_json::{Map, Value};
 use std::sync::Arc;
 
 use crate::{
@@ -14,13 +14,17 @@ use super::{
     keymap::{KeymapContext, KeymapStore},
     options::UiOptions,
     popup::PopupState,
+    review::ReviewState,
     status::StatusLine,
     terminal::TerminalGuard,
     validation::{ValidationOutcome, validate_form},
 };
 
+mod history;
 mod list_ops;
 mod overlay;
+mod save;
+mod text_keys;
 
 use overlay::CompositeEditorOverlay;
 
@@ -46,9 +50,17 @@ pub(crate) struct App {
     should_quit: bool,
     result: Option<Value>,
     popup: Option<AppPopup>,
+    /// Pending save awaiting confirmation.
+    review: Option<ReviewState>,
+    /// The last saved value, initially the one the form was seeded with.
+    baseline: Value,
     composite_editor: Option<CompositeEditorOverlay>,
+    overlay_stack: Vec<CompositeEditorOverlay>,
     input_router: InputRouter,
     keymap_store: Arc<KeymapStore>,
+    /// Schema diagnostics, one line each, shown in the warning panel.
+    warnings: Vec<String>,
+    show_warnings: bool,
 }
 
 impl App {
@@ -133,6 +145,35 @@ impl App {
             AppCommand::EditComposite => {
                 self.try_open_composite_editor();
             }
+            AppCommand::ToggleReveal => {
+                if self.toggle_focused_reveal() {
+                    return true;
+                }
+            }
+            AppCommand::ToggleAdvanced => {
+                self.toggle_advanced_fields();
+                return true;
+            }
+            AppCommand::ToggleWarnings => {
+                self.toggle_warnings();
+                return true;
+            }
+            AppCommand::Undo => {
+                self.step_history(false);
+                return true;
+            }
+            AppCommand::Redo => {
+                self.step_history(true);
+                return true;
+            }
+            AppCommand::ToggleNull => {
+                if let Some(pointer) = self.toggle_focused_null() {
+                    if self.options.auto_validate {
+                        self.dispatch_form_command(FormCommand::FieldEdited { pointer });
+                    }
+                    return true;
+                }
+            }
             AppCommand::ListAddEntry => {
                 if self.handle_list_add_entry() {
                     return true;
@@ -158,12 +199,23 @@ impl App {
     }
 
     fn handle_field_input(&mut self, event: &KeyEvent) {
+        if let Some(field) = self.form_state.focused_field()
+            && field.is_read_only()
+        {
+            self.status.read_only(&field.schema.display_label());
+            return;
+        }
+        let Some(before) = self.form_state.field_checkpoint() else {
+            return;
+        };
         if let Some(field) = self.form_state.focused_field_mut()
             && field.handle_key(event)
         {
             let pointer = field.schema.pointer.clone();
+            let label = field.schema.display_label();
+            self.form_state.record_field_change(before);
             self.exit_armed = false;
-            self.status.editing(&field.schema.display_label());
+            self.status.editing(&label);
             if self.options.auto_validate {
                 self.dispatch_form_command(FormCommand::FieldEdited { pointer });
             }
@@ -183,12 +235,31 @@ impl App {
             should_quit: false,
             result: None,
             popup: None,
+            review: None,
+            baseline: Value::Object(Map::new()),
             composite_editor: None,
+            overlay_stack: Vec::new(),
             input_router: InputRouter::new(keymap_store.clone()),
             keymap_store,
+            warnings: Vec::new(),
+            show_warnings: false,
         }
     }
 
+    /// Warnings start expanded so schema problems are seen before editing.
+    pub fn with_warnings(mut self, warnings: Vec<String>) -> Self {
+        self.show_warnings = !warnings.is_empty();
+        self.warnings = warnings;
+        self
+    }
+
+    /// The document the first save is reviewed against, normally the data the
+    /// form was seeded with; an empty object until set.
+    pub fn with_baseline(mut self, baseline: Value) -> Self {
+        self.baseline = baseline;
+        self
+    }
+
     pub fn run(&mut self) -> Result<Value> {
         let mut terminal = TerminalGuard::new()?;
         while !self.should_quit {
@@ -230,6 +301,7 @@ impl App {
                     instructions: editor.instructions.clone(),
                     list_entries: editor.list_entries.clone(),
                     list_selected: editor.list_selected,
+                    list_duplicates: editor.list_duplicates.clone(),
                 };
                 (Some(label), Some(editor.form_state_mut()), Some(meta))
             }
@@ -254,9 +326,11 @@ impl App {
                 error_count: self.validation_errors,
                 help: help.as_deref(),
                 global_errors: &self.global_errors,
+                warnings: self.show_warnings.then_some(self.warnings.as_slice()),
                 focus_label,
                 popup: self.popup.as_ref().map(|popup| popup.state.as_render()),
                 composite_overlay: overlay_meta,
+                review: self.review.as_ref().map(ReviewState::as_render),
             },
         );
     }
@@ -265,7 +339,19 @@ impl App {
         if key.kind != KeyEventKind::Press {
             return Ok(());
         }
+        self.dispatch_key(key)?;
+        self.form_state.refresh_conditions();
+        if let Some(editor) = self.composite_editor.as_mut() {
+            editor.form_state_mut().refresh_conditions();
+        }
+        Ok(())
+    }
 
+    fn dispatch_key(&mut self, key: KeyEvent) -> Result<()> {
+        if self.review.is_some() {
+            self.handle_review_key(key);
+            return Ok(());
+        }
         if self.handle_popup_key(key)? {
             return Ok(());
         }
@@ -279,6 +365,7 @@ impl App {
             .options
             .keymap
             .resolve(self.input_router.classify(&key));
+        let dispatch = self.route_text_keys(dispatch, key);
         match dispatch {
             CommandDispatch::Form(command) => {
                 self.dispatch_form_command(command);
@@ -312,6 +399,10 @@ impl App {
         let Some(field) = field_opt else {
             return false;
         };
+        if field.is_read_only() {
+            self.status.read_only(&field.schema.display_label());
+            return true;
+        }
         if let Some(popup) = PopupState::from_field(field) {
             let message = if popup.is_multi() {
                 "Use ↑/↓ to move, Space to toggle, Enter to apply"
@@ -328,14 +419,66 @@ impl App {
         false
     }
 
-    fn on_save(&mut self) {
-        if let Some(value) = self.run_validation(true) {
-            self.status
-                .set_raw("Configuration saved. Press Ctrl+Q to exit.");
-            self.result = Some(value);
-            self.form_state.mark_clean();
-            self.exit_armed = false;
+    /// Shows or masks the focused `writeOnly` field of the active form.
+    fn toggle_focused_reveal(&mut self) -> bool {
+        let Some(field) = self.active_form_mut().focused_field_mut() else {
+            return false;
+        };
+        if !field.toggle_reveal() {
+            return false;
+        }
+        let message = if field.is_masked() {
+            "Secret hidden"
+        } else {
+            "Secret revealed (Ctrl+R to hide)"
+        };
+        self.status.set_raw(message);
+        true
+    }
+
+    /// Sets the focused nullable field to `null`, or restores its value.
+    /// Returns the pointer of the toggled field.
+    fn toggle_focused_null(&mut self) -> Option<String> {
+        let before = self.active_form().checkpoint();
+        let field = self.active_form_mut().focused_field_mut()?;
+        if !field.toggle_null() {
+            return None;
+        }
+        let label = field.schema.display_label();
+        let pointer = field.schema.pointer.clone();
+        let message = if field.is_null() {
+            format!("{label} set to null (Ctrl+X to restore)")
+        } else {
+            format!("{label} restored")
+        };
+        self.active_form_mut().record_change(before, None);
+        self.status.set_raw(&message);
+        Some(pointer)
+    }
+
+    fn toggle_advanced_fields(&mut self) {
+        let form = self.active_form_mut();
+        form.toggle_advanced();
+        let message = if form.show_advanced {
+            "Showing advanced fields"
+        } else {
+            "Advanced fields hidden"
+        };
+        self.status.set_raw(message);
+    }
+
+    fn toggle_warnings(&mut self) {
+        if self.warnings.is_empty() {
+            self.status.set_raw("No schema warnings");
+            return;
         }
+        self.show_warnings = !self.show_warnings;
+        let message = if self.show_warnings {
+            "Showing schema warnings"
+        } else {
+            "Schema warnings hidden (Ctrl+P to show)"
+        };
+        self.status.set_raw(message);
     }
 
     fn on_exit(&mut self) {
@@ -382,6 +525,7 @@ impl App {
 mod tests {
     use super::*;
     use crate::{app::options::UiOptions, schema::build_form_schema};
+    use crossterm::event::KeyModifiers;
     use jsonschema::validator_for;
     use serde_json::json;
 
@@ -417,6 +561,144 @@ mod tests {
         assert!(app.should_quit, "exit flag should be set");
     }
 
+    #[test]
+    fn multi_line_fields_keep_enter_and_arrows() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "a_notes": {"type": "string", "x-widget": "textarea"},
+                "b_name": {"type": "string"}
+            }
+        });
+        let form_state = FormState::from_schema(&build_form_schema(&schema).expect("schema"));
+        let validator = validator_for(&schema).expect("validator");
+        let mut app = App::new(form_state, validator, UiOptions::default());
+        let press = |app: &mut App, code: KeyCode| {
+            app.handle_key(KeyEvent::from(code)).expect("key handled");
+        };
+        let focused = |app: &App| {
+            app.form_state
+                .focused_field()
+                .unwrap()
+                .schema
+                .pointer
+                .clone()
+        };
+
+        for code in [KeyCode::Char('a'), KeyCode::Enter, KeyCode::Char('b')] {
+            press(&mut app, code);
+        }
+        press(&mut app, KeyCode::Up);
+        press(&mut app, KeyCode::Down);
+        assert_eq!(focused(&app), "/a_notes");
+        assert_eq!(
+            app.form_state.try_build_value().unwrap()["a_notes"],
+            json!("a\nb")
+        );
+
+        press(&mut app, KeyCode::Down);
+        assert_eq!(
+            focused(&app),
+            "/b_name",
+            "Down on the last line leaves the field"
+        );
+    }
+
+    #[test]
+    fn undo_and_redo_keys_step_through_edits() {
+        let mut app = app_with_single_field();
+        let press = |app: &mut App, code: KeyCode, modifiers: KeyModifiers| {
+            app.handle_key(KeyEvent::new(code, modifiers))
+                .expect("key handled");
+        };
+        for ch in "svc".chars() {
+            press(&mut app, KeyCode::Char(ch), KeyModifiers::NONE);
+        }
+        press(&mut app, KeyCode::Char('z'), KeyModifiers::CONTROL);
+        assert_eq!(app.form_state.try_build_value().unwrap(), json!({}));
+        assert!(!app.form_state.is_dirty(), "undo restores the clean state");
+        assert_eq!(app.status.message(), "Undid change (Ctrl+Y to redo)");
+
+        press(&mut app, KeyCode::Char('y'), KeyModifiers::CONTROL);
+        assert_eq!(
+            app.form_state.try_build_value().unwrap(),
+            json!({"name": "svc"})
+        );
+        press(&mut app, KeyCode::Char('y'), KeyModifiers::CONTROL);
+        assert_eq!(app.status.message(), "Nothing to redo");
+    }
+
+    #[test]
+    fn save_reviews_changes_before_storing() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "a_host": {"type": "string", "default": "localhost"},
+                "b_port": {"type": "integer"}
+            }
+        });
+        let form_state = FormState::from_schema(&build_form_schema(&schema).expect("schema"));
+        let validator = validator_for(&schema).expect("validator");
+        let mut app = App::new(form_state, validator, UiOptions::default())
+            .with_baseline(json!({"a_host": "localhost"}));
+        let press = |app: &mut App, code: KeyCode, modifiers: KeyModifiers| {
+            app.handle_key(KeyEvent::new(code, modifiers))
+                .expect("key handled");
+        };
+        press(&mut app, KeyCode::Tab, KeyModifiers::NONE);
+        press(&mut app, KeyCode::Char('1'), KeyModifiers::NONE);
+        press(&mut app, KeyCode::Up, KeyModifiers::NONE);
+
+        press(&mut app, KeyCode::Char('s'), KeyModifiers::CONTROL);
+        assert_eq!(
+            app.review.as_ref().map(ReviewState::len),
+            Some(1),
+            "only the edit differs from the seeded document"
+        );
+        assert!(app.result.is_none());
+        press(&mut app, KeyCode::Esc, KeyModifiers::NONE);
+        assert!(app.review.is_none());
+        assert!(app.result.is_none(), "going back stores nothing");
+
+        press(&mut app, KeyCode::Char('s'), KeyModifiers::CONTROL);
+        press(&mut app, KeyCode::Enter, KeyModifiers::NONE);
+        assert!(app.review.is_none());
+        assert_eq!(
+            app.form_state.focused_field().unwrap().schema.pointer,
+            "/b_port"
+        );
+
+        press(&mut app, KeyCode::Char('s'), KeyModifiers::CONTROL);
+        press(&mut app, KeyCode::Char('s'), KeyModifiers::CONTROL);
+        assert_eq!(
+            app.result,
+            Some(json!({"a_host": "localhost", "b_port": 1}))
+        );
+        assert!(!app.form_state.is_dirty());
+
+        app.result = None;
+        press(&mut app, KeyCode::Char('s'), KeyModifiers::CONTROL);
+        assert!(app.review.is_none(), "nothing changed since the last save");
+        assert!(app.result.is_some());
+    }
+
+    #[test]
+    fn unseeded_forms_review_defaults_as_added() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "host": {"type": "string", "default": "localhost"}
+            }
+        });
+        let form_state = FormState::from_schema(&build_form_schema(&schema).expect("schema"));
+        let validator = validator_for(&schema).expect("validator");
+        let mut app = App::new(form_state, validator, UiOptions::default());
+        app.on_save();
+        let review = app.review.as_ref().expect("defaults differ from {}");
+        assert_eq!(review.selected_pointer(), Some("/host"));
+        assert!(app.result.is_none());
+    }
+
     #[test]
     fn exit_without_save_leaves_result_empty() {
         let mut app = app_with_single_field();
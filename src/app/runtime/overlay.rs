@@ -35,7 +35,7 @@ pub(super) fn apply_selection_to_field(
     }
 
     match &field.schema.kind {
-        FieldKind::Composite(_) => {
+        FieldKind::Composite(_) | FieldKind::MultiType(_) => {
             field.apply_composite_selection(selection, None);
         }
         FieldKind::Boolean => field.set_bool(selection == 0),
@@ -44,6 +44,34 @@ pub(super) fn apply_selection_to_field(
     }
 }
 
+/// Applies a popup choice to the field at `pointer` as one undo step, unless
+/// it left the field as it was. Returns whether the field exists.
+fn record_selection(
+    form: &mut FormState,
+    pointer: &str,
+    selection: usize,
+    multi: Option<Vec<bool>>,
+) -> bool {
+    let before = form.checkpoint();
+    let Some(field) = form.field_mut_by_pointer(pointer) else {
+        return false;
+    };
+    let previous = (field.display_value(), field.current_value().ok());
+    apply_selection_to_field(field, selection, multi);
+    if (field.display_value(), field.current_value().ok()) != previous {
+        form.record_change(before, None);
+    }
+    true
+}
+
+fn opens_overlay(kind: &FieldKind) -> bool {
+    match kind {
+        FieldKind::Composite(_) | FieldKind::KeyValue(_) | FieldKind::Tuple(_) => true,
+        FieldKind::Array(inner) => !matches!(inner.as_ref(), FieldKind::Enum(_) | FieldKind::Json),
+        _ => false,
+    }
+}
+
 pub(super) enum OverlaySession {
     Composite(CompositeEditorSession),
     KeyValue(KeyValueEditorSession),
@@ -98,6 +126,7 @@ pub(super) struct CompositeEditorOverlay {
     pub(super) exit_armed: bool,
     pub(super) list_entries: Option<Vec<String>>,
     pub(super) list_selected: Option<usize>,
+    pub(super) list_duplicates: Vec<usize>,
     pub(super) instructions: String,
     pub(super) validator: Option<Arc<Validator>>,
 }
@@ -121,6 +150,7 @@ impl CompositeEditorOverlay {
             exit_armed: false,
             list_entries: None,
             list_selected: None,
+            list_duplicates: Vec::new(),
             instructions,
             validator: None,
         }
@@ -163,16 +193,37 @@ impl App {
             .unwrap_or_else(|| "Ctrl+S save • Esc cancel".to_string())
     }
 
+    /// Opens an overlay for the focused field of the active form. When an
+    /// overlay is already open it is suspended on `overlay_stack` and resumes
+    /// once the nested overlay closes.
     pub(super) fn try_open_composite_editor(&mut self) {
-        if self.composite_editor.is_some() {
-            return;
+        if let Some(parent) = self.composite_editor.take() {
+            self.overlay_stack.push(parent);
         }
-        let Some(field) = self.form_state.focused_field_mut() else {
+        self.open_focused_editor();
+        if self.composite_editor.is_none() {
+            self.composite_editor = self.overlay_stack.pop();
+        }
+    }
+
+    fn open_focused_editor(&mut self) {
+        let parent_label = self
+            .overlay_stack
+            .last()
+            .map(|parent| parent.field_label.clone());
+        let Some(field) = self.host_form_mut().focused_field_mut() else {
             self.status.set_raw("No field selected");
             return;
         };
+        let label = match parent_label {
+            Some(parent) => format!("{parent} › {}", field.schema.display_label()),
+            None => field.schema.display_label(),
+        };
         match &field.schema.kind {
-            FieldKind::Composite(_) => {
+            FieldKind::Composite(template) => {
+                if template.recursive && field.active_composite_variants().is_empty() {
+                    field.apply_composite_selection(0, None);
+                }
                 let active = field.active_composite_variants();
                 let Some(&variant_index) = active.first() else {
                     self.status
@@ -180,7 +231,6 @@ impl App {
                     return;
                 };
                 let pointer = field.schema.pointer.clone();
-                let label = field.schema.display_label();
                 match field.open_composite_editor(variant_index) {
                     Ok(session) => {
                         self.popup = None;
@@ -196,9 +246,25 @@ impl App {
                     Err(err) => self.status.set_raw(&err.message),
                 }
             }
+            FieldKind::Tuple(_) => {
+                let pointer = field.schema.pointer.clone();
+                match field.open_composite_editor(0) {
+                    Ok(session) => {
+                        self.popup = None;
+                        self.composite_editor = Some(CompositeEditorOverlay::new(
+                            pointer,
+                            label,
+                            OverlaySession::Composite(session),
+                            self.overlay_help_text(),
+                        ));
+                        self.set_overlay_status_message();
+                        self.setup_overlay_validator();
+                    }
+                    Err(err) => self.status.set_raw(&err.message),
+                }
+            }
             FieldKind::Array(inner) if matches!(inner.as_ref(), FieldKind::Composite(_)) => {
                 let pointer = field.schema.pointer.clone();
-                let label = field.schema.display_label();
                 let (panel_entries, panel_selected) = field
                     .composite_list_panel()
                     .unwrap_or_else(|| (Vec::new(), 0));
@@ -230,7 +296,6 @@ impl App {
             }
             FieldKind::KeyValue(_) => {
                 let pointer = field.schema.pointer.clone();
-                let label = field.schema.display_label();
                 let (panel_entries, panel_selected) = field
                     .composite_list_panel()
                     .unwrap_or_else(|| (Vec::new(), 0));
@@ -263,11 +328,16 @@ impl App {
             FieldKind::Array(inner)
                 if matches!(
                     inner.as_ref(),
-                    FieldKind::String | FieldKind::Integer | FieldKind::Number | FieldKind::Boolean
+                    FieldKind::String
+                        | FieldKind::Integer
+                        | FieldKind::Number
+                        | FieldKind::Boolean
+                        | FieldKind::Array(_)
+                        | FieldKind::KeyValue(_)
+                        | FieldKind::Tuple(_)
                 ) =>
             {
                 let pointer = field.schema.pointer.clone();
-                let label = field.schema.display_label();
                 let (panel_entries, panel_selected) = field
                     .composite_list_panel()
                     .unwrap_or_else(|| (Vec::new(), 0));
@@ -308,19 +378,20 @@ impl App {
         let Some(editor) = self.composite_editor.take() else {
             return;
         };
+        let before = (commit && editor.dirty()).then(|| self.host_form_mut().checkpoint());
         let pointer = editor.field_pointer.clone();
         let mut restored = false;
         match editor.target {
             CompositeOverlayTarget::Field => {
                 if let OverlaySession::Composite(session) = editor.session
-                    && let Some(field) = self.form_state.field_mut_by_pointer(&pointer)
+                    && let Some(field) = self.host_form_mut().field_mut_by_pointer(&pointer)
                 {
                     field.close_composite_editor(session, commit);
                 }
             }
             CompositeOverlayTarget::ListEntry { entry_index } => {
                 if let OverlaySession::Composite(session) = editor.session
-                    && let Some(field) = self.form_state.field_mut_by_pointer(&pointer)
+                    && let Some(field) = self.host_form_mut().field_mut_by_pointer(&pointer)
                 {
                     field.close_composite_list_editor(entry_index, session, commit);
                 }
@@ -328,7 +399,7 @@ impl App {
             CompositeOverlayTarget::KeyValueEntry { entry_index } => {
                 if let OverlaySession::KeyValue(ref session) = editor.session
                     && commit
-                    && let Some(field) = self.form_state.field_mut_by_pointer(&pointer)
+                    && let Some(field) = self.host_form_mut().field_mut_by_pointer(&pointer)
                     && let Err(err) = field.close_key_value_editor(entry_index, session, true)
                 {
                     self.status.set_raw(&err.message);
@@ -340,7 +411,7 @@ impl App {
             CompositeOverlayTarget::ArrayEntry { entry_index } => {
                 if let OverlaySession::Array(ref session) = editor.session
                     && commit
-                    && let Some(field) = self.form_state.field_mut_by_pointer(&pointer)
+                    && let Some(field) = self.host_form_mut().field_mut_by_pointer(&pointer)
                     && let Err(err) = field.close_scalar_array_editor(entry_index, session, true)
                 {
                     self.status.set_raw(&err.message);
@@ -353,7 +424,20 @@ impl App {
         if restored {
             return;
         }
+        if let Some(before) = before {
+            self.host_form_mut().record_change(before, None);
+        }
         self.popup = None;
+        if let Some(parent) = self.overlay_stack.pop() {
+            self.composite_editor = Some(parent);
+            if commit {
+                self.status.value_updated();
+                self.run_overlay_validation();
+            } else {
+                self.status.ready();
+            }
+            return;
+        }
         if commit {
             self.exit_armed = false;
             self.status.value_updated();
@@ -384,6 +468,7 @@ impl App {
             .options
             .keymap
             .resolve(self.input_router.classify(&key));
+        let dispatch = self.route_text_keys(dispatch, key);
         match dispatch {
             CommandDispatch::Form(command) => {
                 if let Some(editor) = self.composite_editor.as_mut() {
@@ -408,6 +493,13 @@ impl App {
 
     fn handle_overlay_app_command(&mut self, command: AppCommand) -> Result<bool> {
         match command {
+            AppCommand::EditComposite if self.overlay_focus_opens_editor() => {
+                if let Some(editor) = self.composite_editor.as_mut() {
+                    editor.exit_armed = false;
+                }
+                self.try_open_composite_editor();
+                return Ok(true);
+            }
             AppCommand::Save | AppCommand::EditComposite => {
                 if let Some(editor) = self.composite_editor.as_mut() {
                     editor.exit_armed = false;
@@ -424,6 +516,33 @@ impl App {
                     return Ok(true);
                 }
             }
+            AppCommand::ToggleReveal => {
+                if self.toggle_focused_reveal() {
+                    return Ok(true);
+                }
+            }
+            AppCommand::ToggleAdvanced => {
+                self.toggle_advanced_fields();
+                return Ok(true);
+            }
+            AppCommand::ToggleWarnings => {
+                self.toggle_warnings();
+                return Ok(true);
+            }
+            AppCommand::Undo => {
+                self.step_history(false);
+                return Ok(true);
+            }
+            AppCommand::Redo => {
+                self.step_history(true);
+                return Ok(true);
+            }
+            AppCommand::ToggleNull => {
+                if let Some(pointer) = self.toggle_focused_null() {
+                    self.validate_overlay_field(pointer);
+                    return Ok(true);
+                }
+            }
             AppCommand::ResetStatus => {
                 self.status.ready();
             }
@@ -455,18 +574,45 @@ impl App {
         if let Some(editor) = self.composite_editor.as_mut() {
             editor.exit_armed = false;
             let label = editor.field_label.clone();
+            if let Some(field) = editor.form_state().focused_field()
+                && field.is_read_only()
+            {
+                self.status
+                    .read_only(&format!("{} › {}", label, field.schema.display_label()));
+                return;
+            }
+            let Some(before) = editor.form_state_mut().field_checkpoint() else {
+                return;
+            };
             if let Some(field) = editor.form_state_mut().focused_field_mut()
                 && field.handle_key(event)
             {
                 self.status
                     .editing(&format!("{} › {}", label, field.schema.display_label()));
-                if let Some(pointer) = Some(field.schema.pointer.clone()) {
-                    self.validate_overlay_field(pointer);
+                let pointer = field.schema.pointer.clone();
+                editor.form_state_mut().record_field_change(before);
+                if pointer == "/key" && self.sync_key_value_route() {
+                    self.setup_overlay_validator();
+                    return;
                 }
+                self.validate_overlay_field(pointer);
             }
         }
     }
 
+    /// Re-targets a key/value entry overlay at the `patternProperties` schema
+    /// its key now matches.
+    pub(super) fn sync_key_value_route(&mut self) -> bool {
+        match self
+            .composite_editor
+            .as_mut()
+            .map(|editor| &mut editor.session)
+        {
+            Some(OverlaySession::KeyValue(session)) => session.sync_route(),
+            _ => false,
+        }
+    }
+
     fn validate_overlay_field(&mut self, pointer: String) {
         let Some(editor) = self.composite_editor.as_mut() else {
             return;
@@ -489,15 +635,12 @@ impl App {
     ) {
         match owner {
             PopupOwner::Root => {
-                if let Some(field) = self.form_state.field_mut_by_pointer(pointer) {
-                    apply_selection_to_field(field, selection, multi);
-                }
+                record_selection(&mut self.form_state, pointer, selection, multi);
             }
             PopupOwner::Composite => {
                 if let Some(editor) = &mut self.composite_editor
-                    && let Some(field) = editor.form_state_mut().field_mut_by_pointer(pointer)
+                    && record_selection(editor.form_state_mut(), pointer, selection, multi)
                 {
-                    apply_selection_to_field(field, selection, multi);
                     self.run_overlay_validation();
                 }
             }
@@ -531,14 +674,42 @@ impl App {
         }
     }
 
-    pub(super) fn overlay_targets_pointer(&self, pointer: &str) -> bool {
+    /// The form that owns the active overlay's field: the suspended parent
+    /// overlay when nested, otherwise the root form.
+    fn host_form_mut(&mut self) -> &mut FormState {
+        match self.overlay_stack.last_mut() {
+            Some(parent) => parent.form_state_mut(),
+            None => &mut self.form_state,
+        }
+    }
+
+    /// The form currently receiving input.
+    pub(super) fn active_form(&self) -> &FormState {
+        match self.composite_editor.as_ref() {
+            Some(editor) => editor.form_state(),
+            None => &self.form_state,
+        }
+    }
+
+    pub(super) fn active_form_mut(&mut self) -> &mut FormState {
+        match self.composite_editor.as_mut() {
+            Some(editor) => editor.form_state_mut(),
+            None => &mut self.form_state,
+        }
+    }
+
+    fn overlay_focus_opens_editor(&self) -> bool {
         self.composite_editor
             .as_ref()
-            .map(|editor| editor.field_pointer == pointer)
-            .unwrap_or(false)
+            .and_then(|editor| editor.form_state().focused_field())
+            .is_some_and(|field| opens_overlay(&field.schema.kind))
     }
 
     pub(super) fn refresh_list_overlay_panel(&mut self) {
+        let host = match self.overlay_stack.last() {
+            Some(parent) => parent.form_state(),
+            None => &self.form_state,
+        };
         let Some(editor) = self.composite_editor.as_mut() else {
             return;
         };
@@ -550,13 +721,15 @@ impl App {
         ) {
             return;
         }
-        let pointer = editor.field_pointer.clone();
-        let (panel, label, idx) = match self.form_state.field_by_pointer(&pointer) {
-            Some(field) => (
-                field.composite_list_panel(),
-                field.composite_list_selected_label(),
-                field.composite_list_selected_index(),
-            ),
+        let (panel, label, idx) = match host.field_by_pointer(&editor.field_pointer) {
+            Some(field) => {
+                editor.list_duplicates = field.composite_list_duplicates();
+                (
+                    field.composite_list_panel(),
+                    field.composite_list_selected_label(),
+                    field.composite_list_selected_index(),
+                )
+            }
             None => return,
         };
         if let Some((entries, selected)) = panel {
@@ -603,7 +776,14 @@ mod tests {
             kind: FieldKind::Array(Box::new(FieldKind::String)),
             required: false,
             default: Some(json!(["GET"])),
+            read_only: false,
+            write_only: false,
+            nullable: false,
+            item_bounds: Default::default(),
+            ui: Default::default(),
             metadata: HashMap::new(),
+            visible_when: Vec::new(),
+            required_when: Vec::new(),
         };
         FieldState::from_schema(schema)
     }
@@ -616,6 +796,7 @@ mod tests {
             path: vec!["app".to_string()],
             depth: 0,
             fields: vec![scalar_array_field_state()],
+            hidden_fields: Vec::new(),
             scroll_offset: 0,
         };
         let form_state = FormState::from_sections("app", "App", None, vec![section]);
@@ -623,6 +804,71 @@ mod tests {
         App::new(form_state, validator, UiOptions::default())
     }
 
+    fn build_app_with_matrix() -> App {
+        let schema = FieldSchema {
+            name: "matrix".to_string(),
+            path: vec!["matrix".to_string()],
+            pointer: "/matrix".to_string(),
+            title: "Matrix".to_string(),
+            description: None,
+            section_id: "app".to_string(),
+            kind: FieldKind::Array(Box::new(FieldKind::Array(Box::new(FieldKind::String)))),
+            required: false,
+            default: Some(json!([["a"]])),
+            read_only: false,
+            write_only: false,
+            nullable: false,
+            item_bounds: Default::default(),
+            ui: Default::default(),
+            metadata: HashMap::new(),
+            visible_when: Vec::new(),
+            required_when: Vec::new(),
+        };
+        let form_state = FormState::from_sections(
+            "app",
+            "App",
+            None,
+            vec![SectionState {
+                id: "section".to_string(),
+                title: "Section".to_string(),
+                description: None,
+                path: Vec::new(),
+                depth: 0,
+                fields: vec![FieldState::from_schema(schema)],
+                hidden_fields: Vec::new(),
+                scroll_offset: 0,
+            }],
+        );
+        let validator = validator_for(&json!({"type": "object"})).expect("validator");
+        App::new(form_state, validator, UiOptions::default())
+    }
+
+    #[test]
+    fn ctrl_e_inside_overlay_opens_nested_collection() {
+        let mut app = build_app_with_matrix();
+        app.try_open_composite_editor();
+        app.handle_overlay_app_command(AppCommand::EditComposite)
+            .expect("command handled");
+        assert_eq!(app.overlay_stack.len(), 1);
+        let nested = app.composite_editor.as_mut().expect("nested overlay");
+        assert_eq!(nested.field_label, "Matrix › Matrix item (value)");
+        nested
+            .form_state_mut()
+            .focused_field_mut()
+            .expect("inner value")
+            .seed_value(&json!("b"));
+
+        app.close_composite_editor(true);
+        assert!(app.overlay_stack.is_empty());
+        assert!(app.composite_editor.is_some());
+        app.close_composite_editor(true);
+        assert!(app.composite_editor.is_none());
+        assert_eq!(
+            app.form_state.try_build_value().unwrap(),
+            json!({"matrix": [["b"]]})
+        );
+    }
+
     #[test]
     fn ctrl_e_opens_scalar_array_overlay() {
         let mut app = build_app_with_scalar_array();
@@ -0,0 +1,88 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use serde_json::Value;
+
+use crate::app::{
+    input::{AppCommand, CommandDispatch},
+    review::{ReviewState, diff_values},
+};
+
+use super::App;
+
+impl App {
+    /// Validates and, when the value differs from the last save, opens the
+    /// review listing the changes; the result is stored once confirmed.
+    pub(super) fn on_save(&mut self) {
+        let Some(value) = self.run_validation(true) else {
+            return;
+        };
+        let changes = diff_values(&self.baseline, &value);
+        if !self.options.review_changes || changes.is_empty() {
+            self.store_result(value);
+            return;
+        }
+        let review = ReviewState::new(value, changes, &self.form_state);
+        self.status.set_raw(format!(
+            "Review {} change(s): Ctrl+S to save, Enter to go to a field, Esc to go back",
+            review.len()
+        ));
+        self.review = Some(review);
+    }
+
+    fn store_result(&mut self, value: Value) {
+        self.status
+            .set_raw("Configuration saved. Press Ctrl+Q to exit.");
+        self.baseline = value.clone();
+        self.result = Some(value);
+        self.form_state.mark_clean();
+        self.exit_armed = false;
+    }
+
+    pub(super) fn handle_review_key(&mut self, key: KeyEvent) {
+        let Some(review) = self.review.as_mut() else {
+            return;
+        };
+        match key.code {
+            KeyCode::Up => review.select_previous(),
+            KeyCode::Down => review.select_next(),
+            KeyCode::Esc => {
+                self.review = None;
+                self.status.set_raw("Save cancelled; nothing was written");
+            }
+            KeyCode::Enter => {
+                let Some(pointer) = review.selected_pointer().map(str::to_string) else {
+                    return;
+                };
+                if !self.form_state.focus_pointer(&pointer) {
+                    self.status
+                        .set_raw(format!("{pointer} is not shown in the form"));
+                    return;
+                }
+                self.review = None;
+                let label = self
+                    .form_state
+                    .focused_field()
+                    .map(|field| field.schema.display_label())
+                    .unwrap_or_default();
+                self.status.set_raw(format!("Changed: {label} ({pointer})"));
+            }
+            _ => {
+                let dispatch = self
+                    .options
+                    .keymap
+                    .resolve(self.input_router.classify(&key));
+                match dispatch {
+                    CommandDispatch::App(AppCommand::Save) => {
+                        if let Some(review) = self.review.take() {
+                            self.store_result(review.into_value());
+                        }
+                    }
+                    CommandDispatch::App(AppCommand::Quit) => {
+                        self.review = None;
+                        self.status.set_raw("Save cancelled; nothing was written");
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,62 @@
+use crossterm::event::{KeyCode, KeyEvent};
+
+use super::super::input::{AppCommand, CommandDispatch};
+use super::App;
+use crate::form::{FieldState, FormCommand, field::components::ComponentKind};
+
+impl App {
+    /// Hands keys bound to app commands to the focused text input where the
+    /// command has nothing to act on: `Ctrl+←/→` outside lists is a word
+    /// motion, and in multi-line editors `Enter` starts a new line and `↑/↓`
+    /// move between lines until the caret reaches the first or last one.
+    pub(super) fn route_text_keys(
+        &self,
+        dispatch: CommandDispatch,
+        key: KeyEvent,
+    ) -> CommandDispatch {
+        let Some(field) = self
+            .active_form()
+            .focused_field()
+            .filter(|field| field.text_cursor().is_some())
+        else {
+            return dispatch;
+        };
+        let to_field = match &dispatch {
+            CommandDispatch::App(AppCommand::ListSelect(_)) => self.list_field_pointer().is_none(),
+            CommandDispatch::App(AppCommand::TogglePopup) => is_multi_line(field),
+            CommandDispatch::Form(FormCommand::FocusPrevField) if key.code == KeyCode::Up => {
+                is_multi_line(field) && caret_has_line(field, false)
+            }
+            CommandDispatch::Form(FormCommand::FocusNextField) if key.code == KeyCode::Down => {
+                is_multi_line(field) && caret_has_line(field, true)
+            }
+            _ => false,
+        };
+        if to_field {
+            CommandDispatch::Input(key)
+        } else {
+            dispatch
+        }
+    }
+}
+
+fn is_multi_line(field: &FieldState) -> bool {
+    matches!(
+        field.component_kind(),
+        ComponentKind::Textarea | ComponentKind::Json
+    )
+}
+
+/// Whether there is a line below (or above) the caret to move to.
+fn caret_has_line(field: &FieldState, below: bool) -> bool {
+    let Some(cursor) = field.text_cursor() else {
+        return false;
+    };
+    let text = field.display_value();
+    let offset = cursor.offset.min(text.len());
+    if below {
+        text[offset..].contains('\n')
+    } else {
+        text[..offset].contains('\n')
+    }
+}
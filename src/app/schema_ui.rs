@@ -1,7 +1,9 @@
 use anyhow::{Context, Result};
-use jsonschema::validator_for;
-use serde_json::Value;
-use std::{sync::Arc, time::Duration};
+use schemars::{JsonSchema, r#gen::SchemaSettings};
+use serde::{Serialize, de::DeserializeOwned};
+use serde_json::{Map, Value};
+use std::{path::Path, sync::Arc, time::Duration};
+use url::Url;
 
 use crate::{
     domain::parse_form_schema,
@@ -10,6 +12,11 @@ use crate::{
         self, DocumentFormat,
         output::{self, OutputOptions},
     },
+    layout::{Diagnostic, FormSchema},
+    schema::{
+        bundle::bundle_external_refs,
+        retriever::{SchemaRetriever, SharedRetriever},
+    },
 };
 
 use super::{input::KeyBindingMap, keymap::KeymapStore, options::UiOptions, runtime::App};
@@ -20,6 +27,10 @@ pub struct SchemaUI {
     title: Option<String>,
     options: UiOptions,
     output: Option<OutputOptions>,
+    base_uri: Option<Url>,
+    retriever: SharedRetriever,
+    /// Data the form was seeded with; the save review diffs against it.
+    seed: Option<Value>,
 }
 
 impl SchemaUI {
@@ -29,6 +40,9 @@ impl SchemaUI {
             title: None,
             options: UiOptions::default(),
             output: None,
+            base_uri: None,
+            retriever: SharedRetriever::default(),
+            seed: None,
         }
     }
 
@@ -39,17 +53,34 @@ impl SchemaUI {
 
     pub fn from_data_value(value: Value) -> Self {
         let schema = io::input::schema_from_data_value(&value);
-        Self::new(schema)
+        Self {
+            seed: Some(value),
+            ..Self::new(schema)
+        }
     }
 
     pub fn from_data_str(contents: &str, format: DocumentFormat) -> Result<Self> {
-        let schema = io::input::schema_from_data_str(contents, format)?;
-        Ok(Self::new(schema))
+        let value = io::input::parse_document_str(contents, format)?;
+        Ok(Self::from_data_value(value))
+    }
+
+    /// Schema generated from `T` with schemars (draft-07); pair with
+    /// [`SchemaUI::run_typed`] to get a `T` back.
+    pub fn for_type<T: JsonSchema>() -> Self {
+        let root = SchemaSettings::draft07()
+            .into_generator()
+            .into_root_schema_for::<T>();
+        let schema = serde_json::to_value(root).expect("schemars schemas serialize to JSON");
+        Self::new(schema)
     }
 
     pub fn from_schema_and_data(schema: Value, defaults: Value) -> Self {
-        let enriched = io::input::schema_with_defaults(&schema, &defaults);
-        Self::new(enriched)
+        Self::new(schema).with_default_data(&defaults)
+    }
+
+    /// The schema the session will render, after defaults were merged in.
+    pub fn schema(&self) -> &Value {
+        &self.schema
     }
 
     pub fn with_title(mut self, title: impl Into<String>) -> Self {
@@ -67,11 +98,46 @@ impl SchemaUI {
         self
     }
 
+    /// Resolve relative `$ref`s against the schema file at `path`.
+    pub fn with_schema_path(mut self, path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let absolute = path
+            .canonicalize()
+            .with_context(|| format!("failed to locate schema {}", path.display()))?;
+        let uri = Url::from_file_path(&absolute)
+            .map_err(|_| anyhow::anyhow!("invalid schema path {}", absolute.display()))?;
+        self.base_uri = Some(uri);
+        Ok(self)
+    }
+
+    /// Resolve relative `$ref`s against an absolute URI (for example an
+    /// `https://` location when the schema was downloaded).
+    pub fn with_base_uri(mut self, uri: &str) -> Result<Self> {
+        self.base_uri = Some(Url::parse(uri).with_context(|| format!("invalid base uri '{uri}'"))?);
+        Ok(self)
+    }
+
+    /// Replace the retriever used for external `$ref`s, both during layout
+    /// and validation.
+    pub fn with_retriever(mut self, retriever: impl SchemaRetriever + 'static) -> Self {
+        self.retriever = SharedRetriever(Arc::new(retriever));
+        self
+    }
+
+    /// Merge `defaults` into the schema; the save review starts from them.
     pub fn with_default_data(mut self, defaults: &Value) -> Self {
         self.schema = io::input::schema_with_defaults(&self.schema, defaults);
+        self.seed = Some(defaults.clone());
         self
     }
 
+    /// Seed the form with an existing value, e.g. the config currently in use.
+    pub fn with_initial<T: Serialize>(self, initial: &T) -> Result<Self> {
+        let defaults =
+            serde_json::to_value(initial).context("failed to serialize the initial value")?;
+        Ok(self.with_default_data(&defaults))
+    }
+
     pub fn with_keymap(mut self, keymap: KeyBindingMap) -> Self {
         self.options = self.options.clone().with_keymap(keymap);
         self
@@ -98,24 +164,70 @@ impl SchemaUI {
         self
     }
 
+    pub fn with_review_changes(mut self, review: bool) -> Self {
+        self.options = self.options.clone().with_review_changes(review);
+        self
+    }
+
     pub fn with_tick_rate(mut self, tick_rate: Duration) -> Self {
         self.options = self.options.clone().with_tick_rate(tick_rate);
         self
     }
 
+    /// The form this session would render, resolving external `$ref`s
+    /// through the configured retriever and base location.
+    pub fn form_schema(&self) -> Result<FormSchema> {
+        let schema = bundle_external_refs(
+            &self.schema,
+            self.base_uri.as_ref(),
+            self.retriever.0.as_ref(),
+        )?;
+        parse_form_schema(&schema).map(|form| FormSchema::from(&form))
+    }
+
+    /// Compatibility report: every unsupported or ignored construct with its
+    /// JSON pointer. The same list is shown in the warning panel on `run`.
+    pub fn diagnostics(&self) -> Result<Vec<Diagnostic>> {
+        self.form_schema().map(|form| form.diagnostics)
+    }
+
+    /// Like [`SchemaUI::run`], deserializing the saved value into `T`.
+    pub fn run_typed<T: DeserializeOwned>(self) -> Result<T> {
+        let value = self.run()?;
+        serde_json::from_value(value).context("saved value does not match the target type")
+    }
+
     pub fn run(self) -> Result<Value> {
         let SchemaUI {
             schema,
             title: _,
             options,
             output,
+            base_uri,
+            retriever,
+            seed,
         } = self;
 
-        let validator = validator_for(&schema).context("failed to compile JSON schema")?;
+        let schema = bundle_external_refs(&schema, base_uri.as_ref(), retriever.0.as_ref())?;
+        let mut validator_options = jsonschema::options().with_retriever(retriever);
+        if let Some(base) = base_uri.as_ref() {
+            validator_options = validator_options.with_base_uri(base.as_str());
+        }
+        let validator = validator_options
+            .build(&schema)
+            .context("failed to compile JSON schema")?;
         let form_schema = parse_form_schema(&schema)?;
         let form_state = FormState::from_schema(&form_schema);
-
-        let mut app = App::new(form_state, validator, options);
+        let warnings = FormSchema::from(&form_schema)
+            .diagnostics
+            .iter()
+            .map(Diagnostic::to_string)
+            .collect();
+
+        let baseline = seed.unwrap_or_else(|| Value::Object(Map::new()));
+        let mut app = App::new(form_state, validator, options)
+            .with_warnings(warnings)
+            .with_baseline(baseline);
         let result = app.run()?;
         if let Some(settings) = output {
             output::emit(&result, &settings)?;
@@ -30,6 +30,10 @@ impl StatusLine {
         self.message = format!("Editing {label}");
     }
 
+    pub fn read_only(&mut self, label: &str) {
+        self.message = format!("{label} is read-only");
+    }
+
     pub fn value_updated(&mut self) {
         self.message = "Value updated".to_string();
     }
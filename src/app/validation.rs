@@ -16,6 +16,7 @@ pub enum ValidationOutcome {
 }
 
 pub fn validate_form(form_state: &mut FormState, validator: &Validator) -> ValidationOutcome {
+    form_state.refresh_conditions();
     match form_state.try_build_value() {
         Ok(value) => {
             if validator.is_valid(&value) {
@@ -3,6 +3,7 @@ mod schema;
 
 pub use parser::parse_form_schema;
 pub use schema::{
-    CompositeField, CompositeMode, CompositeVariant, FieldKind, FieldSchema, FormSchema,
-    FormSection, KeyValueField, RootSection,
+    CompositeField, CompositeMode, CompositeVariant, EnumOption, FieldCondition, FieldKind,
+    FieldSchema, FieldUi, FormSchema, FormSection, ItemBounds, KeyPattern, KeyValueField,
+    KeyValueRoute, RootSection, SchemaDiagnostic, StringFormat, TupleField, Widget,
 };
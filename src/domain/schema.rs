@@ -1,5 +1,7 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, sync::Arc};
 
+use jsonschema::Validator;
+use regex::Regex;
 use serde_json::Value;
 
 #[derive(Debug, Clone)]
@@ -9,6 +11,13 @@ pub struct FormSchema {
     #[allow(dead_code)]
     pub description: Option<String>,
     pub roots: Vec<RootSection>,
+    pub diagnostics: Vec<SchemaDiagnostic>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SchemaDiagnostic {
+    pub pointer: String,
+    pub message: String,
 }
 
 #[derive(Debug, Clone)]
@@ -32,20 +41,92 @@ pub struct FormSection {
 #[derive(Debug, Clone, PartialEq)]
 pub enum FieldKind {
     String,
+    /// String with a recognized `format`, edited by a dedicated widget.
+    Formatted(StringFormat),
     Integer,
     Number,
     Boolean,
-    Enum(Vec<String>),
+    Enum(Vec<EnumOption>),
     Array(Box<FieldKind>),
     Json,
     Composite(Box<CompositeField>),
     KeyValue(Box<KeyValueField>),
+    Tuple(Box<TupleField>),
+    /// `const`: shown locked and always written out.
+    Const(Value),
+    /// `type` lists several non-null types; the user picks one at a time.
+    MultiType(Vec<FieldKind>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StringFormat {
+    Date,
+    DateTime,
+    Time,
+    Email,
+    Uri,
+    Ipv4,
+    Ipv6,
+    Uuid,
+}
+
+impl StringFormat {
+    pub fn from_keyword(keyword: &str) -> Option<Self> {
+        match keyword {
+            "date" => Some(Self::Date),
+            "date-time" => Some(Self::DateTime),
+            "time" => Some(Self::Time),
+            "email" => Some(Self::Email),
+            "uri" => Some(Self::Uri),
+            "ipv4" => Some(Self::Ipv4),
+            "ipv6" => Some(Self::Ipv6),
+            "uuid" => Some(Self::Uuid),
+            _ => None,
+        }
+    }
+
+    pub fn keyword(self) -> &'static str {
+        match self {
+            Self::Date => "date",
+            Self::DateTime => "date-time",
+            Self::Time => "time",
+            Self::Email => "email",
+            Self::Uri => "uri",
+            Self::Ipv4 => "ipv4",
+            Self::Ipv6 => "ipv6",
+            Self::Uuid => "uuid",
+        }
+    }
+}
+
+/// One `enum`/`const` choice: the typed value written to the output and the
+/// label shown to the user (`x-enumNames`, a `oneOf` branch `title`, or the
+/// value itself).
+#[derive(Debug, Clone, PartialEq)]
+pub struct EnumOption {
+    pub value: Value,
+    pub label: String,
+}
+
+impl EnumOption {
+    pub fn new(value: Value) -> Self {
+        let label = match &value {
+            Value::String(text) => text.clone(),
+            other => other.to_string(),
+        };
+        Self { value, label }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct CompositeField {
     pub mode: CompositeMode,
     pub variants: Vec<CompositeVariant>,
+    /// Property named by an OpenAPI `discriminator`.
+    pub discriminator: Option<String>,
+    /// A `$ref` back to an ancestor object: starts unset and its sub-form is
+    /// only laid out when opened.
+    pub recursive: bool,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -60,6 +141,68 @@ pub struct KeyValueField {
     pub value_schema: Value,
     pub value_kind: Box<FieldKind>,
     pub entry_schema: Value,
+    /// `additionalProperties` next to declared properties: entries are
+    /// written into the enclosing object instead of under a key of their own.
+    pub inline: bool,
+    /// Declared property names an inline map may not use as keys.
+    pub reserved_keys: Vec<String>,
+    /// `patternProperties`, checked in order. Keys matching none of them use
+    /// the `value_*` schema above.
+    pub routes: Vec<KeyValueRoute>,
+}
+
+impl KeyValueField {
+    /// The first route whose pattern matches `key`.
+    pub fn route_for(&self, key: &str) -> Option<usize> {
+        self.routes
+            .iter()
+            .position(|route| route.pattern.is_match(key))
+    }
+}
+
+/// A `patternProperties` pattern, compiled once during layout.
+#[derive(Debug, Clone)]
+pub struct KeyPattern(Regex);
+
+impl KeyPattern {
+    pub fn new(pattern: &str) -> Result<Self, regex::Error> {
+        Regex::new(pattern).map(Self)
+    }
+
+    pub fn as_str(&self) -> &str {
+        self.0.as_str()
+    }
+
+    pub fn is_match(&self, key: &str) -> bool {
+        self.0.is_match(key)
+    }
+}
+
+impl PartialEq for KeyPattern {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+/// Value schema for the keys of a map matching one `patternProperties` pattern.
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeyValueRoute {
+    pub pattern: KeyPattern,
+    pub value_title: String,
+    pub value_description: Option<String>,
+    pub value_default: Option<Value>,
+    pub value_schema: Value,
+    pub value_kind: Box<FieldKind>,
+    pub entry_schema: Value,
+}
+
+/// Positional array (`prefixItems` or array-form `items`). Each position is
+/// edited as its own field named after its index.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TupleField {
+    pub items: Vec<FieldSchema>,
+    pub allow_additional: bool,
+    pub entry_schema: Value,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -74,9 +217,12 @@ pub struct CompositeVariant {
     pub title: String,
     pub description: Option<String>,
     pub schema: Value,
+    /// Discriminator values (from `discriminator.mapping` or the `$ref` name)
+    /// that select this variant.
+    pub discriminator_values: Vec<String>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct FieldSchema {
     pub name: String,
     pub path: Vec<String>,
@@ -88,11 +234,125 @@ pub struct FieldSchema {
     pub kind: FieldKind,
     pub required: bool,
     pub default: Option<Value>,
-    #[allow(dead_code)]
+    /// `readOnly`: shown but never edited.
+    pub read_only: bool,
+    /// `writeOnly`: masked until the user reveals it.
+    pub write_only: bool,
+    /// `type` lists `null`: the field can be explicitly set to `null`.
+    pub nullable: bool,
+    /// Array constraints enforced by the list editors.
+    pub item_bounds: ItemBounds,
+    pub ui: FieldUi,
+    /// `x-*` keys outside the `FieldUi` vocabulary.
     pub metadata: HashMap<String, Value>,
+    pub visible_when: Vec<FieldCondition>,
+    pub required_when: Vec<FieldCondition>,
+}
+
+/// `minItems` / `maxItems` / `uniqueItems` of an array field.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ItemBounds {
+    pub min: Option<usize>,
+    pub max: Option<usize>,
+    pub unique: bool,
+}
+
+impl ItemBounds {
+    /// Why another entry cannot be added to a list of `len` entries.
+    pub fn add_blocked(&self, len: usize) -> Option<String> {
+        self.max
+            .filter(|max| len >= *max)
+            .map(|max| format!("maxItems reached: at most {max} entries"))
+    }
+
+    /// Why an entry cannot be removed from a list of `len` entries.
+    pub fn remove_blocked(&self, len: usize) -> Option<String> {
+        self.min
+            .filter(|min| len > 0 && len <= *min)
+            .map(|min| format!("minItems reached: at least {min} entries"))
+    }
+}
+
+/// Presentation hints from the `x-` vocabulary. Other `x-*` keys go to
+/// `FieldSchema::metadata`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FieldUi {
+    /// `x-order`: ordered fields come first (ascending), the rest keep schema order.
+    pub order: Option<i64>,
+    /// `x-hidden`: never shown, but the value is still written out.
+    pub hidden: bool,
+    /// `x-widget`: overrides the control picked from the field kind.
+    pub widget: Option<Widget>,
+    /// `x-placeholder`: shown dimmed while the field is empty.
+    pub placeholder: Option<String>,
+    /// `x-help`: extra guidance shown while the field is focused.
+    pub help: Option<String>,
+    /// `x-advanced`: collapsed until advanced fields are toggled on.
+    pub advanced: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Widget {
+    Textarea,
+    Password,
+    /// Bounds come from `minimum`/`maximum`, exclusive bounds one step
+    /// inward; the step from `multipleOf`.
+    Slider {
+        min: f64,
+        max: f64,
+        step: f64,
+    },
+    Radio,
+}
+
+/// Holds when validating the object at `scope` against `when` yields `matches`.
+/// `when` is compiled once, when the condition is built.
+#[derive(Debug, Clone)]
+pub struct FieldCondition {
+    pub scope: Vec<String>,
+    pub when: Value,
+    pub matches: bool,
+    validator: Arc<Validator>,
+}
+
+impl FieldCondition {
+    /// Fails with the compile error when `when` is not a usable schema.
+    pub fn new(scope: Vec<String>, when: Value, matches: bool) -> Result<Self, String> {
+        let validator = jsonschema::validator_for(&when).map_err(|err| err.to_string())?;
+        Ok(Self {
+            scope,
+            when,
+            matches,
+            validator: Arc::new(validator),
+        })
+    }
+
+    /// The same condition with the opposite expectation, e.g. for `else`.
+    pub fn negated(&self) -> Self {
+        Self {
+            matches: !self.matches,
+            ..self.clone()
+        }
+    }
+
+    /// Whether `scope_value` is valid against `when`.
+    pub fn accepts(&self, scope_value: &Value) -> bool {
+        self.validator.is_valid(scope_value)
+    }
+}
+
+impl PartialEq for FieldCondition {
+    fn eq(&self, other: &Self) -> bool {
+        self.scope == other.scope && self.when == other.when && self.matches == other.matches
+    }
 }
 
 impl FieldSchema {
+    /// `writeOnly` or `x-widget: password`.
+    pub fn is_secret(&self) -> bool {
+        self.write_only || self.ui.widget == Some(Widget::Password)
+    }
+
     pub fn display_label(&self) -> String {
         if self.title.eq_ignore_ascii_case(&self.name) {
             self.title.clone()
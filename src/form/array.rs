@@ -1,6 +1,6 @@
-use serde_json::{Number, Value, json};
+use serde_json::{Map, Number, Value, json};
 
-use crate::domain::FieldKind;
+use crate::domain::{CompositeMode, FieldKind, ItemBounds};
 
 use super::{
     error::FieldCoercionError, field::FieldState, section::SectionState, state::FormState,
@@ -20,6 +20,7 @@ struct ScalarArrayTemplate {
     description: Option<String>,
     entry_schema: Value,
     item_kind: FieldKind,
+    bounds: ItemBounds,
 }
 
 #[derive(Debug, Clone)]
@@ -43,15 +44,20 @@ impl ScalarArrayState {
         label: String,
         description: Option<String>,
         kind: &FieldKind,
+        bounds: ItemBounds,
         default: Option<&Value>,
     ) -> Self {
-        let entry_schema = json!({
+        let mut definitions = Map::new();
+        let mut entry_schema = json!({
             "type": "object",
             "required": ["value"],
             "properties": {
-                "value": kind_to_schema_fragment(kind),
+                "value": kind_to_schema_fragment(kind, &mut definitions),
             }
         });
+        if let Value::Object(map) = &mut entry_schema {
+            map.extend(definitions);
+        }
         let mut state = Self {
             pointer: pointer.to_string(),
             template: ScalarArrayTemplate {
@@ -59,6 +65,7 @@ impl ScalarArrayState {
                 description,
                 entry_schema,
                 item_kind: kind.clone(),
+                bounds,
             },
             entries: Vec::new(),
             selected: 0,
@@ -92,7 +99,27 @@ impl ScalarArrayState {
         changed
     }
 
+    pub fn add_blocked(&self) -> Option<String> {
+        self.template.bounds.add_blocked(self.entries.len())
+    }
+
+    pub fn remove_blocked(&self) -> Option<String> {
+        self.template.bounds.remove_blocked(self.entries.len())
+    }
+
+    /// Entries equal to another entry, when `uniqueItems` is set.
+    pub fn duplicates(&self) -> Vec<usize> {
+        if self.template.bounds.unique {
+            duplicate_indices(&self.entries)
+        } else {
+            Vec::new()
+        }
+    }
+
     pub fn add_entry(&mut self) -> bool {
+        if self.add_blocked().is_some() {
+            return false;
+        }
         let value = default_value(&self.template.item_kind);
         self.entries.push(value);
         if !self.entries.is_empty() {
@@ -102,7 +129,7 @@ impl ScalarArrayState {
     }
 
     pub fn remove_selected(&mut self) -> bool {
-        if self.entries.is_empty() {
+        if self.entries.is_empty() || self.remove_blocked().is_some() {
             return false;
         }
         let idx = self.selected.min(self.entries.len() - 1);
@@ -215,6 +242,18 @@ impl ScalarArrayState {
     }
 }
 
+/// Indices of every entry that has an equal twin elsewhere in the list.
+pub(crate) fn duplicate_indices(values: &[Value]) -> Vec<usize> {
+    (0..values.len())
+        .filter(|&index| {
+            values
+                .iter()
+                .enumerate()
+                .any(|(other, value)| other != index && *value == values[index])
+        })
+        .collect()
+}
+
 fn build_entry_form_state(template: &ScalarArrayTemplate, value: &Value) -> FormState {
     let schema = FieldSchemaStub::new(template, value.clone());
     let mut field_state = FieldState::from_schema(schema.into());
@@ -226,6 +265,7 @@ fn build_entry_form_state(template: &ScalarArrayTemplate, value: &Value) -> Form
         path: vec!["value".to_string()],
         depth: 0,
         fields: vec![field_state],
+        hidden_fields: Vec::new(),
         scroll_offset: 0,
     };
     FormState::from_sections(
@@ -262,35 +302,105 @@ impl From<FieldSchemaStub> for crate::domain::FieldSchema {
             kind: stub.template.item_kind,
             required: true,
             default: Some(stub.value),
+            read_only: false,
+            write_only: false,
+            nullable: false,
+            item_bounds: Default::default(),
+            ui: Default::default(),
             metadata: Default::default(),
+            visible_when: Vec::new(),
+            required_when: Vec::new(),
         }
     }
 }
 
-fn kind_to_schema_fragment(kind: &FieldKind) -> Value {
+/// Nested collections carry their own schemas; their `definitions`/`$defs`
+/// are hoisted into `definitions` so `#/...` references still resolve from
+/// the entry schema root.
+fn kind_to_schema_fragment(kind: &FieldKind, definitions: &mut Map<String, Value>) -> Value {
     match kind {
         FieldKind::String => json!({"type": "string"}),
+        FieldKind::Formatted(format) => json!({"type": "string", "format": format.keyword()}),
         FieldKind::Integer => json!({"type": "integer"}),
         FieldKind::Number => json!({"type": "number"}),
         FieldKind::Boolean => json!({"type": "boolean"}),
-        FieldKind::Enum(options) => json!({"type": "string", "enum": options}),
+        FieldKind::Enum(options) => json!({
+            "enum": options.iter().map(|option| option.value.clone()).collect::<Vec<_>>(),
+        }),
         FieldKind::Json => json!({"type": "object"}),
-        _ => json!({"type": "string"}),
+        FieldKind::Const(value) => json!({"const": value}),
+        FieldKind::Array(inner) => json!({
+            "type": "array",
+            "items": kind_to_schema_fragment(inner, definitions),
+        }),
+        FieldKind::KeyValue(template) => json!({
+            "type": "object",
+            "propertyNames": hoist_definitions(&template.key_schema, definitions),
+            "additionalProperties": hoist_definitions(&template.value_schema, definitions),
+        }),
+        FieldKind::Tuple(template) => {
+            let entry = hoist_definitions(&template.entry_schema, definitions);
+            let positions = (0..template.items.len())
+                .map(|index| entry["properties"][index.to_string()].clone())
+                .collect::<Vec<_>>();
+            let mut fragment = json!({"type": "array", "prefixItems": positions});
+            if !template.allow_additional {
+                fragment["items"] = Value::Bool(false);
+            }
+            fragment
+        }
+        FieldKind::Composite(template) => {
+            let variants = template
+                .variants
+                .iter()
+                .map(|variant| hoist_definitions(&variant.schema, definitions))
+                .collect::<Vec<_>>();
+            match template.mode {
+                CompositeMode::OneOf => json!({"oneOf": variants}),
+                CompositeMode::AnyOf => json!({"type": "array"}),
+            }
+        }
+        FieldKind::MultiType(kinds) => json!({
+            "anyOf": kinds
+                .iter()
+                .map(|kind| kind_to_schema_fragment(kind, definitions))
+                .collect::<Vec<_>>(),
+        }),
     }
 }
 
+fn hoist_definitions(schema: &Value, definitions: &mut Map<String, Value>) -> Value {
+    let mut schema = schema.clone();
+    if let Value::Object(map) = &mut schema {
+        for keyword in ["definitions", "$defs"] {
+            if let Some(Value::Object(defs)) = map.remove(keyword)
+                && let Value::Object(target) = definitions
+                    .entry(keyword)
+                    .or_insert_with(|| Value::Object(Map::new()))
+            {
+                target.extend(defs);
+            }
+        }
+    }
+    schema
+}
+
 fn default_value(kind: &FieldKind) -> Value {
     match kind {
-        FieldKind::String | FieldKind::Json => Value::String(String::new()),
+        FieldKind::String | FieldKind::Formatted(_) | FieldKind::Json => {
+            Value::String(String::new())
+        }
         FieldKind::Integer => Value::Number(0.into()),
         FieldKind::Number => Value::Number(Number::from_f64(0.0).unwrap()),
         FieldKind::Boolean => Value::Bool(false),
         FieldKind::Enum(options) => options
             .first()
-            .cloned()
-            .map(Value::String)
+            .map(|option| option.value.clone())
             .unwrap_or_else(|| Value::String(String::new())),
-        _ => Value::String(String::new()),
+        FieldKind::Const(value) => value.clone(),
+        FieldKind::Array(_) | FieldKind::Tuple(_) => Value::Array(Vec::new()),
+        FieldKind::KeyValue(_) | FieldKind::Composite(_) => Value::Object(Map::new()),
+        FieldKind::MultiType(kinds) => kinds.first().map(default_value).unwrap_or(Value::Null),
     }
 }
 
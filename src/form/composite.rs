@@ -2,15 +2,17 @@ use std::cell::{RefCell, RefMut};
 
 use serde_json::{Map, Value};
 
-use crate::domain::{CompositeField, CompositeMode, parse_form_schema};
+use crate::domain::{CompositeField, CompositeMode, ItemBounds, parse_form_schema};
 
-use super::{error::FieldCoercionError, state::FormState};
+use super::{array::duplicate_indices, error::FieldCoercionError, state::FormState};
 
 #[derive(Debug, Clone)]
 pub struct CompositeState {
     pointer: String,
     mode: CompositeMode,
     variants: Vec<CompositeVariantState>,
+    discriminator: Option<String>,
+    recursive: bool,
 }
 
 #[derive(Debug, Clone)]
@@ -21,6 +23,7 @@ pub struct CompositeVariantState {
     #[allow(dead_code)]
     description: Option<String>,
     schema: Value,
+    discriminator_values: Vec<String>,
     active: bool,
     form: RefCell<Option<FormState>>,
 }
@@ -52,6 +55,7 @@ pub struct CompositeVariantSummary {
 pub struct CompositeListState {
     pointer: String,
     template: CompositeField,
+    bounds: ItemBounds,
     entries: Vec<CompositeListEntry>,
     selected: usize,
     counter: usize,
@@ -64,10 +68,16 @@ struct CompositeListEntry {
 }
 
 impl CompositeListState {
-    pub fn new(pointer: &str, template: &CompositeField, defaults: Option<&Value>) -> Self {
+    pub fn new(
+        pointer: &str,
+        template: &CompositeField,
+        bounds: ItemBounds,
+        defaults: Option<&Value>,
+    ) -> Self {
         let mut state = Self {
             pointer: pointer.to_string(),
             template: template.clone(),
+            bounds,
             entries: Vec::new(),
             selected: 0,
             counter: 0,
@@ -103,7 +113,32 @@ impl CompositeListState {
         changed
     }
 
-    pub fn add_entry(&mut self) -> usize {
+    pub fn add_blocked(&self) -> Option<String> {
+        self.bounds.add_blocked(self.entries.len())
+    }
+
+    pub fn remove_blocked(&self) -> Option<String> {
+        self.bounds.remove_blocked(self.entries.len())
+    }
+
+    /// Entries equal to another entry, when `uniqueItems` is set.
+    pub fn duplicates(&self) -> Vec<usize> {
+        if !self.bounds.unique {
+            return Vec::new();
+        }
+        let values = self
+            .entries
+            .iter()
+            .map(|entry| entry.state.build_value(false).ok().flatten())
+            .map(|value| value.unwrap_or(Value::Null))
+            .collect::<Vec<_>>();
+        duplicate_indices(&values)
+    }
+
+    pub fn add_entry(&mut self) -> Option<usize> {
+        if self.add_blocked().is_some() {
+            return None;
+        }
         let entry_pointer = format!("{}/entry_{}", self.pointer, self.counter);
         self.counter += 1;
         let state = CompositeState::new(&entry_pointer, &self.template);
@@ -112,11 +147,11 @@ impl CompositeListState {
             state,
         });
         self.selected = self.entries.len().saturating_sub(1);
-        self.selected
+        Some(self.selected)
     }
 
     pub fn remove_selected(&mut self) -> bool {
-        if self.entries.is_empty() {
+        if self.entries.is_empty() || self.remove_blocked().is_some() {
             return false;
         }
         let idx = self.selected.min(self.entries.len() - 1);
@@ -229,7 +264,10 @@ impl CompositeState {
                 title: variant.title.clone(),
                 description: variant.description.clone(),
                 schema: variant.schema.clone(),
-                active: matches!(field.mode, CompositeMode::OneOf) && index == 0,
+                discriminator_values: variant.discriminator_values.clone(),
+                active: matches!(field.mode, CompositeMode::OneOf)
+                    && index == 0
+                    && !field.recursive,
                 form: RefCell::new(None),
             });
         }
@@ -238,10 +276,15 @@ impl CompositeState {
             pointer: pointer.to_string(),
             mode: field.mode.clone(),
             variants,
+            discriminator: field.discriminator.clone(),
+            recursive: field.recursive,
         }
     }
 
     pub fn summary(&self) -> String {
+        if self.recursive && self.selected_index().is_none() {
+            return "Not set".to_string();
+        }
         match self.mode {
             CompositeMode::OneOf => self
                 .variants
@@ -273,15 +316,31 @@ impl CompositeState {
         self.pointer = pointer.to_string();
     }
 
+    /// The discriminator property decides when present; otherwise the variant
+    /// whose `const` properties match the most keys wins, ties going to the
+    /// earlier variant.
     fn pick_variant_index(&self, value: &Value) -> usize {
-        if let Value::Object(obj) = value {
-            for (idx, variant) in self.variants.iter().enumerate() {
-                if variant.matches_value(obj) {
-                    return idx;
-                }
-            }
+        let Value::Object(obj) = value else {
+            return 0;
+        };
+        if let Some(property) = &self.discriminator
+            && let Some(tag) = obj.get(property)
+            && let Some(idx) = self
+                .variants
+                .iter()
+                .position(|variant| variant.is_tagged(property, tag))
+        {
+            return idx;
         }
-        0
+        self.variants
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, variant)| Some((idx, variant.const_matches(obj)?)))
+            .max_by(|(left_idx, left), (right_idx, right)| {
+                left.cmp(right).then(right_idx.cmp(left_idx))
+            })
+            .map(|(idx, _)| idx)
+            .unwrap_or(0)
     }
 
     pub fn seed_from_value(&mut self, value: &Value) -> Result<(), FieldCoercionError> {
@@ -300,6 +359,10 @@ impl CompositeState {
         Ok(())
     }
 
+    pub fn is_recursive(&self) -> bool {
+        self.recursive
+    }
+
     pub fn is_multi(&self) -> bool {
         matches!(self.mode, CompositeMode::AnyOf)
     }
@@ -492,7 +555,8 @@ impl CompositeVariantState {
             .expect("variant form should be initialized"))
     }
 
-    fn store_form(&self, form_state: FormState) {
+    fn store_form(&self, mut form_state: FormState) {
+        form_state.clear_history();
         *self.form.borrow_mut() = Some(form_state);
     }
 
@@ -514,11 +578,12 @@ impl CompositeVariantState {
                         lines.push("  • <empty>".to_string());
                     } else {
                         for field in &section.fields {
-                            lines.push(format!(
-                                "  • {} = {}",
-                                field.schema.display_label(),
+                            let value = if field.is_masked() {
+                                "••••••".to_string()
+                            } else {
                                 field.display_value()
-                            ));
+                            };
+                            lines.push(format!("  • {} = {}", field.schema.display_label(), value));
                         }
                     }
                 }
@@ -531,17 +596,27 @@ impl CompositeVariantState {
         })
     }
 
-    fn matches_value(&self, value: &Map<String, Value>) -> bool {
+    fn is_tagged(&self, property: &str, tag: &Value) -> bool {
+        tag.as_str()
+            .is_some_and(|tag| self.discriminator_values.iter().any(|value| value == tag))
+            || self.schema["properties"][property].get("const") == Some(tag)
+    }
+
+    /// Number of `const` properties matched by `value`, or `None` when one of
+    /// them contradicts it.
+    fn const_matches(&self, value: &Map<String, Value>) -> Option<usize> {
+        let mut matched = 0;
         if let Some(props) = self.schema.get("properties").and_then(Value::as_object) {
             for (key, schema) in props {
-                if let Some(expected) = schema.get("const")
-                    && value.get(key) != Some(expected)
-                {
-                    return false;
+                if let Some(expected) = schema.get("const") {
+                    if value.get(key) != Some(expected) {
+                        return None;
+                    }
+                    matched += 1;
                 }
             }
         }
-        true
+        Some(matched)
     }
 }
 
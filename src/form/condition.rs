@@ -0,0 +1,15 @@
+use serde_json::{Map, Value};
+
+use crate::domain::FieldCondition;
+
+pub(crate) fn condition_holds(condition: &FieldCondition, root: &Value) -> bool {
+    let scope = value_at(root, &condition.scope)
+        .cloned()
+        .unwrap_or_else(|| Value::Object(Map::new()));
+    condition.accepts(&scope) == condition.matches
+}
+
+fn value_at<'a>(value: &'a Value, path: &[String]) -> Option<&'a Value> {
+    path.iter()
+        .try_fold(value, |current, segment| current.as_object()?.get(segment))
+}
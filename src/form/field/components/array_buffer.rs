@@ -4,12 +4,12 @@ use crate::domain::{FieldKind, FieldSchema};
 use crate::form::error::FieldCoercionError;
 
 use super::helpers::handle_text_edit;
-use super::{ComponentKind, FieldComponent};
+use super::{ComponentKind, FieldComponent, LineEditor, TextCursor};
 use crate::form::field::convert::{array_to_string, array_value};
 
 #[derive(Debug, Clone)]
 pub struct ArrayBufferComponent {
-    buffer: String,
+    editor: LineEditor,
 }
 
 impl ArrayBufferComponent {
@@ -20,7 +20,9 @@ impl ArrayBufferComponent {
             .and_then(|value| value.as_array())
             .map(|items| array_to_string(items))
             .unwrap_or_default();
-        Self { buffer }
+        Self {
+            editor: LineEditor::new(buffer),
+        }
     }
 }
 
@@ -30,22 +32,32 @@ impl FieldComponent for ArrayBufferComponent {
     }
 
     fn display_value(&self, _schema: &FieldSchema) -> String {
-        format!("[{}]", self.buffer.trim())
+        format!("[{}]", self.editor.text())
     }
 
     fn handle_key(&mut self, schema: &FieldSchema, key: &crossterm::event::KeyEvent) -> bool {
-        handle_text_edit(&mut self.buffer, schema, key)
+        handle_text_edit(&mut self.editor, schema, key)
     }
 
     fn seed_value(&mut self, _schema: &FieldSchema, value: &Value) {
         if let Value::Array(items) = value {
-            self.buffer = array_to_string(items);
+            self.editor.set_text(array_to_string(items));
         }
     }
 
+    /// Shifted past the opening bracket of `display_value`.
+    fn text_cursor(&self) -> Option<TextCursor> {
+        let cursor = self.editor.cursor();
+        Some(TextCursor {
+            offset: cursor.offset + 1,
+            selection: cursor.selection.map(|range| range.start + 1..range.end + 1),
+            overwrite: cursor.overwrite,
+        })
+    }
+
     fn current_value(&self, schema: &FieldSchema) -> Result<Option<Value>, FieldCoercionError> {
         if let FieldKind::Array(inner) = &schema.kind {
-            array_value(&self.buffer, inner.as_ref(), schema)
+            array_value(self.editor.text(), inner.as_ref(), schema)
         } else {
             Ok(None)
         }
@@ -7,17 +7,28 @@ use crate::form::composite::{CompositeEditorSession, CompositeListEditorContext}
 use crate::form::error::FieldCoercionError;
 use crate::form::key_value::{KeyValueEditorContext, KeyValueEditorSession};
 
+use super::TextCursor;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ComponentKind {
     TextInput,
+    Textarea,
     ArrayBuffer,
     Bool,
+    Const,
     Enum,
     MultiSelect,
     Composite,
     CompositeList,
     ScalarArray,
     KeyValue,
+    Tuple,
+    DateTime,
+    IpAddress,
+    FormattedText,
+    Slider,
+    MultiType,
+    Json,
 }
 
 pub trait FieldComponent: FieldComponentClone + std::fmt::Debug {
@@ -30,6 +41,21 @@ pub trait FieldComponent: FieldComponentClone + std::fmt::Debug {
     fn seed_value(&mut self, schema: &FieldSchema, value: &Value);
     fn current_value(&self, schema: &FieldSchema) -> Result<Option<Value>, FieldCoercionError>;
 
+    /// Problem with the text as typed, reported live before schema validation.
+    fn syntax_error(&self) -> Option<String> {
+        None
+    }
+
+    /// Caret of text inputs, drawn by the renderer.
+    fn text_cursor(&self) -> Option<TextCursor> {
+        None
+    }
+
+    /// Widget-specific key help shown under the field while it is focused.
+    fn editor_hint(&self) -> Option<String> {
+        None
+    }
+
     fn bool_value(&self) -> Option<bool> {
         None
     }
@@ -116,6 +142,21 @@ pub trait FieldComponent: FieldComponentClone + std::fmt::Debug {
         false
     }
 
+    /// Why `collection_add` is refused right now (`maxItems`).
+    fn collection_add_blocked(&self) -> Option<String> {
+        None
+    }
+
+    /// Why `collection_remove` is refused right now (`minItems`).
+    fn collection_remove_blocked(&self) -> Option<String> {
+        None
+    }
+
+    /// Entries violating `uniqueItems`.
+    fn collection_duplicates(&self) -> Vec<usize> {
+        Vec::new()
+    }
+
     fn open_composite_list_editor(
         &mut self,
         pointer: &str,
@@ -26,7 +26,9 @@ impl FieldComponent for CompositeComponent {
 
     fn display_value(&self, _schema: &FieldSchema) -> String {
         let mut label = self.state.summary();
-        if self.state.is_multi() {
+        if self.state.is_recursive() {
+            label.push_str(" (Ctrl+E to expand)");
+        } else if self.state.is_multi() {
             label.push_str(" (Enter to toggle)");
         } else {
             label.push_str(" (Enter to choose)");
@@ -1,6 +1,6 @@
 use serde_json::Value;
 
-use crate::domain::{CompositeField, FieldSchema};
+use crate::domain::{CompositeField, FieldSchema, ItemBounds};
 use crate::form::composite::{CompositeListEditorContext, CompositeListState};
 use crate::form::error::FieldCoercionError;
 
@@ -13,9 +13,14 @@ pub struct CompositeListComponent {
 }
 
 impl CompositeListComponent {
-    pub fn new(pointer: &str, template: &CompositeField, defaults: Option<&Value>) -> Self {
+    pub fn new(
+        pointer: &str,
+        template: &CompositeField,
+        bounds: &ItemBounds,
+        defaults: Option<&Value>,
+    ) -> Self {
         Self {
-            state: CompositeListState::new(pointer, template, defaults),
+            state: CompositeListState::new(pointer, template, bounds.clone(), defaults),
         }
     }
 }
@@ -63,8 +68,19 @@ impl FieldComponent for CompositeListComponent {
     }
 
     fn collection_add(&mut self) -> bool {
-        self.state.add_entry();
-        true
+        self.state.add_entry().is_some()
+    }
+
+    fn collection_add_blocked(&self) -> Option<String> {
+        self.state.add_blocked()
+    }
+
+    fn collection_remove_blocked(&self) -> Option<String> {
+        self.state.remove_blocked()
+    }
+
+    fn collection_duplicates(&self) -> Vec<usize> {
+        self.state.duplicates()
     }
 
     fn collection_remove(&mut self) -> bool {
@@ -0,0 +1,40 @@
+use serde_json::Value;
+
+use crate::domain::FieldSchema;
+use crate::form::error::FieldCoercionError;
+
+use super::{ComponentKind, FieldComponent};
+
+/// Fixed `const` value: ignores input and seeding, always emitted.
+#[derive(Debug, Clone)]
+pub struct ConstComponent {
+    value: Value,
+}
+
+impl ConstComponent {
+    pub fn new(value: &Value) -> Self {
+        Self {
+            value: value.clone(),
+        }
+    }
+}
+
+impl FieldComponent for ConstComponent {
+    fn kind(&self) -> ComponentKind {
+        ComponentKind::Const
+    }
+
+    fn display_value(&self, _schema: &FieldSchema) -> String {
+        let text = match &self.value {
+            Value::String(text) => text.clone(),
+            other => other.to_string(),
+        };
+        format!("{text} (locked)")
+    }
+
+    fn seed_value(&mut self, _schema: &FieldSchema, _value: &Value) {}
+
+    fn current_value(&self, _schema: &FieldSchema) -> Result<Option<Value>, FieldCoercionError> {
+        Ok(Some(self.value.clone()))
+    }
+}
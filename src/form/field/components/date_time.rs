@@ -0,0 +1,99 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use serde_json::Value;
+
+use crate::domain::{FieldSchema, StringFormat};
+use crate::form::error::FieldCoercionError;
+
+use super::helpers::handle_text_edit;
+use super::{ComponentKind, FieldComponent, LineEditor, TextCursor};
+use crate::form::field::convert::{string_value, value_to_string};
+use crate::form::field::format::{step_temporal, syntax_error, temporal_segments};
+
+/// `format: date`, `date-time`, `time`: free typing plus segment stepping
+/// (`←/→` pick year…second, `Shift+↑/↓` step it).
+#[derive(Debug, Clone)]
+pub struct DateTimeComponent {
+    format: StringFormat,
+    editor: LineEditor,
+    segment: usize,
+}
+
+impl DateTimeComponent {
+    pub fn new(format: StringFormat, schema: &FieldSchema) -> Self {
+        let buffer = schema
+            .default
+            .as_ref()
+            .map(value_to_string)
+            .unwrap_or_default();
+        Self {
+            format,
+            editor: LineEditor::new(buffer),
+            segment: 0,
+        }
+    }
+}
+
+impl FieldComponent for DateTimeComponent {
+    fn kind(&self) -> ComponentKind {
+        ComponentKind::DateTime
+    }
+
+    fn display_value(&self, _schema: &FieldSchema) -> String {
+        self.editor.text().to_string()
+    }
+
+    fn handle_key(&mut self, schema: &FieldSchema, key: &KeyEvent) -> bool {
+        let segments = temporal_segments(self.format).len();
+        match key.code {
+            KeyCode::Left if key.modifiers.is_empty() => {
+                self.segment = (self.segment + segments - 1) % segments;
+                false
+            }
+            KeyCode::Right if key.modifiers.is_empty() => {
+                self.segment = (self.segment + 1) % segments;
+                false
+            }
+            KeyCode::Up | KeyCode::Down if key.modifiers.contains(KeyModifiers::SHIFT) => {
+                let delta = if key.code == KeyCode::Up { 1 } else { -1 };
+                match step_temporal(self.format, self.editor.text(), self.segment, delta) {
+                    Some(next) => {
+                        self.editor.set_text(next);
+                        true
+                    }
+                    None => false,
+                }
+            }
+            _ => handle_text_edit(&mut self.editor, schema, key),
+        }
+    }
+
+    fn seed_value(&mut self, _schema: &FieldSchema, value: &Value) {
+        self.editor.set_text(value_to_string(value));
+    }
+
+    fn current_value(&self, schema: &FieldSchema) -> Result<Option<Value>, FieldCoercionError> {
+        if let Some(message) = self.syntax_error() {
+            return Err(FieldCoercionError {
+                pointer: schema.pointer.clone(),
+                message,
+            });
+        }
+        string_value(self.editor.text(), schema)
+    }
+
+    fn syntax_error(&self) -> Option<String> {
+        if self.editor.text().is_empty() {
+            return None;
+        }
+        syntax_error(self.format, self.editor.text())
+    }
+
+    fn text_cursor(&self) -> Option<TextCursor> {
+        Some(self.editor.cursor())
+    }
+
+    fn editor_hint(&self) -> Option<String> {
+        let segment = temporal_segments(self.format)[self.segment];
+        Some(format!("←/→ segment: {segment} • Shift+↑/↓ step"))
+    }
+}
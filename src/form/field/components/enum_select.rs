@@ -1,32 +1,42 @@
 use serde_json::Value;
 
-use crate::domain::FieldSchema;
+use crate::domain::{EnumOption, FieldSchema, Widget};
 
 use super::{ComponentKind, EnumStateRef, FieldComponent};
 use crate::form::field::convert::value_to_string;
 
 #[derive(Debug, Clone)]
 pub struct EnumComponent {
+    values: Vec<Value>,
     options: Vec<String>,
     selected: usize,
 }
 
 impl EnumComponent {
-    pub fn new(options: &[String], schema: &FieldSchema) -> Self {
-        let default_value = schema
-            .default
-            .as_ref()
-            .map(value_to_string)
-            .and_then(|value| if value.is_empty() { None } else { Some(value) })
-            .unwrap_or_else(|| options.first().cloned().unwrap_or_default());
-        let selected = options
-            .iter()
-            .position(|item| item == &default_value)
-            .unwrap_or(0);
-        Self {
-            options: options.to_vec(),
-            selected,
+    pub fn new(options: &[EnumOption], schema: &FieldSchema) -> Self {
+        let mut component = Self {
+            values: options.iter().map(|option| option.value.clone()).collect(),
+            options: options.iter().map(|option| option.label.clone()).collect(),
+            selected: 0,
+        };
+        if let Some(default) = &schema.default {
+            component.selected = component.position_of(default).unwrap_or(0);
         }
+        component
+    }
+
+    /// Exact match first; documents written before options were typed may
+    /// still carry the stringified value.
+    fn position_of(&self, value: &Value) -> Option<usize> {
+        self.values
+            .iter()
+            .position(|item| item == value)
+            .or_else(|| {
+                let text = value_to_string(value);
+                self.values
+                    .iter()
+                    .position(|item| !text.is_empty() && value_to_string(item) == text)
+            })
     }
 }
 
@@ -35,7 +45,19 @@ impl FieldComponent for EnumComponent {
         ComponentKind::Enum
     }
 
-    fn display_value(&self, _schema: &FieldSchema) -> String {
+    fn display_value(&self, schema: &FieldSchema) -> String {
+        if schema.ui.widget == Some(Widget::Radio) {
+            return self
+                .options
+                .iter()
+                .enumerate()
+                .map(|(index, label)| {
+                    let mark = if index == self.selected { '•' } else { ' ' };
+                    format!("({mark}) {label}")
+                })
+                .collect::<Vec<_>>()
+                .join("  ");
+        }
         self.options
             .get(self.selected)
             .cloned()
@@ -67,9 +89,7 @@ impl FieldComponent for EnumComponent {
     }
 
     fn seed_value(&mut self, _schema: &FieldSchema, value: &Value) {
-        if let Some(text) = value.as_str()
-            && let Some(idx) = self.options.iter().position(|opt| opt == text)
-        {
+        if let Some(idx) = self.position_of(value) {
             self.selected = idx;
         }
     }
@@ -78,7 +98,7 @@ impl FieldComponent for EnumComponent {
         &self,
         _schema: &FieldSchema,
     ) -> Result<Option<Value>, crate::form::error::FieldCoercionError> {
-        Ok(self.options.get(self.selected).cloned().map(Value::String))
+        Ok(self.values.get(self.selected).cloned())
     }
 
     fn enum_state(&self) -> Option<EnumStateRef<'_>> {
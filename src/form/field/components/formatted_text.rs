@@ -0,0 +1,82 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use serde_json::Value;
+
+use crate::domain::{FieldSchema, StringFormat};
+use crate::form::error::FieldCoercionError;
+
+use super::helpers::handle_text_edit;
+use super::{ComponentKind, FieldComponent, LineEditor, TextCursor};
+use crate::form::field::convert::{string_value, value_to_string};
+use crate::form::field::format::{random_uuid, syntax_error};
+
+/// `format: email`, `uri`, `uuid`: a text input with live syntax checks;
+/// `Ctrl+G` fills in a fresh UUID.
+#[derive(Debug, Clone)]
+pub struct FormattedTextComponent {
+    format: StringFormat,
+    editor: LineEditor,
+}
+
+impl FormattedTextComponent {
+    pub fn new(format: StringFormat, schema: &FieldSchema) -> Self {
+        let buffer = schema
+            .default
+            .as_ref()
+            .map(value_to_string)
+            .unwrap_or_default();
+        Self {
+            format,
+            editor: LineEditor::new(buffer),
+        }
+    }
+}
+
+impl FieldComponent for FormattedTextComponent {
+    fn kind(&self) -> ComponentKind {
+        ComponentKind::FormattedText
+    }
+
+    fn display_value(&self, _schema: &FieldSchema) -> String {
+        self.editor.text().to_string()
+    }
+
+    fn handle_key(&mut self, schema: &FieldSchema, key: &KeyEvent) -> bool {
+        if self.format == StringFormat::Uuid
+            && key.code == KeyCode::Char('g')
+            && key.modifiers.contains(KeyModifiers::CONTROL)
+        {
+            self.editor.set_text(random_uuid());
+            return true;
+        }
+        handle_text_edit(&mut self.editor, schema, key)
+    }
+
+    fn seed_value(&mut self, _schema: &FieldSchema, value: &Value) {
+        self.editor.set_text(value_to_string(value));
+    }
+
+    fn current_value(&self, schema: &FieldSchema) -> Result<Option<Value>, FieldCoercionError> {
+        if let Some(message) = self.syntax_error() {
+            return Err(FieldCoercionError {
+                pointer: schema.pointer.clone(),
+                message,
+            });
+        }
+        string_value(self.editor.text(), schema)
+    }
+
+    fn syntax_error(&self) -> Option<String> {
+        if self.editor.text().is_empty() {
+            return None;
+        }
+        syntax_error(self.format, self.editor.text())
+    }
+
+    fn text_cursor(&self) -> Option<TextCursor> {
+        Some(self.editor.cursor())
+    }
+
+    fn editor_hint(&self) -> Option<String> {
+        (self.format == StringFormat::Uuid).then(|| "Ctrl+G generate".to_string())
+    }
+}
@@ -1,31 +1,26 @@
-use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use crossterm::event::{KeyCode, KeyEvent};
 
 use crate::domain::FieldSchema;
 
-use super::ComponentKind;
+use super::{ComponentKind, LineEditor};
 use crate::form::field::convert::adjust_numeric_value;
 
-pub(crate) fn handle_text_edit(buffer: &mut String, schema: &FieldSchema, key: &KeyEvent) -> bool {
-    match key.code {
-        KeyCode::Left => adjust_numeric_value(buffer, &schema.kind, -1),
-        KeyCode::Right => adjust_numeric_value(buffer, &schema.kind, 1),
-        KeyCode::Char(ch) => {
-            if key.modifiers.contains(KeyModifiers::CONTROL) {
-                return false;
-            }
-            buffer.push(ch);
-            true
+/// Text keys for `editor`. Integer and number fields keep `←/→` as a
+/// stepper; `Home`/`End` and word motions still move their caret.
+pub(crate) fn handle_text_edit(
+    editor: &mut LineEditor,
+    schema: &FieldSchema,
+    key: &KeyEvent,
+) -> bool {
+    if key.modifiers.is_empty() && matches!(key.code, KeyCode::Left | KeyCode::Right) {
+        let mut buffer = editor.text().to_string();
+        let delta = if key.code == KeyCode::Left { -1 } else { 1 };
+        if adjust_numeric_value(&mut buffer, &schema.kind, delta) {
+            editor.set_text(buffer);
+            return true;
         }
-        KeyCode::Backspace => {
-            buffer.pop();
-            true
-        }
-        KeyCode::Delete => {
-            buffer.clear();
-            true
-        }
-        _ => false,
     }
+    editor.handle_key(key)
 }
 
 pub(crate) fn format_collection_value(
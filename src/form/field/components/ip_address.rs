@@ -0,0 +1,107 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use serde_json::Value;
+
+use crate::domain::{FieldSchema, StringFormat};
+use crate::form::error::FieldCoercionError;
+
+use super::helpers::handle_text_edit;
+use super::{ComponentKind, FieldComponent, LineEditor, TextCursor};
+use crate::form::field::convert::{string_value, value_to_string};
+use crate::form::field::format::{ip_parts, step_ip, syntax_error};
+
+/// `format: ipv4` / `ipv6`: free typing plus octet (group) navigation with
+/// `←/→` and stepping with `Shift+↑/↓`.
+#[derive(Debug, Clone)]
+pub struct IpAddressComponent {
+    format: StringFormat,
+    editor: LineEditor,
+    part: usize,
+}
+
+impl IpAddressComponent {
+    pub fn new(format: StringFormat, schema: &FieldSchema) -> Self {
+        let buffer = schema
+            .default
+            .as_ref()
+            .map(value_to_string)
+            .unwrap_or_default();
+        Self {
+            format,
+            editor: LineEditor::new(buffer),
+            part: 0,
+        }
+    }
+}
+
+impl FieldComponent for IpAddressComponent {
+    fn kind(&self) -> ComponentKind {
+        ComponentKind::IpAddress
+    }
+
+    fn display_value(&self, _schema: &FieldSchema) -> String {
+        self.editor.text().to_string()
+    }
+
+    fn handle_key(&mut self, schema: &FieldSchema, key: &KeyEvent) -> bool {
+        let parts = ip_parts(self.format);
+        match key.code {
+            KeyCode::Left if key.modifiers.is_empty() => {
+                self.part = (self.part + parts - 1) % parts;
+                false
+            }
+            KeyCode::Right if key.modifiers.is_empty() => {
+                self.part = (self.part + 1) % parts;
+                false
+            }
+            KeyCode::Up | KeyCode::Down if key.modifiers.contains(KeyModifiers::SHIFT) => {
+                let delta = if key.code == KeyCode::Up { 1 } else { -1 };
+                match step_ip(self.format, self.editor.text(), self.part, delta) {
+                    Some(next) => {
+                        self.editor.set_text(next);
+                        true
+                    }
+                    None => false,
+                }
+            }
+            _ => handle_text_edit(&mut self.editor, schema, key),
+        }
+    }
+
+    fn seed_value(&mut self, _schema: &FieldSchema, value: &Value) {
+        self.editor.set_text(value_to_string(value));
+    }
+
+    fn current_value(&self, schema: &FieldSchema) -> Result<Option<Value>, FieldCoercionError> {
+        if let Some(message) = self.syntax_error() {
+            return Err(FieldCoercionError {
+                pointer: schema.pointer.clone(),
+                message,
+            });
+        }
+        string_value(self.editor.text(), schema)
+    }
+
+    fn syntax_error(&self) -> Option<String> {
+        if self.editor.text().is_empty() {
+            return None;
+        }
+        syntax_error(self.format, self.editor.text())
+    }
+
+    fn text_cursor(&self) -> Option<TextCursor> {
+        Some(self.editor.cursor())
+    }
+
+    fn editor_hint(&self) -> Option<String> {
+        let name = if self.format == StringFormat::Ipv4 {
+            "octet"
+        } else {
+            "group"
+        };
+        Some(format!(
+            "←/→ {name} {}/{} • Shift+↑/↓ step",
+            self.part + 1,
+            ip_parts(self.format)
+        ))
+    }
+}
@@ -0,0 +1,97 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use serde_json::Value;
+
+use crate::domain::FieldSchema;
+use crate::form::error::FieldCoercionError;
+
+use super::helpers::handle_text_edit;
+use super::{ComponentKind, FieldComponent, LineEditor, TextCursor};
+use crate::form::field::convert::{json_syntax_error, json_value};
+
+/// Free-form objects and schemas the form cannot lay out: a multi-line
+/// buffer parsed as JSON. Values are pretty-printed when seeded and `Ctrl+F`
+/// reformats the buffer.
+#[derive(Debug, Clone)]
+pub struct JsonComponent {
+    editor: LineEditor,
+}
+
+impl JsonComponent {
+    pub fn new(schema: &FieldSchema) -> Self {
+        let buffer = schema.default.as_ref().map(pretty).unwrap_or_default();
+        Self {
+            editor: LineEditor::new(buffer),
+        }
+    }
+
+    /// Starts a new line indented like the current one, one level deeper
+    /// after an opening bracket.
+    fn newline(&mut self) {
+        let current = self.editor.line_before_cursor();
+        let mut indent: String = current
+            .chars()
+            .take_while(|ch| ch.is_whitespace())
+            .collect();
+        if current.trim_end().ends_with(['{', '[']) {
+            indent.push_str("  ");
+        }
+        indent.insert(0, '\n');
+        self.editor.insert(&indent);
+    }
+}
+
+impl FieldComponent for JsonComponent {
+    fn kind(&self) -> ComponentKind {
+        ComponentKind::Json
+    }
+
+    fn display_value(&self, _schema: &FieldSchema) -> String {
+        self.editor.text().to_string()
+    }
+
+    fn handle_key(&mut self, schema: &FieldSchema, key: &KeyEvent) -> bool {
+        if key.code == KeyCode::Enter {
+            self.newline();
+            return true;
+        }
+        if key.code == KeyCode::Char('f') && key.modifiers.contains(KeyModifiers::CONTROL) {
+            return match serde_json::from_str::<Value>(self.editor.text()) {
+                Ok(value) => {
+                    self.editor.set_text(pretty(&value));
+                    true
+                }
+                Err(_) => false,
+            };
+        }
+        handle_text_edit(&mut self.editor, schema, key)
+    }
+
+    fn seed_value(&mut self, _schema: &FieldSchema, value: &Value) {
+        self.editor.set_text(pretty(value));
+    }
+
+    fn current_value(&self, schema: &FieldSchema) -> Result<Option<Value>, FieldCoercionError> {
+        json_value(self.editor.text(), schema)
+    }
+
+    fn syntax_error(&self) -> Option<String> {
+        if self.editor.text().trim().is_empty() {
+            return None;
+        }
+        serde_json::from_str::<Value>(self.editor.text())
+            .err()
+            .map(|err| json_syntax_error(&err))
+    }
+
+    fn text_cursor(&self) -> Option<TextCursor> {
+        Some(self.editor.cursor())
+    }
+
+    fn editor_hint(&self) -> Option<String> {
+        Some("Enter newline, Ctrl+F format".to_string())
+    }
+}
+
+fn pretty(value: &Value) -> String {
+    serde_json::to_string_pretty(value).unwrap_or_else(|_| value.to_string())
+}
@@ -0,0 +1,275 @@
+use std::ops::Range;
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+/// Text buffer with a caret, an optional selection and insert/overwrite
+/// mode, shared by the text-based components. Positions are byte offsets
+/// kept on grapheme cluster boundaries, so combining marks, emoji sequences
+/// and wide characters move and delete as one unit. `Home`/`End` and the
+/// kill keys work on the caret's line when the text spans several.
+#[derive(Debug, Clone, Default)]
+pub struct LineEditor {
+    text: String,
+    cursor: usize,
+    anchor: Option<usize>,
+    overwrite: bool,
+}
+
+/// Caret state handed to the renderer; offsets index the component's
+/// `display_value`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TextCursor {
+    pub offset: usize,
+    pub selection: Option<Range<usize>>,
+    pub overwrite: bool,
+}
+
+impl LineEditor {
+    pub fn new(text: impl Into<String>) -> Self {
+        let mut editor = Self::default();
+        editor.set_text(text);
+        editor
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Replaces the contents and parks the caret at the end.
+    pub fn set_text(&mut self, text: impl Into<String>) {
+        self.text = text.into();
+        self.cursor = self.text.len();
+        self.anchor = None;
+    }
+
+    pub fn cursor(&self) -> TextCursor {
+        TextCursor {
+            offset: self.cursor,
+            selection: self.selection(),
+            overwrite: self.overwrite,
+        }
+    }
+
+    pub fn selection(&self) -> Option<Range<usize>> {
+        let anchor = self.anchor?;
+        (anchor != self.cursor).then(|| anchor.min(self.cursor)..anchor.max(self.cursor))
+    }
+
+    /// The caret's line up to the caret.
+    pub fn line_before_cursor(&self) -> &str {
+        &self.text[self.line_start(self.cursor)..self.cursor]
+    }
+
+    /// Types `text` at the caret. A selection is replaced; in overwrite mode
+    /// as many graphemes as are typed are replaced, up to the end of the line.
+    pub fn insert(&mut self, text: &str) {
+        if !self.delete_selection() && self.overwrite {
+            let line_end = self.line_end(self.cursor);
+            let mut end = self.cursor;
+            for _ in text.graphemes(true) {
+                if end >= line_end {
+                    break;
+                }
+                end = self.next_boundary(end);
+            }
+            self.text.replace_range(self.cursor..end, "");
+        }
+        self.text.insert_str(self.cursor, text);
+        self.cursor += text.len();
+    }
+
+    /// Applies an editing or caret key. Returns whether the text changed;
+    /// caret and selection moves return `false`.
+    ///
+    /// `←/→` move by grapheme, `Ctrl`/`Alt` + `←/→` by word, `↑/↓` between
+    /// lines and `Home`/`End` to the line bounds; `Shift` extends the selection. `Ctrl+W` deletes the
+    /// word before the caret, `Ctrl+U` everything before it on the line, and
+    /// `Insert` toggles overwrite mode.
+    pub fn handle_key(&mut self, key: &KeyEvent) -> bool {
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        let by_word = ctrl || key.modifiers.contains(KeyModifiers::ALT);
+        let extend = key.modifiers.contains(KeyModifiers::SHIFT);
+        match key.code {
+            KeyCode::Left => {
+                let target = match self.selection() {
+                    Some(range) if !extend && !by_word => range.start,
+                    _ if by_word => self.prev_word(self.cursor),
+                    _ => self.prev_boundary(self.cursor),
+                };
+                self.move_to(target, extend);
+                false
+            }
+            KeyCode::Right => {
+                let target = match self.selection() {
+                    Some(range) if !extend && !by_word => range.end,
+                    _ if by_word => self.next_word(self.cursor),
+                    _ => self.next_boundary(self.cursor),
+                };
+                self.move_to(target, extend);
+                false
+            }
+            KeyCode::Up | KeyCode::Down => {
+                if let Some(target) = self.vertical_target(key.code == KeyCode::Down) {
+                    self.move_to(target, extend);
+                }
+                false
+            }
+            KeyCode::Home => {
+                self.move_to(self.line_start(self.cursor), extend);
+                false
+            }
+            KeyCode::End => {
+                self.move_to(self.line_end(self.cursor), extend);
+                false
+            }
+            KeyCode::Insert => {
+                self.overwrite = !self.overwrite;
+                false
+            }
+            KeyCode::Backspace => {
+                if self.delete_selection() {
+                    return true;
+                }
+                let start = if by_word {
+                    self.prev_word(self.cursor)
+                } else {
+                    self.prev_boundary(self.cursor)
+                };
+                self.delete_range(start..self.cursor)
+            }
+            KeyCode::Delete => {
+                if self.delete_selection() {
+                    return true;
+                }
+                let end = if by_word {
+                    self.next_word(self.cursor)
+                } else {
+                    self.next_boundary(self.cursor)
+                };
+                self.delete_range(self.cursor..end)
+            }
+            KeyCode::Char('w') if ctrl => {
+                self.delete_selection()
+                    || self.delete_range(self.prev_word(self.cursor)..self.cursor)
+            }
+            KeyCode::Char('u') if ctrl => {
+                self.delete_selection()
+                    || self.delete_range(self.line_start(self.cursor)..self.cursor)
+            }
+            KeyCode::Char(_) if ctrl => false,
+            KeyCode::Char(ch) => {
+                self.insert(ch.encode_utf8(&mut [0; 4]));
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Same display column on the previous or next line, clamped to its end.
+    fn vertical_target(&self, down: bool) -> Option<usize> {
+        let start = self.line_start(self.cursor);
+        let column = UnicodeWidthStr::width(&self.text[start..self.cursor]);
+        let line = if down {
+            let end = self.line_end(self.cursor);
+            (end < self.text.len()).then_some(end + 1)?
+        } else {
+            (start > 0).then(|| self.line_start(start - 1))?
+        };
+        let mut offset = line;
+        let mut width = 0;
+        for grapheme in self.text[line..self.line_end(line)].graphemes(true) {
+            width += UnicodeWidthStr::width(grapheme);
+            if width > column {
+                break;
+            }
+            offset += grapheme.len();
+        }
+        Some(offset)
+    }
+
+    fn move_to(&mut self, target: usize, extend: bool) {
+        if extend {
+            self.anchor.get_or_insert(self.cursor);
+        } else {
+            self.anchor = None;
+        }
+        self.cursor = target;
+    }
+
+    fn delete_selection(&mut self) -> bool {
+        match self.selection() {
+            Some(range) => self.delete_range(range),
+            None => {
+                self.anchor = None;
+                false
+            }
+        }
+    }
+
+    fn delete_range(&mut self, range: Range<usize>) -> bool {
+        self.anchor = None;
+        if range.is_empty() {
+            return false;
+        }
+        self.cursor = range.start;
+        self.text.replace_range(range, "");
+        true
+    }
+
+    fn prev_boundary(&self, pos: usize) -> usize {
+        self.text[..pos]
+            .grapheme_indices(true)
+            .next_back()
+            .map_or(0, |(index, _)| index)
+    }
+
+    fn next_boundary(&self, pos: usize) -> usize {
+        self.text[pos..]
+            .graphemes(true)
+            .next()
+            .map_or(pos, |grapheme| pos + grapheme.len())
+    }
+
+    /// Start of the word before `pos`, skipping separators first.
+    fn prev_word(&self, pos: usize) -> usize {
+        let mut graphemes = self.text[..pos].grapheme_indices(true).rev().peekable();
+        let mut start = pos;
+        for in_word in [false, true] {
+            while let Some((index, _)) = graphemes.next_if(|(_, g)| is_word(g) == in_word) {
+                start = index;
+            }
+        }
+        start
+    }
+
+    /// End of the word after `pos`, skipping separators first.
+    fn next_word(&self, pos: usize) -> usize {
+        let mut graphemes = self.text[pos..].grapheme_indices(true).peekable();
+        let mut end = pos;
+        for in_word in [false, true] {
+            while let Some((index, grapheme)) = graphemes.next_if(|(_, g)| is_word(g) == in_word) {
+                end = pos + index + grapheme.len();
+            }
+        }
+        end
+    }
+
+    fn line_start(&self, pos: usize) -> usize {
+        self.text[..pos].rfind('\n').map_or(0, |index| index + 1)
+    }
+
+    fn line_end(&self, pos: usize) -> usize {
+        self.text[pos..]
+            .find('\n')
+            .map_or(self.text.len(), |index| pos + index)
+    }
+}
+
+fn is_word(grapheme: &str) -> bool {
+    grapheme
+        .chars()
+        .next()
+        .is_some_and(|ch| ch.is_alphanumeric() || ch == '_')
+}
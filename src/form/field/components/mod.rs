@@ -3,12 +3,22 @@ mod base;
 mod bool;
 mod composite;
 mod composite_list;
+mod const_value;
+mod date_time;
 mod enum_select;
+mod formatted_text;
 pub(crate) mod helpers;
+mod ip_address;
+mod json;
 mod key_value;
+mod line_editor;
 mod multi_select;
+mod multi_type;
 mod scalar_array;
+mod slider;
 mod text;
+mod textarea;
+mod tuple;
 
 pub use array_buffer::ArrayBufferComponent;
 pub use base::{
@@ -18,8 +28,18 @@ pub use base::{
 pub use bool::BoolComponent;
 pub use composite::CompositeComponent;
 pub use composite_list::CompositeListComponent;
+pub use const_value::ConstComponent;
+pub use date_time::DateTimeComponent;
 pub use enum_select::EnumComponent;
+pub use formatted_text::FormattedTextComponent;
+pub use ip_address::IpAddressComponent;
+pub use json::JsonComponent;
 pub use key_value::KeyValueComponent;
+pub use line_editor::{LineEditor, TextCursor};
 pub use multi_select::MultiSelectComponent;
+pub use multi_type::MultiTypeComponent;
 pub use scalar_array::ScalarArrayComponent;
+pub use slider::SliderComponent;
 pub use text::TextComponent;
+pub use textarea::TextareaComponent;
+pub use tuple::TupleComponent;
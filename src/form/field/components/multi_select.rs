@@ -1,30 +1,38 @@
 use serde_json::Value;
 
-use crate::domain::FieldSchema;
+use crate::domain::{EnumOption, FieldSchema};
 use crate::form::error::FieldCoercionError;
 
 use super::{ComponentKind, FieldComponent, MultiSelectStateRef};
 
 #[derive(Debug, Clone)]
 pub struct MultiSelectComponent {
+    values: Vec<Value>,
     options: Vec<String>,
     selected: Vec<bool>,
 }
 
 impl MultiSelectComponent {
-    pub fn new(options: &[String], default: Option<&Value>) -> Self {
-        let mut selected = vec![false; options.len()];
+    pub fn new(options: &[EnumOption], default: Option<&Value>) -> Self {
+        let mut component = Self {
+            values: options.iter().map(|option| option.value.clone()).collect(),
+            options: options.iter().map(|option| option.label.clone()).collect(),
+            selected: vec![false; options.len()],
+        };
         if let Some(Value::Array(items)) = default {
-            for item in items.iter().filter_map(Value::as_str) {
-                if let Some(idx) = options.iter().position(|opt| opt == item) {
-                    selected[idx] = true;
-                }
-            }
+            component.selected = component.flags_for(items);
         }
-        Self {
-            options: options.to_vec(),
-            selected,
+        component
+    }
+
+    fn flags_for(&self, items: &[Value]) -> Vec<bool> {
+        let mut flags = vec![false; self.values.len()];
+        for item in items {
+            if let Some(idx) = self.values.iter().position(|value| value == item) {
+                flags[idx] = true;
+            }
         }
+        flags
     }
 }
 
@@ -49,30 +57,16 @@ impl FieldComponent for MultiSelectComponent {
 
     fn seed_value(&mut self, _schema: &FieldSchema, value: &Value) {
         if let Value::Array(items) = value {
-            let mut flags = vec![false; self.options.len()];
-            for item in items.iter().filter_map(Value::as_str) {
-                if let Some(idx) = self.options.iter().position(|opt| opt == item) {
-                    flags[idx] = true;
-                }
-            }
-            if flags.len() == self.selected.len() {
-                self.selected = flags;
-            }
+            self.selected = self.flags_for(items);
         }
     }
 
     fn current_value(&self, _schema: &FieldSchema) -> Result<Option<Value>, FieldCoercionError> {
         let values = self
-            .options
+            .values
             .iter()
             .zip(self.selected.iter())
-            .filter_map(|(option, flag)| {
-                if *flag {
-                    Some(Value::String(option.clone()))
-                } else {
-                    None
-                }
-            })
+            .filter_map(|(value, flag)| if *flag { Some(value.clone()) } else { None })
             .collect();
         Ok(Some(Value::Array(values)))
     }
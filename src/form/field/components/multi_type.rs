@@ -0,0 +1,137 @@
+use crossterm::event::KeyEvent;
+use serde_json::Value;
+
+use crate::domain::{FieldKind, FieldSchema};
+use crate::form::error::FieldCoercionError;
+use crate::form::field::convert::{coerce_value, matches_kind};
+
+use super::{ComponentKind, CompositePopupData, FieldComponent, TextCursor};
+
+/// `type: [...]` with several non-null types: one inner component per type,
+/// switched through the same chooser popup composites use.
+#[derive(Debug, Clone)]
+pub struct MultiTypeComponent {
+    options: Vec<String>,
+    variants: Vec<(FieldSchema, Box<dyn FieldComponent>)>,
+    active: usize,
+}
+
+impl MultiTypeComponent {
+    pub fn new(
+        schema: &FieldSchema,
+        variants: Vec<(FieldSchema, Box<dyn FieldComponent>)>,
+    ) -> Self {
+        let options = variants
+            .iter()
+            .map(|(variant, _)| type_name(&variant.kind).to_string())
+            .collect();
+        let active = schema
+            .default
+            .as_ref()
+            .and_then(|value| {
+                variants
+                    .iter()
+                    .position(|(variant, _)| matches_kind(value, &variant.kind))
+            })
+            .unwrap_or(0);
+        Self {
+            options,
+            variants,
+            active,
+        }
+    }
+
+    fn current(&self) -> &(FieldSchema, Box<dyn FieldComponent>) {
+        &self.variants[self.active]
+    }
+
+    fn current_mut(&mut self) -> &mut (FieldSchema, Box<dyn FieldComponent>) {
+        &mut self.variants[self.active]
+    }
+}
+
+impl FieldComponent for MultiTypeComponent {
+    fn kind(&self) -> ComponentKind {
+        ComponentKind::MultiType
+    }
+
+    fn display_value(&self, _schema: &FieldSchema) -> String {
+        let (schema, component) = self.current();
+        component.display_value(schema)
+    }
+
+    fn handle_key(&mut self, _schema: &FieldSchema, key: &KeyEvent) -> bool {
+        let (schema, component) = self.current_mut();
+        component.handle_key(schema, key)
+    }
+
+    fn seed_value(&mut self, _schema: &FieldSchema, value: &Value) {
+        if let Some(index) = self
+            .variants
+            .iter()
+            .position(|(schema, _)| matches_kind(value, &schema.kind))
+        {
+            self.active = index;
+        }
+        let (schema, component) = self.current_mut();
+        component.seed_value(schema, value);
+    }
+
+    fn current_value(&self, _schema: &FieldSchema) -> Result<Option<Value>, FieldCoercionError> {
+        let (schema, component) = self.current();
+        component.current_value(schema)
+    }
+
+    fn syntax_error(&self) -> Option<String> {
+        self.current().1.syntax_error()
+    }
+
+    fn text_cursor(&self) -> Option<TextCursor> {
+        self.current().1.text_cursor()
+    }
+
+    fn editor_hint(&self) -> Option<String> {
+        let switch = "Enter change type".to_string();
+        Some(match self.current().1.editor_hint() {
+            Some(hint) => format!("{hint}, {switch}"),
+            None => switch,
+        })
+    }
+
+    fn composite_popup(&self) -> Option<CompositePopupData> {
+        Some(CompositePopupData {
+            options: self.options.clone(),
+            selected: self.active,
+            multi: false,
+            active: (0..self.options.len())
+                .map(|index| index == self.active)
+                .collect(),
+        })
+    }
+
+    /// Switches the edited type, carrying the value over when it converts.
+    fn apply_composite_selection(&mut self, selection: usize, _flags: Option<Vec<bool>>) -> bool {
+        if selection == self.active || selection >= self.variants.len() {
+            return false;
+        }
+        let previous = self.current_value(&self.current().0).ok().flatten();
+        self.active = selection;
+        let (schema, component) = self.current_mut();
+        if let Some(value) = previous.and_then(|value| coerce_value(&value, &schema.kind)) {
+            component.seed_value(schema, &value);
+        }
+        true
+    }
+}
+
+fn type_name(kind: &FieldKind) -> &'static str {
+    match kind {
+        FieldKind::String | FieldKind::Formatted(_) => "string",
+        FieldKind::Integer => "integer",
+        FieldKind::Number => "number",
+        FieldKind::Boolean => "boolean",
+        FieldKind::Array(_) | FieldKind::Tuple(_) => "array",
+        FieldKind::Json | FieldKind::Composite(_) | FieldKind::KeyValue(_) => "object",
+        FieldKind::Enum(_) | FieldKind::Const(_) | FieldKind::MultiType(_) => "value",
+    }
+}
@@ -20,6 +20,7 @@ impl ScalarArrayComponent {
                 schema.display_label(),
                 schema.description.clone(),
                 inner,
+                schema.item_bounds.clone(),
                 schema.default.as_ref(),
             ),
         }
@@ -70,6 +71,18 @@ impl FieldComponent for ScalarArrayComponent {
         self.state.add_entry()
     }
 
+    fn collection_add_blocked(&self) -> Option<String> {
+        self.state.add_blocked()
+    }
+
+    fn collection_remove_blocked(&self) -> Option<String> {
+        self.state.remove_blocked()
+    }
+
+    fn collection_duplicates(&self) -> Vec<usize> {
+        self.state.duplicates()
+    }
+
     fn collection_remove(&mut self) -> bool {
         self.state.remove_selected()
     }
@@ -0,0 +1,113 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use serde_json::{Number, Value};
+
+use crate::domain::{FieldKind, FieldSchema};
+use crate::form::error::FieldCoercionError;
+
+use super::{ComponentKind, FieldComponent};
+
+const TRACK_WIDTH: usize = 20;
+
+/// `x-widget: slider` on a bounded integer/number: `←/→` step by
+/// `multipleOf`, `Home`/`End` jump to the bounds.
+#[derive(Debug, Clone)]
+pub struct SliderComponent {
+    value: Option<f64>,
+    min: f64,
+    max: f64,
+    step: f64,
+}
+
+impl SliderComponent {
+    pub fn new(schema: &FieldSchema, min: f64, max: f64, step: f64) -> Self {
+        let (min, max) = if min <= max { (min, max) } else { (max, min) };
+        let mut component = Self {
+            value: None,
+            min,
+            max,
+            step: if step > 0.0 { step } else { 1.0 },
+        };
+        if let Some(default) = &schema.default {
+            component.seed_value(schema, default);
+        }
+        component
+    }
+
+    fn set(&mut self, value: f64) -> bool {
+        let next = value.clamp(self.min, self.max);
+        if self.value == Some(next) {
+            return false;
+        }
+        self.value = Some(next);
+        true
+    }
+}
+
+impl FieldComponent for SliderComponent {
+    fn kind(&self) -> ComponentKind {
+        ComponentKind::Slider
+    }
+
+    fn display_value(&self, _schema: &FieldSchema) -> String {
+        let Some(value) = self.value else {
+            return format!("[{}] <unset>", "─".repeat(TRACK_WIDTH));
+        };
+        let span = self.max - self.min;
+        let ratio = if span > 0.0 {
+            (value - self.min) / span
+        } else {
+            1.0
+        };
+        let filled = (ratio * TRACK_WIDTH as f64).round() as usize;
+        format!(
+            "[{}{}] {value}",
+            "━".repeat(filled),
+            "─".repeat(TRACK_WIDTH - filled)
+        )
+    }
+
+    fn handle_key(&mut self, _schema: &FieldSchema, key: &KeyEvent) -> bool {
+        match key.code {
+            KeyCode::Left => {
+                let current = self.value.unwrap_or(self.min + self.step);
+                self.set(current - self.step)
+            }
+            KeyCode::Right => {
+                let current = self.value.unwrap_or(self.min - self.step);
+                self.set(current + self.step)
+            }
+            KeyCode::Home => self.set(self.min),
+            KeyCode::End => self.set(self.max),
+            KeyCode::Delete => self.value.take().is_some(),
+            _ => false,
+        }
+    }
+
+    fn seed_value(&mut self, _schema: &FieldSchema, value: &Value) {
+        if let Some(number) = value.as_f64() {
+            self.value = Some(number.clamp(self.min, self.max));
+        }
+    }
+
+    fn current_value(&self, schema: &FieldSchema) -> Result<Option<Value>, FieldCoercionError> {
+        let Some(value) = self.value else {
+            return Ok(None);
+        };
+        if schema.kind == FieldKind::Integer {
+            return Ok(Some(Value::from(value.round() as i64)));
+        }
+        Number::from_f64(value)
+            .map(|number| Some(Value::Number(number)))
+            .ok_or_else(|| FieldCoercionError {
+                pointer: schema.pointer.clone(),
+                message: "expected a finite number".to_string(),
+            })
+    }
+
+    fn editor_hint(&self) -> Option<String> {
+        Some(format!(
+            "←/→ step {} • Home/End {}…{}",
+            self.step, self.min, self.max
+        ))
+    }
+}
@@ -1,25 +1,25 @@
 use serde_json::Value;
 
+use crossterm::event::KeyEvent;
+
 use crate::domain::{FieldKind, FieldSchema};
 use crate::form::error::FieldCoercionError;
 
 use super::helpers::handle_text_edit;
-use super::{ComponentKind, FieldComponent};
+use super::{ComponentKind, FieldComponent, LineEditor, TextCursor};
 use crate::form::field::convert::{integer_value, number_value, string_value, value_to_string};
 
 #[derive(Debug, Clone)]
 pub struct TextComponent {
-    buffer: String,
+    editor: LineEditor,
 }
 
 impl TextComponent {
     pub fn new(schema: &FieldSchema) -> Self {
-        let buffer = schema
-            .default
-            .as_ref()
-            .map(value_to_string)
-            .unwrap_or_default();
-        Self { buffer }
+        let buffer = schema.default.as_ref().map(seed_text).unwrap_or_default();
+        Self {
+            editor: LineEditor::new(buffer),
+        }
     }
 }
 
@@ -29,28 +29,34 @@ impl FieldComponent for TextComponent {
     }
 
     fn display_value(&self, _schema: &FieldSchema) -> String {
-        self.buffer.clone()
+        self.editor.text().to_string()
     }
 
-    fn handle_key(&mut self, schema: &FieldSchema, key: &crossterm::event::KeyEvent) -> bool {
-        handle_text_edit(&mut self.buffer, schema, key)
+    fn handle_key(&mut self, schema: &FieldSchema, key: &KeyEvent) -> bool {
+        handle_text_edit(&mut self.editor, schema, key)
     }
 
     fn seed_value(&mut self, _schema: &FieldSchema, value: &Value) {
-        match value {
-            Value::String(text) => self.buffer = text.clone(),
-            Value::Number(num) => self.buffer = num.to_string(),
-            other => self.buffer = value_to_string(other),
-        }
+        self.editor.set_text(seed_text(value));
     }
 
     fn current_value(&self, schema: &FieldSchema) -> Result<Option<Value>, FieldCoercionError> {
         match schema.kind {
-            FieldKind::String => string_value(&self.buffer, schema),
-            FieldKind::Integer => integer_value(&self.buffer, schema),
-            FieldKind::Number => number_value(&self.buffer, schema),
-            FieldKind::Json => string_value(&self.buffer, schema),
+            FieldKind::String => string_value(self.editor.text(), schema),
+            FieldKind::Integer => integer_value(self.editor.text(), schema),
+            FieldKind::Number => number_value(self.editor.text(), schema),
             _ => Ok(None),
         }
     }
+
+    fn text_cursor(&self) -> Option<TextCursor> {
+        Some(self.editor.cursor())
+    }
+}
+
+fn seed_text(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        other => value_to_string(other),
+    }
 }
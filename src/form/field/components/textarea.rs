@@ -0,0 +1,68 @@
+use serde_json::Value;
+
+use crossterm::event::{KeyCode, KeyEvent};
+
+use crate::domain::FieldSchema;
+use crate::form::error::FieldCoercionError;
+
+use super::helpers::handle_text_edit;
+use super::{ComponentKind, FieldComponent, LineEditor, TextCursor};
+use crate::form::field::convert::{string_value, value_to_string};
+
+/// Long strings (`x-widget: textarea`, `contentMediaType`, a large
+/// `maxLength`): a multi-line buffer where `Enter` starts a new line and
+/// `↑/↓` move between lines.
+#[derive(Debug, Clone)]
+pub struct TextareaComponent {
+    editor: LineEditor,
+}
+
+impl TextareaComponent {
+    pub fn new(schema: &FieldSchema) -> Self {
+        let buffer = schema.default.as_ref().map(seed_text).unwrap_or_default();
+        Self {
+            editor: LineEditor::new(buffer),
+        }
+    }
+}
+
+impl FieldComponent for TextareaComponent {
+    fn kind(&self) -> ComponentKind {
+        ComponentKind::Textarea
+    }
+
+    fn display_value(&self, _schema: &FieldSchema) -> String {
+        self.editor.text().to_string()
+    }
+
+    fn handle_key(&mut self, schema: &FieldSchema, key: &KeyEvent) -> bool {
+        if key.code == KeyCode::Enter {
+            self.editor.insert("\n");
+            return true;
+        }
+        handle_text_edit(&mut self.editor, schema, key)
+    }
+
+    fn seed_value(&mut self, _schema: &FieldSchema, value: &Value) {
+        self.editor.set_text(seed_text(value));
+    }
+
+    fn current_value(&self, schema: &FieldSchema) -> Result<Option<Value>, FieldCoercionError> {
+        string_value(self.editor.text(), schema)
+    }
+
+    fn text_cursor(&self) -> Option<TextCursor> {
+        Some(self.editor.cursor())
+    }
+
+    fn editor_hint(&self) -> Option<String> {
+        Some("Enter newline, ↑/↓ move between lines".to_string())
+    }
+}
+
+fn seed_text(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        other => value_to_string(other),
+    }
+}
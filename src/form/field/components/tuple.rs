@@ -0,0 +1,53 @@
+use serde_json::Value;
+
+use crate::domain::{FieldSchema, TupleField};
+use crate::form::composite::CompositeEditorSession;
+use crate::form::error::FieldCoercionError;
+use crate::form::tuple::TupleState;
+
+use super::{ComponentKind, FieldComponent};
+
+#[derive(Debug, Clone)]
+pub struct TupleComponent {
+    state: TupleState,
+}
+
+impl TupleComponent {
+    pub fn new(pointer: &str, template: &TupleField, default: Option<&Value>) -> Self {
+        Self {
+            state: TupleState::new(pointer, template, default),
+        }
+    }
+}
+
+impl FieldComponent for TupleComponent {
+    fn kind(&self) -> ComponentKind {
+        ComponentKind::Tuple
+    }
+
+    fn display_value(&self, _schema: &FieldSchema) -> String {
+        format!("{} (Ctrl+E edit)", self.state.summary())
+    }
+
+    fn seed_value(&mut self, _schema: &FieldSchema, value: &Value) {
+        if let Value::Array(items) = value {
+            self.state.seed_from_array(items);
+        }
+    }
+
+    fn current_value(&self, schema: &FieldSchema) -> Result<Option<Value>, FieldCoercionError> {
+        self.state.build_value(schema.required)
+    }
+
+    fn open_composite_editor(
+        &mut self,
+        _pointer: &str,
+        _variant_index: usize,
+    ) -> Result<CompositeEditorSession, FieldCoercionError> {
+        Ok(self.state.take_editor_session())
+    }
+
+    fn restore_composite_editor(&mut self, session: CompositeEditorSession) {
+        self.state.restore_editor_session(session);
+    }
+}
@@ -4,6 +4,8 @@ use crate::domain::{FieldKind, FieldSchema};
 
 use crate::form::error::FieldCoercionError;
 
+use super::format::syntax_error;
+
 pub(super) fn value_to_string(value: &Value) -> String {
     match value {
         Value::String(text) => text.clone(),
@@ -36,6 +38,33 @@ pub(super) fn string_value(
     Ok(Some(Value::String(contents.to_string())))
 }
 
+pub(super) fn json_value(
+    contents: &str,
+    schema: &FieldSchema,
+) -> Result<Option<Value>, FieldCoercionError> {
+    if contents.trim().is_empty() {
+        return Ok(None);
+    }
+    serde_json::from_str(contents)
+        .map(Some)
+        .map_err(|err| FieldCoercionError {
+            pointer: schema.pointer.clone(),
+            message: json_syntax_error(&err),
+        })
+}
+
+/// `invalid JSON at line 2, column 7: expected `,` or `}``.
+pub(super) fn json_syntax_error(err: &serde_json::Error) -> String {
+    let text = err.to_string();
+    let location = format!(" at line {} column {}", err.line(), err.column());
+    let detail = text.strip_suffix(&location).unwrap_or(&text);
+    format!(
+        "invalid JSON at line {}, column {}: {detail}",
+        err.line(),
+        err.column()
+    )
+}
+
 pub(super) fn integer_value(
     contents: &str,
     schema: &FieldSchema,
@@ -93,6 +122,15 @@ pub(super) fn array_value(
         }
         let value = match inner {
             FieldKind::String => Value::String(item.to_string()),
+            FieldKind::Formatted(format) => match syntax_error(*format, item) {
+                None => Value::String(item.to_string()),
+                Some(message) => {
+                    return Err(FieldCoercionError {
+                        pointer: schema.pointer.clone(),
+                        message: format!("'{item}': {message}"),
+                    });
+                }
+            },
             FieldKind::Integer => {
                 item.parse::<i64>()
                     .map(Value::from)
@@ -119,29 +157,46 @@ pub(super) fn array_value(
                     });
                 }
             },
-            FieldKind::Enum(options) => {
-                if options.iter().any(|opt| opt == item) {
-                    Value::String(item.to_string())
-                } else {
+            FieldKind::Enum(options) => match options.iter().find(|option| option.label == item) {
+                Some(option) => option.value.clone(),
+                None => {
+                    let labels = options
+                        .iter()
+                        .map(|option| option.label.as_str())
+                        .collect::<Vec<_>>();
                     return Err(FieldCoercionError {
                         pointer: schema.pointer.clone(),
-                        message: format!("value '{item}' is not one of: {}", options.join(", ")),
+                        message: format!("value '{item}' is not one of: {}", labels.join(", ")),
                     });
                 }
-            }
+            },
             FieldKind::Json | FieldKind::Composite(_) => Value::String(item.to_string()),
+            FieldKind::Const(value) => value.clone(),
             FieldKind::KeyValue(_) => {
                 return Err(FieldCoercionError {
                     pointer: schema.pointer.clone(),
                     message: "arrays of key/value maps are not supported".to_string(),
                 });
             }
-            FieldKind::Array(_) => {
+            FieldKind::Array(_) | FieldKind::Tuple(_) => {
                 return Err(FieldCoercionError {
                     pointer: schema.pointer.clone(),
                     message: "nested arrays are not supported".to_string(),
                 });
             }
+            FieldKind::MultiType(kinds) => {
+                let literal = serde_json::from_str::<Value>(item)
+                    .unwrap_or_else(|_| Value::String(item.to_string()));
+                let value = if kinds.iter().any(|kind| matches_kind(&literal, kind)) {
+                    Some(literal.clone())
+                } else {
+                    kinds.iter().find_map(|kind| coerce_value(&literal, kind))
+                };
+                value.ok_or_else(|| FieldCoercionError {
+                    pointer: schema.pointer.clone(),
+                    message: format!("'{item}' does not match any allowed type"),
+                })?
+            }
         };
         values.push(value);
     }
@@ -165,3 +220,60 @@ pub(super) fn adjust_numeric_value(buffer: &mut String, kind: &FieldKind, delta:
         _ => false,
     }
 }
+
+/// Whether `value` already has the JSON type `kind` edits.
+pub(super) fn matches_kind(value: &Value, kind: &FieldKind) -> bool {
+    match kind {
+        FieldKind::String | FieldKind::Formatted(_) => value.is_string(),
+        FieldKind::Integer => value.is_i64() || value.is_u64(),
+        FieldKind::Number => value.is_number(),
+        FieldKind::Boolean => value.is_boolean(),
+        FieldKind::Enum(options) => options.iter().any(|option| option.value == *value),
+        FieldKind::Const(expected) => expected == value,
+        FieldKind::Array(_) | FieldKind::Tuple(_) => value.is_array(),
+        FieldKind::Json | FieldKind::Composite(_) | FieldKind::KeyValue(_) => value.is_object(),
+        FieldKind::MultiType(kinds) => kinds.iter().any(|kind| matches_kind(value, kind)),
+    }
+}
+
+/// Carries a value over to another type when the conversion is lossless
+/// enough to be unsurprising (`"8080"` -> `8080`, `1` -> `true`).
+pub(crate) fn coerce_value(value: &Value, kind: &FieldKind) -> Option<Value> {
+    if matches_kind(value, kind) {
+        return Some(value.clone());
+    }
+    let text = match value {
+        Value::String(text) => Some(text.trim()),
+        _ => None,
+    };
+    match kind {
+        FieldKind::String | FieldKind::Formatted(_) => match value {
+            Value::Number(_) | Value::Bool(_) => Some(Value::String(value_to_string(value))),
+            _ => None,
+        },
+        FieldKind::Integer => match value {
+            Value::Number(num) => num
+                .as_f64()
+                .filter(|float| float.fract() == 0.0)
+                .map(|float| Value::from(float as i64)),
+            Value::Bool(flag) => Some(Value::from(i64::from(*flag))),
+            _ => text?.parse::<i64>().ok().map(Value::from),
+        },
+        FieldKind::Number => match value {
+            Value::Bool(flag) => Some(Value::from(i64::from(*flag))),
+            _ => text?.parse::<f64>().ok().map(Value::from),
+        },
+        FieldKind::Boolean => match value {
+            Value::Number(num) => num.as_f64().map(|float| Value::Bool(float != 0.0)),
+            _ => match text? {
+                "true" => Some(Value::Bool(true)),
+                "false" => Some(Value::Bool(false)),
+                _ => None,
+            },
+        },
+        FieldKind::Json => serde_json::from_str::<Value>(text?)
+            .ok()
+            .filter(Value::is_object),
+        _ => None,
+    }
+}
@@ -0,0 +1,307 @@
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::net::{Ipv4Addr, Ipv6Addr};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use url::Url;
+
+use crate::domain::StringFormat;
+
+/// Live syntactic check run while typing, ahead of the `jsonschema` validator.
+pub(crate) fn syntax_error(format: StringFormat, text: &str) -> Option<String> {
+    let valid = match format {
+        StringFormat::Date => parse_date(text).is_some(),
+        StringFormat::DateTime => parse_timestamp(format, text).is_some(),
+        StringFormat::Time => parse_clock(text).is_some(),
+        StringFormat::Email => is_email(text),
+        StringFormat::Uri => Url::parse(text).is_ok(),
+        StringFormat::Ipv4 => text.parse::<Ipv4Addr>().is_ok(),
+        StringFormat::Ipv6 => text.parse::<Ipv6Addr>().is_ok(),
+        StringFormat::Uuid => is_uuid(text),
+    };
+    if valid {
+        return None;
+    }
+    let example = match format {
+        StringFormat::Date => "a date like 2024-05-17",
+        StringFormat::DateTime => "a date-time like 2024-05-17T09:30:00Z",
+        StringFormat::Time => "a time like 09:30:00Z",
+        StringFormat::Email => "an email address like user@example.com",
+        StringFormat::Uri => "an absolute URI like https://example.com",
+        StringFormat::Ipv4 => "an IPv4 address like 192.168.0.1",
+        StringFormat::Ipv6 => "an IPv6 address like 2001:db8::1",
+        StringFormat::Uuid => "a UUID like 123e4567-e89b-12d3-a456-426614174000",
+    };
+    Some(format!("expected {example}"))
+}
+
+/// Names of the segments a date/time picker steps through, in display order.
+pub(crate) fn temporal_segments(format: StringFormat) -> &'static [&'static str] {
+    match format {
+        StringFormat::Date => &["year", "month", "day"],
+        StringFormat::Time => &["hour", "minute", "second"],
+        _ => &["year", "month", "day", "hour", "minute", "second"],
+    }
+}
+
+/// Steps one segment of a date/time value. Each segment wraps within its own
+/// range (days are clamped to the month); an empty buffer starts from now.
+pub(crate) fn step_temporal(
+    format: StringFormat,
+    text: &str,
+    segment: usize,
+    delta: i64,
+) -> Option<String> {
+    if text.trim().is_empty() {
+        return Some(now().render(format));
+    }
+    let mut stamp = parse_timestamp(format, text)?;
+    let name = *temporal_segments(format).get(segment)?;
+    match name {
+        "year" => stamp.year = (stamp.year + delta).clamp(0, 9999),
+        "month" => stamp.month = wrap(stamp.month, delta, 1, 12),
+        "day" => stamp.day = wrap(stamp.day, delta, 1, days_in_month(stamp.year, stamp.month)),
+        "hour" => stamp.clock.hour = wrap(stamp.clock.hour, delta, 0, 23),
+        "minute" => stamp.clock.minute = wrap(stamp.clock.minute, delta, 0, 59),
+        _ => stamp.clock.second = wrap(stamp.clock.second.min(59), delta, 0, 59),
+    }
+    stamp.day = stamp.day.min(days_in_month(stamp.year, stamp.month));
+    Some(stamp.render(format))
+}
+
+/// Steps one octet (IPv4) or group (IPv6), wrapping around; an empty buffer
+/// starts from the unspecified address.
+pub(crate) fn step_ip(format: StringFormat, text: &str, part: usize, delta: i64) -> Option<String> {
+    let text = text.trim();
+    if format == StringFormat::Ipv4 {
+        let mut octets = if text.is_empty() {
+            [0; 4]
+        } else {
+            text.parse::<Ipv4Addr>().ok()?.octets()
+        };
+        let octet = octets.get_mut(part)?;
+        *octet = (i64::from(*octet) + delta).rem_euclid(256) as u8;
+        return Some(Ipv4Addr::from(octets).to_string());
+    }
+    let mut groups = if text.is_empty() {
+        [0; 8]
+    } else {
+        text.parse::<Ipv6Addr>().ok()?.segments()
+    };
+    let group = groups.get_mut(part)?;
+    *group = (i64::from(*group) + delta).rem_euclid(65536) as u16;
+    Some(Ipv6Addr::from(groups).to_string())
+}
+
+pub(crate) fn ip_parts(format: StringFormat) -> usize {
+    if format == StringFormat::Ipv4 { 4 } else { 8 }
+}
+
+/// Random (version 4) UUID. Seeded from the std hasher keys so no extra
+/// dependency is needed; good enough for configuration identifiers.
+pub(crate) fn random_uuid() -> String {
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_nanos())
+        .unwrap_or_default();
+    let mut bytes = [0u8; 16];
+    for (index, chunk) in bytes.chunks_mut(8).enumerate() {
+        let mut hasher = RandomState::new().build_hasher();
+        hasher.write_u128(nanos);
+        hasher.write_u64(COUNTER.fetch_add(1, Ordering::Relaxed));
+        hasher.write_usize(index);
+        chunk.copy_from_slice(&hasher.finish().to_le_bytes());
+    }
+    bytes[6] = (bytes[6] & 0x0f) | 0x40;
+    bytes[8] = (bytes[8] & 0x3f) | 0x80;
+    let hex = bytes
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect::<String>();
+    format!(
+        "{}-{}-{}-{}-{}",
+        &hex[0..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..32]
+    )
+}
+
+#[derive(Debug, Clone, Default)]
+struct Clock {
+    hour: i64,
+    minute: i64,
+    second: i64,
+    /// Fractional seconds and offset, kept verbatim (`.250Z`, `+02:00`).
+    suffix: String,
+}
+
+#[derive(Debug, Clone)]
+struct Timestamp {
+    year: i64,
+    month: i64,
+    day: i64,
+    clock: Clock,
+}
+
+impl Timestamp {
+    fn render(&self, format: StringFormat) -> String {
+        let date = format!("{:04}-{:02}-{:02}", self.year, self.month, self.day);
+        let clock = format!(
+            "{:02}:{:02}:{:02}{}",
+            self.clock.hour, self.clock.minute, self.clock.second, self.clock.suffix
+        );
+        match format {
+            StringFormat::Date => date,
+            StringFormat::Time => clock,
+            _ => format!("{date}T{clock}"),
+        }
+    }
+}
+
+fn parse_timestamp(format: StringFormat, text: &str) -> Option<Timestamp> {
+    let (date, clock) = match format {
+        StringFormat::Date => (parse_date(text)?, Clock::default()),
+        StringFormat::Time => ((1970, 1, 1), parse_clock(text)?),
+        _ => {
+            let split = text.find(['T', 't', ' '])?;
+            (
+                parse_date(&text[..split])?,
+                parse_clock(&text[split + 1..])?,
+            )
+        }
+    };
+    let (year, month, day) = date;
+    Some(Timestamp {
+        year,
+        month,
+        day,
+        clock,
+    })
+}
+
+fn parse_date(text: &str) -> Option<(i64, i64, i64)> {
+    let bytes = text.as_bytes();
+    if !text.is_ascii() || bytes.len() != 10 || bytes[4] != b'-' || bytes[7] != b'-' {
+        return None;
+    }
+    let year = digits(&text[0..4])?;
+    let month = digits(&text[5..7])?;
+    let day = digits(&text[8..10])?;
+    if !(1..=12).contains(&month) || !(1..=days_in_month(year, month)).contains(&day) {
+        return None;
+    }
+    Some((year, month, day))
+}
+
+/// RFC 3339 `full-time`: `HH:MM:SS[.frac]` followed by `Z` or `±HH:MM`.
+fn parse_clock(text: &str) -> Option<Clock> {
+    let bytes = text.as_bytes();
+    if !text.is_ascii() || bytes.len() < 9 || bytes[2] != b':' || bytes[5] != b':' {
+        return None;
+    }
+    let hour = digits(&text[0..2])?;
+    let minute = digits(&text[3..5])?;
+    let second = digits(&text[6..8])?;
+    if hour > 23 || minute > 59 || second > 60 {
+        return None;
+    }
+    let suffix = &text[8..];
+    let mut offset = suffix;
+    if let Some(fraction) = suffix.strip_prefix('.') {
+        let len = fraction.bytes().take_while(u8::is_ascii_digit).count();
+        if len == 0 {
+            return None;
+        }
+        offset = &fraction[len..];
+    }
+    let valid_offset = match offset.as_bytes() {
+        [b'Z' | b'z'] => true,
+        [b'+' | b'-', _, _, b':', _, _] => matches!(
+            (digits(&offset[1..3]), digits(&offset[4..6])),
+            (Some(h), Some(m)) if h <= 23 && m <= 59
+        ),
+        _ => false,
+    };
+    valid_offset.then(|| Clock {
+        hour,
+        minute,
+        second,
+        suffix: suffix.to_string(),
+    })
+}
+
+fn digits(text: &str) -> Option<i64> {
+    if text.bytes().all(|byte| byte.is_ascii_digit()) {
+        text.parse().ok()
+    } else {
+        None
+    }
+}
+
+fn days_in_month(year: i64, month: i64) -> i64 {
+    match month {
+        2 if (year % 4 == 0 && year % 100 != 0) || year % 400 == 0 => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+fn wrap(value: i64, delta: i64, min: i64, max: i64) -> i64 {
+    (value - min + delta).rem_euclid(max - min + 1) + min
+}
+
+/// Current UTC time, using the civil-from-days conversion.
+fn now() -> Timestamp {
+    let secs = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs() as i64)
+        .unwrap_or_default();
+    let days = secs.div_euclid(86_400);
+    let rem = secs.rem_euclid(86_400);
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    let clock = Clock {
+        hour: rem / 3600,
+        minute: rem % 3600 / 60,
+        second: rem % 60,
+        suffix: "Z".to_string(),
+    };
+    Timestamp {
+        year,
+        month,
+        day,
+        clock,
+    }
+}
+
+fn is_email(text: &str) -> bool {
+    let Some((local, domain)) = text.split_once('@') else {
+        return false;
+    };
+    !local.is_empty()
+        && !local.chars().any(char::is_whitespace)
+        && !domain.is_empty()
+        && domain.split('.').all(|label| {
+            !label.is_empty() && label.chars().all(|ch| ch.is_alphanumeric() || ch == '-')
+        })
+}
+
+fn is_uuid(text: &str) -> bool {
+    text.len() == 36
+        && text.char_indices().all(|(index, ch)| match index {
+            8 | 13 | 18 | 23 => ch == '-',
+            _ => ch.is_ascii_hexdigit(),
+        })
+}
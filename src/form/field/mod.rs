@@ -1,6 +1,9 @@
 pub(crate) mod components;
 mod convert;
+pub(crate) mod format;
 mod state;
 
 pub use components::CompositePopupData;
 pub use state::FieldState;
+
+pub(crate) use convert::coerce_value;
@@ -1,29 +1,61 @@
-use crate::domain::{FieldKind, FieldSchema};
+use serde_json::Value;
+
+use crate::domain::{FieldKind, FieldSchema, StringFormat, Widget};
 
 use super::super::components::{
-    ArrayBufferComponent, BoolComponent, CompositeComponent, CompositeListComponent, EnumComponent,
-    FieldComponent, KeyValueComponent, MultiSelectComponent, ScalarArrayComponent, TextComponent,
+    ArrayBufferComponent, BoolComponent, CompositeComponent, CompositeListComponent,
+    ConstComponent, DateTimeComponent, EnumComponent, FieldComponent, FormattedTextComponent,
+    IpAddressComponent, JsonComponent, KeyValueComponent, MultiSelectComponent, MultiTypeComponent,
+    ScalarArrayComponent, SliderComponent, TextComponent, TextareaComponent, TupleComponent,
 };
 use super::FieldState;
 
 impl FieldState {
     pub fn from_schema(schema: FieldSchema) -> Self {
         let component = build_component(&schema);
+        let declared_required = schema.required;
+        let null = schema.nullable && schema.default == Some(Value::Null);
         Self {
             schema,
             component,
             dirty: false,
             error: None,
+            position: 0,
+            declared_required,
+            revealed: false,
+            null,
+            condition_met: true,
         }
     }
 }
 
 fn build_component(schema: &FieldSchema) -> Box<dyn FieldComponent> {
+    if let Some(Widget::Slider { min, max, step }) = schema.ui.widget
+        && matches!(schema.kind, FieldKind::Integer | FieldKind::Number)
+    {
+        return Box::new(SliderComponent::new(schema, min, max, step));
+    }
+    if schema.ui.widget == Some(Widget::Textarea) && schema.kind == FieldKind::String {
+        return Box::new(TextareaComponent::new(schema));
+    }
     match &schema.kind {
-        FieldKind::String | FieldKind::Integer | FieldKind::Number | FieldKind::Json => {
+        FieldKind::Json => Box::new(JsonComponent::new(schema)),
+        FieldKind::String | FieldKind::Integer | FieldKind::Number => {
             Box::new(TextComponent::new(schema))
         }
+        FieldKind::Formatted(format) => match format {
+            StringFormat::Date | StringFormat::DateTime | StringFormat::Time => {
+                Box::new(DateTimeComponent::new(*format, schema))
+            }
+            StringFormat::Ipv4 | StringFormat::Ipv6 => {
+                Box::new(IpAddressComponent::new(*format, schema))
+            }
+            StringFormat::Email | StringFormat::Uri | StringFormat::Uuid => {
+                Box::new(FormattedTextComponent::new(*format, schema))
+            }
+        },
         FieldKind::Boolean => Box::new(BoolComponent::new(schema)),
+        FieldKind::Const(value) => Box::new(ConstComponent::new(value)),
         FieldKind::Enum(options) => Box::new(EnumComponent::new(options, schema)),
         FieldKind::Array(inner) => match inner.as_ref() {
             FieldKind::Enum(options) => {
@@ -32,11 +64,17 @@ fn build_component(schema: &FieldSchema) -> Box<dyn FieldComponent> {
             FieldKind::Composite(meta) => Box::new(CompositeListComponent::new(
                 &schema.pointer,
                 meta,
+                &schema.item_bounds,
                 schema.default.as_ref(),
             )),
-            FieldKind::String | FieldKind::Integer | FieldKind::Number | FieldKind::Boolean => {
-                Box::new(ScalarArrayComponent::new(schema, inner.as_ref()))
-            }
+            FieldKind::String
+            | FieldKind::Formatted(_)
+            | FieldKind::Integer
+            | FieldKind::Number
+            | FieldKind::Boolean
+            | FieldKind::Array(_)
+            | FieldKind::KeyValue(_)
+            | FieldKind::Tuple(_) => Box::new(ScalarArrayComponent::new(schema, inner.as_ref())),
             _ => Box::new(ArrayBufferComponent::new(schema)),
         },
         FieldKind::Composite(meta) => Box::new(CompositeComponent::new(&schema.pointer, meta)),
@@ -45,5 +83,24 @@ fn build_component(schema: &FieldSchema) -> Box<dyn FieldComponent> {
             template,
             schema.default.as_ref(),
         )),
+        FieldKind::Tuple(template) => Box::new(TupleComponent::new(
+            &schema.pointer,
+            template,
+            schema.default.as_ref(),
+        )),
+        FieldKind::MultiType(kinds) => {
+            let variants = kinds
+                .iter()
+                .map(|kind| {
+                    let variant = FieldSchema {
+                        kind: kind.clone(),
+                        ..schema.clone()
+                    };
+                    let component = build_component(&variant);
+                    (variant, component)
+                })
+                .collect();
+            Box::new(MultiTypeComponent::new(schema, variants))
+        }
     }
 }
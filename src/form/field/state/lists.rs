@@ -50,9 +50,10 @@ impl FieldState {
     }
 
     pub fn apply_composite_selection(&mut self, selection: usize, multi_flags: Option<Vec<bool>>) {
-        if self
-            .component
-            .apply_composite_selection(selection, multi_flags)
+        if !self.is_read_only()
+            && self
+                .component
+                .apply_composite_selection(selection, multi_flags)
         {
             self.after_edit();
         }
@@ -74,8 +75,22 @@ impl FieldState {
         self.component.collection_selected_index()
     }
 
+    /// Why the list refuses another entry, if it does.
+    pub fn composite_list_add_blocked(&self) -> Option<String> {
+        self.component.collection_add_blocked()
+    }
+
+    /// Why the list refuses to drop an entry, if it does.
+    pub fn composite_list_remove_blocked(&self) -> Option<String> {
+        self.component.collection_remove_blocked()
+    }
+
+    pub fn composite_list_duplicates(&self) -> Vec<usize> {
+        self.component.collection_duplicates()
+    }
+
     pub fn composite_list_add_entry(&mut self) -> bool {
-        if self.component.collection_add() {
+        if !self.is_read_only() && self.component.collection_add() {
             self.after_edit();
             true
         } else {
@@ -84,7 +99,7 @@ impl FieldState {
     }
 
     pub fn composite_list_remove_entry(&mut self) -> bool {
-        if self.component.collection_remove() {
+        if !self.is_read_only() && self.component.collection_remove() {
             self.after_edit();
             true
         } else {
@@ -93,7 +108,7 @@ impl FieldState {
     }
 
     pub fn composite_list_move_entry(&mut self, delta: i32) -> bool {
-        if self.component.collection_move(delta) {
+        if !self.is_read_only() && self.component.collection_move(delta) {
             self.after_edit();
             true
         } else {
@@ -105,6 +120,7 @@ impl FieldState {
         &mut self,
         variant_index: usize,
     ) -> Result<CompositeEditorSession, FieldCoercionError> {
+        self.ensure_editable()?;
         self.component
             .open_composite_editor(&self.schema.pointer, variant_index)
     }
@@ -112,19 +128,32 @@ impl FieldState {
     pub fn open_composite_list_editor(
         &mut self,
     ) -> Result<CompositeListEditorContext, FieldCoercionError> {
+        self.ensure_editable()?;
         self.component
             .open_composite_list_editor(&self.schema.pointer)
     }
 
     pub fn open_key_value_editor(&mut self) -> Result<KeyValueEditorContext, FieldCoercionError> {
+        self.ensure_editable()?;
         self.component.open_key_value_editor(&self.schema.pointer)
     }
 
     pub fn open_scalar_array_editor(&mut self) -> Result<ArrayEditorContext, FieldCoercionError> {
+        self.ensure_editable()?;
         self.component
             .open_scalar_array_editor(&self.schema.pointer)
     }
 
+    fn ensure_editable(&self) -> Result<(), FieldCoercionError> {
+        if self.is_read_only() {
+            return Err(FieldCoercionError {
+                pointer: self.schema.pointer.clone(),
+                message: format!("{} is read-only", self.schema.display_label()),
+            });
+        }
+        Ok(())
+    }
+
     pub fn close_composite_editor(&mut self, session: CompositeEditorSession, mark_dirty: bool) {
         if mark_dirty {
             self.after_edit();
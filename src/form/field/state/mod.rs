@@ -13,10 +13,50 @@ pub struct FieldState {
     pub(crate) component: Box<dyn FieldComponent>,
     pub dirty: bool,
     pub error: Option<String>,
+    pub(crate) position: usize,
+    pub(crate) declared_required: bool,
+    pub(crate) revealed: bool,
+    /// Explicitly set to `null` (only for nullable fields); any edit clears it.
+    pub(crate) null: bool,
+    /// `visible_when` holds; fields tucked away by `x-hidden`/`x-advanced`
+    /// keep contributing to the output.
+    pub(crate) condition_met: bool,
 }
 
 impl FieldState {
     pub fn component_kind(&self) -> ComponentKind {
         self.component.kind()
     }
+
+    pub fn is_read_only(&self) -> bool {
+        self.schema.read_only
+    }
+
+    /// Whether a `writeOnly` value should be masked when rendered.
+    pub fn is_masked(&self) -> bool {
+        self.schema.is_secret() && !self.revealed
+    }
+
+    pub fn is_null(&self) -> bool {
+        self.null
+    }
+
+    /// Switches a nullable field between `null` and its edited value.
+    pub fn toggle_null(&mut self) -> bool {
+        if !self.schema.nullable || self.is_read_only() {
+            return false;
+        }
+        self.null = !self.null;
+        self.dirty = true;
+        self.error = None;
+        true
+    }
+
+    pub fn toggle_reveal(&mut self) -> bool {
+        if !self.schema.is_secret() {
+            return false;
+        }
+        self.revealed = !self.revealed;
+        true
+    }
 }
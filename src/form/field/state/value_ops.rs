@@ -2,20 +2,42 @@ use serde_json::Value;
 
 use crate::form::error::FieldCoercionError;
 
+use super::super::components::TextCursor;
 use super::FieldState;
 
 impl FieldState {
     pub fn seed_value(&mut self, value: &Value) {
-        self.component.seed_value(&self.schema, value);
+        self.null = value.is_null() && self.schema.nullable;
+        if !self.null {
+            self.component.seed_value(&self.schema, value);
+        }
         self.dirty = false;
         self.error = None;
     }
 
     pub fn display_value(&self) -> String {
+        if self.null {
+            return "null".to_string();
+        }
         self.component.display_value(&self.schema)
     }
 
+    /// Caret of an editable text input; `None` for other widgets.
+    pub fn text_cursor(&self) -> Option<TextCursor> {
+        if self.null || self.is_read_only() {
+            return None;
+        }
+        self.component.text_cursor()
+    }
+
+    pub fn editor_hint(&self) -> Option<String> {
+        self.component.editor_hint()
+    }
+
     pub fn current_value(&self) -> Result<Option<Value>, FieldCoercionError> {
+        if self.null {
+            return Ok(Some(Value::Null));
+        }
         self.component.current_value(&self.schema)
     }
 
@@ -28,6 +50,7 @@ impl FieldState {
     }
 
     pub fn after_edit(&mut self) {
+        self.null = false;
         self.dirty = true;
         self.error = None;
     }
@@ -0,0 +1,142 @@
+use super::{
+    field::FieldState,
+    state::{FormState, RootSectionState},
+};
+
+/// Most undo steps kept; older ones are dropped first.
+const HISTORY_LIMIT: usize = 100;
+
+/// Undo/redo stacks of whole-form snapshots. Each step stores the form as it
+/// was before the change, so undoing a list removal or a variant switch
+/// brings back the exact entries and sub-forms.
+#[derive(Debug, Clone, Default)]
+pub struct FormHistory {
+    undo: Vec<FormSnapshot>,
+    redo: Vec<FormSnapshot>,
+    /// Field the latest step was typed into; more keystrokes there extend it.
+    typing: Option<String>,
+}
+
+/// Field states and focus at one point in time.
+#[derive(Debug, Clone)]
+pub struct FormSnapshot {
+    roots: Vec<RootSectionState>,
+    root_index: usize,
+    section_index: usize,
+    field_index: usize,
+}
+
+/// The focused field before a keystroke. Far cheaper to take than a
+/// [`FormSnapshot`], which is only built once the key changed something.
+#[derive(Debug, Clone)]
+pub struct FieldCheckpoint {
+    field: FieldState,
+}
+
+impl FormState {
+    /// The state to hand back to [`FormState::record_change`] once a change
+    /// has been applied.
+    pub fn checkpoint(&self) -> FormSnapshot {
+        FormSnapshot {
+            roots: self.roots.clone(),
+            root_index: self.root_index,
+            section_index: self.section_index,
+            field_index: self.field_index,
+        }
+    }
+
+    /// Makes the change since `before` one undo step and forgets undone
+    /// steps. Passing the pointer of a field being typed into merges
+    /// consecutive keystrokes into the same step.
+    pub fn record_change(&mut self, before: FormSnapshot, typing: Option<&str>) {
+        let history = &mut self.history;
+        history.redo.clear();
+        if typing.is_some() && history.typing.as_deref() == typing {
+            return;
+        }
+        history.typing = typing.map(str::to_string);
+        history.undo.push(before);
+        if history.undo.len() > HISTORY_LIMIT {
+            history.undo.remove(0);
+        }
+    }
+
+    pub fn field_checkpoint(&mut self) -> Option<FieldCheckpoint> {
+        self.focused_field_mut().map(|field| FieldCheckpoint {
+            field: field.clone(),
+        })
+    }
+
+    /// Records a keystroke that changed the focused field since `before`.
+    /// Keystrokes that extend the current typing step skip the snapshot.
+    pub fn record_field_change(&mut self, before: FieldCheckpoint) {
+        let pointer = before.field.schema.pointer.clone();
+        if self.history.typing.as_deref() == Some(pointer.as_str()) {
+            self.history.redo.clear();
+            return;
+        }
+        let mut snapshot = self.checkpoint();
+        if let Some(slot) = snapshot
+            .roots
+            .get_mut(snapshot.root_index)
+            .and_then(|root| root.sections.get_mut(snapshot.section_index))
+            .and_then(|section| {
+                section
+                    .fields
+                    .iter_mut()
+                    .find(|field| field.schema.pointer == pointer)
+            })
+        {
+            *slot = before.field;
+        }
+        self.record_change(snapshot, Some(&pointer));
+    }
+
+    /// Drops every step. Sub-forms are cleared when stored back into their
+    /// field so snapshots of the host form do not carry their history.
+    pub fn clear_history(&mut self) {
+        self.history = FormHistory::default();
+    }
+
+    /// Ends the typing step so the next keystroke starts a new one.
+    pub fn end_typing(&mut self) {
+        self.history.typing = None;
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.history.undo.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.history.redo.is_empty()
+    }
+
+    pub fn undo(&mut self) -> bool {
+        let Some(snapshot) = self.history.undo.pop() else {
+            return false;
+        };
+        let current = self.checkpoint();
+        self.history.redo.push(current);
+        self.restore(snapshot);
+        true
+    }
+
+    pub fn redo(&mut self) -> bool {
+        let Some(snapshot) = self.history.redo.pop() else {
+            return false;
+        };
+        let current = self.checkpoint();
+        self.history.undo.push(current);
+        self.restore(snapshot);
+        true
+    }
+
+    fn restore(&mut self, snapshot: FormSnapshot) {
+        self.history.typing = None;
+        self.roots = snapshot.roots;
+        self.root_index = snapshot.root_index;
+        self.section_index = snapshot.section_index;
+        self.field_index = snapshot.field_index;
+        self.refresh_conditions();
+    }
+}
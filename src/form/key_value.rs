@@ -3,12 +3,18 @@ use serde_json::{Map, Value};
 use crate::domain::{FieldKind, FieldSchema, KeyValueField};
 
 use super::{
-    error::FieldCoercionError, field::FieldState, section::SectionState, state::FormState,
+    error::FieldCoercionError,
+    field::{FieldState, coerce_value},
+    section::SectionState,
+    state::FormState,
 };
 
 #[derive(Debug, Clone)]
 pub struct KeyValueState {
     pointer: String,
+    /// Where entry keys are appended: the field's pointer, or the enclosing
+    /// object's for an inline map.
+    entry_pointer: String,
     template: KeyValueField,
     entries: Vec<KeyValueEntry>,
     selected: usize,
@@ -25,6 +31,41 @@ struct KeyValueEntry {
 pub struct KeyValueEditorSession {
     pub form_state: FormState,
     pub schema: Value,
+    template: Box<KeyValueField>,
+    route: Option<usize>,
+}
+
+impl KeyValueEditorSession {
+    /// Swaps the value editor for the schema routed by the key currently
+    /// typed, carrying the value over when it converts. Returns whether the
+    /// route changed.
+    pub fn sync_route(&mut self) -> bool {
+        let key = self
+            .form_state
+            .field_by_pointer("/key")
+            .and_then(|field| field.current_value().ok().flatten());
+        let route = key
+            .as_ref()
+            .and_then(Value::as_str)
+            .and_then(|key| self.template.route_for(key));
+        if route == self.route {
+            return false;
+        }
+        self.route = route;
+        self.schema = entry_schema(&self.template, route).clone();
+        if let Some(field) = self.form_state.field_mut_by_pointer("/value") {
+            let previous = field.current_value().ok().flatten();
+            let mut next = FieldState::from_schema(value_field_schema(&self.template, route));
+            next.position = field.position;
+            if let Some(value) = previous.and_then(|value| coerce_value(&value, &next.schema.kind))
+            {
+                next.seed_value(&value);
+            }
+            next.dirty = true;
+            *field = next;
+        }
+        true
+    }
 }
 
 #[derive(Debug)]
@@ -36,8 +77,13 @@ pub struct KeyValueEditorContext {
 
 impl KeyValueState {
     pub fn new(pointer: &str, template: &KeyValueField, default: Option<&Value>) -> Self {
+        let entry_pointer = match pointer.rsplit_once('/') {
+            Some((object, _)) if template.inline => object,
+            _ => pointer,
+        };
         let mut state = Self {
             pointer: pointer.to_string(),
+            entry_pointer: entry_pointer.to_string(),
             template: template.clone(),
             entries: Vec::new(),
             selected: 0,
@@ -144,9 +190,12 @@ impl KeyValueState {
                     message: "key cannot be empty".to_string(),
                 });
             }
+            if let Some(error) = self.reserved_key_error(key) {
+                return Err(error);
+            }
             if map.insert(key.to_string(), entry.value.clone()).is_some() {
                 return Err(FieldCoercionError {
-                    pointer: pointer_for_key(&self.pointer, key),
+                    pointer: pointer_for_key(&self.entry_pointer, key),
                     message: format!("duplicate key '{key}'"),
                 });
             }
@@ -157,6 +206,9 @@ impl KeyValueState {
     pub fn seed_entries_from_object(&mut self, map: &Map<String, Value>) {
         self.entries.clear();
         for (key, value) in map {
+            if self.is_reserved(key) {
+                continue;
+            }
             self.entries.push(KeyValueEntry {
                 key: key.clone(),
                 value: value.clone(),
@@ -178,13 +230,16 @@ impl KeyValueState {
             pointer: self.pointer.clone(),
             message: "invalid entry selection".to_string(),
         })?;
-        let form_state = self.build_form_state(Some(entry));
+        let route = self.template.route_for(&entry.key);
+        let form_state = self.build_form_state(Some(entry), route);
         Ok(KeyValueEditorContext {
             entry_index: idx,
             entry_label: format!("{} = {}", entry.key, summarize_value(&entry.value)),
             session: KeyValueEditorSession {
                 form_state,
-                schema: self.template.entry_schema.clone(),
+                schema: entry_schema(&self.template, route).clone(),
+                template: Box::new(self.template.clone()),
+                route,
             },
         })
     }
@@ -210,16 +265,19 @@ impl KeyValueState {
         };
         if key.trim().is_empty() {
             return Err(FieldCoercionError {
-                pointer: pointer_for_key(&self.pointer, &key),
+                pointer: pointer_for_key(&self.entry_pointer, &key),
                 message: "key cannot be empty".to_string(),
             });
         }
+        if let Some(error) = self.reserved_key_error(&key) {
+            return Err(error);
+        }
         let value_field = object.get("value").cloned().unwrap_or(Value::Null);
 
         for (idx, existing) in self.entries.iter().enumerate() {
             if idx != entry_index && existing.key == key {
                 return Err(FieldCoercionError {
-                    pointer: pointer_for_key(&self.pointer, &key),
+                    pointer: pointer_for_key(&self.entry_pointer, &key),
                     message: format!("duplicate key '{key}'"),
                 });
             }
@@ -248,16 +306,14 @@ impl KeyValueState {
         Ok(changed)
     }
 
-    fn build_form_state(&self, entry: Option<&KeyValueEntry>) -> FormState {
+    fn build_form_state(&self, entry: Option<&KeyValueEntry>, route: Option<usize>) -> FormState {
         let mut key_schema = self.key_field_schema();
         key_schema.default = entry
             .map(|item| Value::String(item.key.clone()))
             .or_else(|| self.template.key_default.clone());
-        let mut value_schema = self.value_field_schema();
+        let mut value_schema = value_field_schema(&self.template, route);
         if let Some(item) = entry {
             value_schema.default = Some(item.value.clone());
-        } else if value_schema.default.is_none() {
-            value_schema.default = self.template.value_default.clone();
         }
 
         let key_field = FieldState::from_schema(key_schema);
@@ -269,6 +325,7 @@ impl KeyValueState {
             path: vec!["entry".to_string()],
             depth: 0,
             fields: vec![key_field, value_field],
+            hidden_fields: Vec::new(),
             scroll_offset: 0,
         };
         FormState::from_sections("key_value", "Key/Value Entry", None, vec![section])
@@ -285,36 +342,100 @@ impl KeyValueState {
             kind: FieldKind::String,
             required: true,
             default: self.template.key_default.clone(),
+            read_only: false,
+            write_only: false,
+            nullable: false,
+            item_bounds: Default::default(),
+            ui: Default::default(),
             metadata: Default::default(),
+            visible_when: Vec::new(),
+            required_when: Vec::new(),
         }
     }
 
-    fn value_field_schema(&self) -> FieldSchema {
-        FieldSchema {
-            name: "value".to_string(),
-            path: vec!["value".to_string()],
-            pointer: "/value".to_string(),
-            title: self.template.value_title.clone(),
-            description: self.template.value_description.clone(),
-            section_id: "key_value".to_string(),
-            kind: (*self.template.value_kind).clone(),
-            required: true,
-            default: self.template.value_default.clone(),
-            metadata: Default::default(),
-        }
+    fn is_reserved(&self, key: &str) -> bool {
+        self.template
+            .reserved_keys
+            .iter()
+            .any(|reserved| reserved == key)
+    }
+
+    fn reserved_key_error(&self, key: &str) -> Option<FieldCoercionError> {
+        self.is_reserved(key).then(|| FieldCoercionError {
+            pointer: pointer_for_key(&self.entry_pointer, key),
+            message: format!("key '{key}' is a declared property"),
+        })
     }
 
     fn next_placeholder_key(&mut self) -> String {
         loop {
             let candidate = format!("key-{}", self.counter + 1);
             self.counter = self.counter.saturating_add(1);
-            if !self.entries.iter().any(|entry| entry.key == candidate) {
+            if !self.entries.iter().any(|entry| entry.key == candidate)
+                && !self.is_reserved(&candidate)
+            {
                 return candidate;
             }
         }
     }
 }
 
+fn entry_schema(template: &KeyValueField, route: Option<usize>) -> &Value {
+    match route.and_then(|index| template.routes.get(index)) {
+        Some(route) => &route.entry_schema,
+        None => &template.entry_schema,
+    }
+}
+
+/// The value field for `route`; its description names the pattern that
+/// applies when the map has several.
+fn value_field_schema(template: &KeyValueField, route: Option<usize>) -> FieldSchema {
+    let routed = route.and_then(|index| template.routes.get(index));
+    let (title, description, default, kind) = match routed {
+        Some(route) => (
+            &route.value_title,
+            &route.value_description,
+            &route.value_default,
+            &route.value_kind,
+        ),
+        None => (
+            &template.value_title,
+            &template.value_description,
+            &template.value_default,
+            &template.value_kind,
+        ),
+    };
+    let rule = match routed {
+        Some(route) => Some(format!("keys matching `{}`", route.pattern.as_str())),
+        None if !template.routes.is_empty() => Some("keys matching no pattern".to_string()),
+        None => None,
+    };
+    let description = match (description, rule) {
+        (Some(text), Some(rule)) => Some(format!("{text} ({rule})")),
+        (None, Some(rule)) => Some(format!("Value for {rule}")),
+        (text, None) => text.clone(),
+    };
+    FieldSchema {
+        name: "value".to_string(),
+        path: vec!["value".to_string()],
+        pointer: "/value".to_string(),
+        title: title.clone(),
+        description,
+        section_id: "key_value".to_string(),
+        kind: (**kind).clone(),
+        required: true,
+        default: default.clone(),
+        read_only: false,
+        write_only: false,
+        nullable: false,
+        item_bounds: Default::default(),
+        ui: Default::default(),
+        metadata: Default::default(),
+        visible_when: Vec::new(),
+        required_when: Vec::new(),
+    }
+}
+
 pub(crate) fn summarize_value(value: &Value) -> String {
     match value {
         Value::Null => "null".to_string(),
@@ -1,12 +1,15 @@
 pub mod actions;
 mod array;
 mod composite;
+mod condition;
 mod error;
 pub(crate) mod field;
+mod history;
 pub(crate) mod key_value;
 pub mod reducers;
 mod section;
 mod state;
+mod tuple;
 
 pub use actions::FormCommand;
 pub use array::ArrayEditorSession;
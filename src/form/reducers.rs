@@ -3,12 +3,16 @@ use jsonschema::Validator;
 use super::{actions::FormCommand, state::FormState};
 
 pub fn apply_command(state: &mut FormState, command: FormCommand) {
+    if !matches!(command, FormCommand::FieldEdited { .. }) {
+        // Leaving a field closes its typing step in the undo history.
+        state.end_typing();
+    }
     match command {
         FormCommand::FocusNextField => state.focus_next_field(),
         FormCommand::FocusPrevField => state.focus_prev_field(),
         FormCommand::FocusNextSection(delta) => state.focus_next_section(delta),
         FormCommand::FocusNextRoot(delta) => state.focus_next_root(delta),
-        FormCommand::FieldEdited { .. } => {}
+        FormCommand::FieldEdited { .. } => state.refresh_conditions(),
     }
 }
 
@@ -33,6 +37,7 @@ impl<'a> FormEngine<'a> {
     }
 
     fn validate_field(&mut self, pointer: &str) -> Result<(), String> {
+        self.state.refresh_conditions();
         match self.state.try_build_value() {
             Ok(value) => {
                 self.state.clear_error(pointer);
@@ -1,6 +1,8 @@
+use serde_json::Value;
+
 use crate::domain::FormSection;
 
-use super::field::FieldState;
+use super::{condition::condition_holds, field::FieldState};
 
 #[derive(Debug, Clone)]
 pub struct SectionState {
@@ -12,6 +14,7 @@ pub struct SectionState {
     pub path: Vec<String>,
     pub depth: usize,
     pub fields: Vec<FieldState>,
+    pub(crate) hidden_fields: Vec<FieldState>,
     pub scroll_offset: usize,
 }
 
@@ -21,7 +24,12 @@ impl SectionState {
             .fields
             .iter()
             .cloned()
-            .map(FieldState::from_schema)
+            .enumerate()
+            .map(|(position, schema)| {
+                let mut field = FieldState::from_schema(schema);
+                field.position = position;
+                field
+            })
             .collect();
         acc.push(SectionState {
             id: section.id.clone(),
@@ -30,10 +38,64 @@ impl SectionState {
             path: section.path.clone(),
             depth,
             fields,
+            hidden_fields: Vec::new(),
             scroll_offset: 0,
         });
         for child in &section.children {
             SectionState::collect(child, depth + 1, acc);
         }
     }
+
+    pub(crate) fn has_conditions(&self) -> bool {
+        self.fields.iter().chain(&self.hidden_fields).any(|field| {
+            !field.schema.visible_when.is_empty()
+                || !field.schema.required_when.is_empty()
+                || field.schema.ui.hidden
+                || field.schema.ui.advanced
+        })
+    }
+
+    /// `x-advanced` fields currently collapsed.
+    pub fn collapsed_advanced(&self) -> usize {
+        self.hidden_fields
+            .iter()
+            .filter(|field| {
+                field.condition_met && field.schema.ui.advanced && !field.schema.ui.hidden
+            })
+            .count()
+    }
+
+    /// Re-evaluate `visible_when`/`required_when` against the current form value,
+    /// moving fields between the visible and hidden lists in declaration order.
+    /// `x-hidden` fields, and `x-advanced` ones unless `show_advanced`, are
+    /// kept off screen as well.
+    pub(crate) fn apply_conditions(&mut self, root: &Value, show_advanced: bool) {
+        let mut all = std::mem::take(&mut self.fields);
+        all.append(&mut self.hidden_fields);
+        all.sort_by_key(|field| field.position);
+        for mut field in all {
+            field.schema.required = field.declared_required
+                || field
+                    .schema
+                    .required_when
+                    .iter()
+                    .any(|condition| condition_holds(condition, root));
+            let visible = field
+                .schema
+                .visible_when
+                .iter()
+                .all(|condition| condition_holds(condition, root));
+            field.condition_met = visible;
+            let shown =
+                visible && !field.schema.ui.hidden && (show_advanced || !field.schema.ui.advanced);
+            if shown {
+                self.fields.push(field);
+            } else {
+                if !visible {
+                    field.clear_error();
+                }
+                self.hidden_fields.push(field);
+            }
+        }
+    }
 }
@@ -1,8 +1,10 @@
 use serde_json::{Map, Value};
 
-use crate::domain::FormSchema;
+use crate::domain::{FieldKind, FieldSchema, FormSchema};
 
-use super::{error::FieldCoercionError, field::FieldState, section::SectionState};
+use super::{
+    error::FieldCoercionError, field::FieldState, history::FormHistory, section::SectionState,
+};
 
 #[derive(Debug, Clone)]
 pub struct RootSectionState {
@@ -20,6 +22,9 @@ pub struct FormState {
     pub root_index: usize,
     pub section_index: usize,
     pub field_index: usize,
+    /// Whether `x-advanced` fields are listed.
+    pub show_advanced: bool,
+    pub(crate) history: FormHistory,
 }
 
 impl FormState {
@@ -49,7 +54,10 @@ impl FormState {
             root_index: 0,
             section_index: 0,
             field_index: 0,
+            show_advanced: false,
+            history: FormHistory::default(),
         };
+        state.refresh_conditions();
         state.normalize_focus();
         state
     }
@@ -70,6 +78,8 @@ impl FormState {
             root_index: 0,
             section_index: 0,
             field_index: 0,
+            show_advanced: false,
+            history: FormHistory::default(),
         };
         state.normalize_focus();
         state
@@ -172,9 +182,12 @@ impl FormState {
     pub fn try_build_value(&self) -> Result<Value, FieldCoercionError> {
         let mut root = Value::Object(Map::new());
         for section in self.iter_sections() {
-            for field in &section.fields {
+            for field in section.fields.iter().chain(&section.hidden_fields) {
+                if !field.condition_met {
+                    continue;
+                }
                 if let Some(value) = field.current_value()? {
-                    insert_path(&mut root, &field.schema.path, value);
+                    insert_field_value(&mut root, &field.schema, value);
                 }
             }
         }
@@ -183,12 +196,67 @@ impl FormState {
 
     pub fn seed_from_value(&mut self, value: &Value) {
         for section in self.iter_sections_mut() {
-            for field in &mut section.fields {
+            for field in section.fields.iter_mut().chain(&mut section.hidden_fields) {
                 if let Some(subvalue) = value_at_path(value, &field.schema.path) {
                     field.seed_value(subvalue);
                 }
             }
         }
+        self.refresh_conditions();
+    }
+
+    /// Show, hide, or toggle `required` on conditional fields based on the
+    /// values currently entered. Focus stays on the same field when it remains
+    /// visible.
+    pub fn refresh_conditions(&mut self) {
+        if !self.iter_sections().any(SectionState::has_conditions) {
+            return;
+        }
+        let snapshot = self.snapshot_value();
+        let focused = self
+            .focused_field()
+            .map(|field| (field.schema.pointer.clone(), field.position));
+        let show_advanced = self.show_advanced;
+        for section in self.iter_sections_mut() {
+            section.apply_conditions(&snapshot, show_advanced);
+        }
+        if let Some((pointer, position)) = focused
+            && let Some(section) = self.active_section()
+        {
+            self.field_index = section
+                .fields
+                .iter()
+                .position(|field| field.schema.pointer == pointer && field.position == position)
+                .or_else(|| {
+                    section
+                        .fields
+                        .iter()
+                        .position(|field| field.position > position)
+                })
+                .unwrap_or(self.field_index);
+        }
+        self.normalize_focus();
+    }
+
+    /// Lists or collapses `x-advanced` fields.
+    pub fn toggle_advanced(&mut self) {
+        self.show_advanced = !self.show_advanced;
+        self.refresh_conditions();
+    }
+
+    fn snapshot_value(&self) -> Value {
+        let mut root = Value::Object(Map::new());
+        for section in self.iter_sections() {
+            for field in section.fields.iter().chain(&section.hidden_fields) {
+                if !field.condition_met {
+                    continue;
+                }
+                if let Ok(Some(value)) = field.current_value() {
+                    insert_field_value(&mut root, &field.schema, value);
+                }
+            }
+        }
+        root
     }
 
     pub fn clear_errors(&mut self) {
@@ -201,7 +269,7 @@ impl FormState {
 
     pub fn mark_clean(&mut self) {
         for section in self.iter_sections_mut() {
-            for field in &mut section.fields {
+            for field in section.fields.iter_mut().chain(&mut section.hidden_fields) {
                 field.dirty = false;
             }
         }
@@ -251,6 +319,41 @@ impl FormState {
         None
     }
 
+    /// The field whose value holds `pointer`: the field at that pointer or
+    /// the one owning an array item or map entry below it. Hidden fields are
+    /// included.
+    pub fn field_for_pointer(&self, pointer: &str) -> Option<&FieldState> {
+        self.iter_sections()
+            .flat_map(|section| section.fields.iter().chain(&section.hidden_fields))
+            .filter_map(|field| owned_prefix(field, pointer).map(|len| (field, len)))
+            .max_by_key(|(_, len)| *len)
+            .map(|(field, _)| field)
+    }
+
+    /// Focuses the visible field whose value holds `pointer`. Returns whether
+    /// one was found.
+    pub fn focus_pointer(&mut self, pointer: &str) -> bool {
+        let mut best: Option<(usize, usize, usize, usize)> = None;
+        for (root_index, root) in self.roots.iter().enumerate() {
+            for (section_index, section) in root.sections.iter().enumerate() {
+                for (field_index, field) in section.fields.iter().enumerate() {
+                    if let Some(own) = owned_prefix(field, pointer)
+                        && best.is_none_or(|(_, _, _, len)| own > len)
+                    {
+                        best = Some((root_index, section_index, field_index, own));
+                    }
+                }
+            }
+        }
+        let Some((root_index, section_index, field_index, _)) = best else {
+            return false;
+        };
+        self.root_index = root_index;
+        self.section_index = section_index;
+        self.field_index = field_index;
+        true
+    }
+
     pub fn is_dirty(&self) -> bool {
         self.iter_sections()
             .any(|section| section.fields.iter().any(|field| field.dirty))
@@ -398,6 +501,21 @@ impl FormState {
     }
 }
 
+/// Writes a field's value at its path; inline maps spread their entries into
+/// the object at that path instead of replacing it.
+fn insert_field_value(root: &mut Value, schema: &FieldSchema, value: Value) {
+    match (&schema.kind, value) {
+        (FieldKind::KeyValue(map), Value::Object(entries)) if map.inline => {
+            for (key, entry) in entries {
+                let mut path = schema.path.clone();
+                path.push(key);
+                insert_path(root, &path, entry);
+            }
+        }
+        (_, value) => insert_path(root, &schema.path, value),
+    }
+}
+
 fn insert_path(root: &mut Value, path: &[String], value: Value) {
     if path.is_empty() {
         *root = value;
@@ -429,6 +547,7 @@ fn empty_section_state() -> SectionState {
         path: Vec::new(),
         depth: 0,
         fields: Vec::new(),
+        hidden_fields: Vec::new(),
         scroll_offset: 0,
     }
 }
@@ -454,3 +573,28 @@ fn value_at_path<'a>(value: &'a Value, path: &[String]) -> Option<&'a Value> {
     }
     Some(current)
 }
+
+/// Whether `pointer` is `base` itself or lies below it.
+/// Length of the prefix through which `field` holds `pointer`, if it does.
+/// An inline map's entries sit in the enclosing object beside the declared
+/// properties, so it holds the object's other keys.
+fn owned_prefix(field: &FieldState, pointer: &str) -> Option<usize> {
+    let own = &field.schema.pointer;
+    if pointer_within(own, pointer) {
+        return Some(own.len());
+    }
+    let FieldKind::KeyValue(map) = &field.schema.kind else {
+        return None;
+    };
+    let (object, _) = own.rsplit_once('/')?;
+    let key = pointer.strip_prefix(object)?.strip_prefix('/')?;
+    let key = key.split('/').next().unwrap_or_default();
+    let key = key.replace("~1", "/").replace("~0", "~");
+    (map.inline && !map.reserved_keys.contains(&key)).then_some(object.len())
+}
+
+fn pointer_within(base: &str, pointer: &str) -> bool {
+    pointer
+        .strip_prefix(base)
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+}
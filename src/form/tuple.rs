@@ -0,0 +1,138 @@
+use serde_json::{Map, Value};
+
+use crate::domain::{FormSchema, FormSection, RootSection, TupleField};
+
+use super::{composite::CompositeEditorSession, error::FieldCoercionError, state::FormState};
+
+#[derive(Debug, Clone)]
+pub struct TupleState {
+    pointer: String,
+    template: TupleField,
+    form: FormState,
+    extra: Vec<Value>,
+}
+
+impl TupleState {
+    pub fn new(pointer: &str, template: &TupleField, default: Option<&Value>) -> Self {
+        let mut state = Self {
+            pointer: pointer.to_string(),
+            template: template.clone(),
+            form: positional_form(template),
+            extra: Vec::new(),
+        };
+        if let Some(Value::Array(items)) = default {
+            state.seed_from_array(items);
+        }
+        state
+    }
+
+    pub fn seed_from_array(&mut self, items: &[Value]) {
+        let declared = self.template.items.len();
+        let positions = items
+            .iter()
+            .take(declared)
+            .enumerate()
+            .map(|(index, item)| (index.to_string(), item.clone()))
+            .collect::<Map<_, _>>();
+        self.form = positional_form(&self.template);
+        self.form.seed_from_value(&Value::Object(positions));
+        self.extra = if self.template.allow_additional {
+            items.iter().skip(declared).cloned().collect()
+        } else {
+            Vec::new()
+        };
+    }
+
+    pub fn summary(&self) -> String {
+        let label = match self.build_value(false) {
+            Ok(Some(value)) => value.to_string(),
+            Ok(None) => "[]".to_string(),
+            Err(err) => format!("<invalid: {}>", err.message),
+        };
+        format!("Tuple {label}")
+    }
+
+    pub fn take_editor_session(&self) -> CompositeEditorSession {
+        CompositeEditorSession {
+            variant_index: 0,
+            title: "Items".to_string(),
+            description: None,
+            form_state: self.form.clone(),
+            schema: self.template.entry_schema.clone(),
+        }
+    }
+
+    pub fn restore_editor_session(&mut self, session: CompositeEditorSession) {
+        self.form = session.form_state;
+        self.form.clear_history();
+    }
+
+    /// Positions are emitted in order; a position may only be left empty when
+    /// every position after it is empty too.
+    pub fn build_value(&self, required: bool) -> Result<Option<Value>, FieldCoercionError> {
+        let positions = match self.form.try_build_value() {
+            Ok(Value::Object(map)) => map,
+            Ok(_) => Map::new(),
+            Err(mut err) => {
+                err.pointer = format!("{}{}", self.pointer, err.pointer);
+                return Err(err);
+            }
+        };
+        let mut values = Vec::new();
+        let mut gap = None;
+        for index in 0..self.template.items.len() {
+            match positions.get(&index.to_string()) {
+                Some(value) => {
+                    if let Some(missing) = gap {
+                        return Err(FieldCoercionError {
+                            pointer: format!("{}/{missing}", self.pointer),
+                            message: format!(
+                                "item {} must be set before item {}",
+                                missing + 1,
+                                index + 1
+                            ),
+                        });
+                    }
+                    values.push(value.clone());
+                }
+                None => {
+                    gap.get_or_insert(index);
+                }
+            }
+        }
+        if let Some(missing) = gap
+            && !self.extra.is_empty()
+        {
+            return Err(FieldCoercionError {
+                pointer: format!("{}/{missing}", self.pointer),
+                message: format!("item {} must be set before additional items", missing + 1),
+            });
+        }
+        values.extend(self.extra.iter().cloned());
+        if values.is_empty() && !required {
+            return Ok(None);
+        }
+        Ok(Some(Value::Array(values)))
+    }
+}
+
+fn positional_form(template: &TupleField) -> FormState {
+    FormState::from_schema(&FormSchema {
+        title: None,
+        description: None,
+        roots: vec![RootSection {
+            id: "tuple".to_string(),
+            title: "Items".to_string(),
+            description: None,
+            sections: vec![FormSection {
+                id: "tuple".to_string(),
+                title: "Items".to_string(),
+                description: None,
+                path: Vec::new(),
+                fields: template.items.clone(),
+                children: Vec::new(),
+            }],
+        }],
+        diagnostics: Vec::new(),
+    })
+}
@@ -0,0 +1,327 @@
+//! Public, serializable view of the form schemaui lays out for a JSON Schema.
+//!
+//! The TUI works on internal types that change with its widgets; this module
+//! mirrors the parts a custom renderer or linter needs (sections, pointers,
+//! kinds, defaults, required flags) in a shape that round-trips through
+//! serde.
+
+use std::{collections::HashMap, fmt};
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{domain, schema::layout::to_pointer};
+
+/// Lays out `schema` the same way `SchemaUI` does. External `$ref`s are read
+/// from the file system; use [`crate::SchemaUI::form_schema`] to go through a
+/// custom retriever or base location.
+pub fn build_form_schema(schema: &Value) -> Result<FormSchema> {
+    domain::parse_form_schema(schema).map(|form| FormSchema::from(&form))
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FormSchema {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Top-level tabs, in display order.
+    pub roots: Vec<RootSection>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+/// Something in the schema the form cannot honor exactly: an `allOf`
+/// conflict, an ignored keyword, or a field edited as raw JSON because its
+/// schema is unsupported.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Diagnostic {
+    pub pointer: String,
+    pub message: String,
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "#{}: {}", self.pointer, self.message)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RootSection {
+    pub id: String,
+    pub title: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    pub sections: Vec<Section>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Section {
+    pub id: String,
+    pub title: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// JSON Pointer of the object this section edits (`""` for the root).
+    pub pointer: String,
+    pub fields: Vec<Field>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub children: Vec<Section>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Field {
+    pub name: String,
+    /// JSON Pointer of the value in the output document.
+    pub pointer: String,
+    pub title: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    pub kind: FieldKind,
+    pub required: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default: Option<Value>,
+    #[serde(default)]
+    pub nullable: bool,
+    #[serde(default)]
+    pub read_only: bool,
+    #[serde(default)]
+    pub write_only: bool,
+    /// `x-hidden`: not rendered, but still part of the output.
+    #[serde(default)]
+    pub hidden: bool,
+    /// `x-*` keys schemaui does not read itself, e.g. for a custom renderer.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub metadata: HashMap<String, Value>,
+}
+
+/// The control a field maps to. Serialized with a `type` tag, e.g.
+/// `{"type": "array", "items": {"type": "integer"}}`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum FieldKind {
+    String {
+        /// Recognized `format` keyword (`date`, `uuid`, ...).
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        format: Option<String>,
+    },
+    Integer,
+    Number,
+    Boolean,
+    Enum {
+        options: Vec<EnumOption>,
+    },
+    Const {
+        value: Value,
+    },
+    Array {
+        items: Box<FieldKind>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        min_items: Option<usize>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        max_items: Option<usize>,
+        #[serde(default)]
+        unique_items: bool,
+    },
+    Tuple {
+        items: Vec<Field>,
+        additional_items: bool,
+    },
+    /// `patternProperties` / `additionalProperties` map.
+    Map {
+        key_schema: Value,
+        value: Box<FieldKind>,
+        /// Extra entries of an object with declared properties: they are
+        /// merged into the object at the field's path. The field's pointer
+        /// is the object's followed by the keyword, e.g. `/additionalProperties`.
+        #[serde(default)]
+        inline: bool,
+        /// Declared property names rejected as keys.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        reserved_keys: Vec<String>,
+        /// `patternProperties`; keys matching none of them use `value`.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        patterns: Vec<PatternRoute>,
+    },
+    /// `oneOf` / `anyOf`.
+    Variants {
+        any_of: bool,
+        variants: Vec<Variant>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        discriminator: Option<String>,
+        /// Points back at an ancestor; variant schemas are laid out on demand.
+        #[serde(default)]
+        recursive: bool,
+    },
+    /// `type` lists several non-null types.
+    OneOfTypes {
+        types: Vec<FieldKind>,
+    },
+    /// Edited as raw JSON.
+    Json,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EnumOption {
+    pub value: Value,
+    pub label: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PatternRoute {
+    pub pattern: String,
+    pub value: FieldKind,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Variant {
+    pub id: String,
+    pub title: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Self-contained schema of the variant; feed it back to
+    /// [`build_form_schema`] to lay it out.
+    pub schema: Value,
+}
+
+impl From<&domain::FormSchema> for FormSchema {
+    fn from(form: &domain::FormSchema) -> Self {
+        Self {
+            title: form.title.clone(),
+            description: form.description.clone(),
+            roots: form.roots.iter().map(RootSection::from).collect(),
+            diagnostics: form
+                .diagnostics
+                .iter()
+                .map(|diagnostic| Diagnostic {
+                    pointer: diagnostic.pointer.clone(),
+                    message: diagnostic.message.clone(),
+                })
+                .collect(),
+        }
+    }
+}
+
+impl From<&domain::RootSection> for RootSection {
+    fn from(root: &domain::RootSection) -> Self {
+        Self {
+            id: root.id.clone(),
+            title: root.title.clone(),
+            description: root.description.clone(),
+            sections: root.sections.iter().map(Section::from).collect(),
+        }
+    }
+}
+
+impl From<&domain::FormSection> for Section {
+    fn from(section: &domain::FormSection) -> Self {
+        Self {
+            id: section.id.clone(),
+            title: section.title.clone(),
+            description: section.description.clone(),
+            pointer: to_pointer(&section.path),
+            fields: section.fields.iter().map(Field::from).collect(),
+            children: section.children.iter().map(Section::from).collect(),
+        }
+    }
+}
+
+impl From<&domain::FieldSchema> for Field {
+    fn from(field: &domain::FieldSchema) -> Self {
+        let mut kind = FieldKind::from(&field.kind);
+        if let FieldKind::Array {
+            min_items,
+            max_items,
+            unique_items,
+            ..
+        } = &mut kind
+        {
+            *min_items = field.item_bounds.min;
+            *max_items = field.item_bounds.max;
+            *unique_items = field.item_bounds.unique;
+        }
+        Self {
+            name: field.name.clone(),
+            pointer: field.pointer.clone(),
+            title: field.title.clone(),
+            description: field.description.clone(),
+            kind,
+            required: field.required,
+            default: field.default.clone(),
+            nullable: field.nullable,
+            read_only: field.read_only,
+            write_only: field.write_only,
+            hidden: field.ui.hidden,
+            metadata: field.metadata.clone(),
+        }
+    }
+}
+
+impl From<&domain::FieldKind> for FieldKind {
+    fn from(kind: &domain::FieldKind) -> Self {
+        match kind {
+            domain::FieldKind::String => Self::String { format: None },
+            domain::FieldKind::Formatted(format) => Self::String {
+                format: Some(format.keyword().to_string()),
+            },
+            domain::FieldKind::Integer => Self::Integer,
+            domain::FieldKind::Number => Self::Number,
+            domain::FieldKind::Boolean => Self::Boolean,
+            domain::FieldKind::Enum(options) => Self::Enum {
+                options: options
+                    .iter()
+                    .map(|option| EnumOption {
+                        value: option.value.clone(),
+                        label: option.label.clone(),
+                    })
+                    .collect(),
+            },
+            domain::FieldKind::Const(value) => Self::Const {
+                value: value.clone(),
+            },
+            domain::FieldKind::Array(inner) => Self::Array {
+                items: Box::new(Self::from(inner.as_ref())),
+                min_items: None,
+                max_items: None,
+                unique_items: false,
+            },
+            domain::FieldKind::Tuple(tuple) => Self::Tuple {
+                items: tuple.items.iter().map(Field::from).collect(),
+                additional_items: tuple.allow_additional,
+            },
+            domain::FieldKind::KeyValue(map) => Self::Map {
+                key_schema: map.key_schema.clone(),
+                value: Box::new(Self::from(map.value_kind.as_ref())),
+                inline: map.inline,
+                reserved_keys: map.reserved_keys.clone(),
+                patterns: map
+                    .routes
+                    .iter()
+                    .map(|route| PatternRoute {
+                        pattern: route.pattern.as_str().to_string(),
+                        value: Self::from(route.value_kind.as_ref()),
+                    })
+                    .collect(),
+            },
+            domain::FieldKind::Composite(composite) => Self::Variants {
+                any_of: composite.mode == domain::CompositeMode::AnyOf,
+                variants: composite
+                    .variants
+                    .iter()
+                    .map(|variant| Variant {
+                        id: variant.id.clone(),
+                        title: variant.title.clone(),
+                        description: variant.description.clone(),
+                        schema: variant.schema.clone(),
+                    })
+                    .collect(),
+                discriminator: composite.discriminator.clone(),
+                recursive: composite.recursive,
+            },
+            domain::FieldKind::MultiType(kinds) => Self::OneOfTypes {
+                types: kinds.iter().map(Self::from).collect(),
+            },
+            domain::FieldKind::Json => Self::Json,
+        }
+    }
+}
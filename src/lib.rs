@@ -5,6 +5,7 @@ mod app;
 mod domain;
 mod form;
 pub mod io;
+pub mod layout;
 mod presentation;
 mod schema;
 
@@ -19,6 +20,9 @@ pub use io::{
     },
     output::{OutputDestination, OutputOptions},
 };
+#[cfg(feature = "remote_refs")]
+pub use schema::retriever::HttpRetriever;
+pub use schema::retriever::{FileSystemRetriever, SchemaRetriever};
 
 pub mod prelude {
     pub use super::{SchemaUI, UiOptions};
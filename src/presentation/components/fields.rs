@@ -1,3 +1,5 @@
+use std::ops::Range;
+
 use ratatui::{
     Frame,
     layout::{Constraint, Direction, Layout, Rect},
@@ -6,11 +8,12 @@ use ratatui::{
     widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Wrap},
 };
 use textwrap::wrap;
-use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
 
 use crate::{
     domain::FieldKind,
-    form::{FieldState, FormState, SectionState},
+    form::{FieldState, FormState, SectionState, field::components::ComponentKind},
 };
 
 pub fn render_fields(
@@ -77,12 +80,12 @@ pub fn render_fields(
         *list_state.offset_mut() = section.scroll_offset;
     }
 
+    let title = match section.collapsed_advanced() {
+        0 => section.title.clone(),
+        count => format!("{} (+{count} advanced, Ctrl+A)", section.title),
+    };
     let list = List::new(items)
-        .block(
-            Block::default()
-                .title(section.title.clone())
-                .borders(Borders::ALL),
-        )
+        .block(Block::default().title(title).borders(Borders::ALL))
         .highlight_style(Style::default())
         .highlight_symbol("» ");
 
@@ -140,15 +143,19 @@ fn adjust_scroll_offset(section: &mut SectionState, selected: usize, height: u16
 }
 
 const HIGHLIGHT_WIDTH: u16 = 2;
+/// Rows a focused multi-line editor shows before it scrolls.
+const MULTI_LINE_ROWS: usize = 8;
+/// Rows of a multi-line value shown while another field is focused.
+const MULTI_LINE_PREVIEW_ROWS: usize = 3;
 
 struct FieldRender {
     lines: Vec<Line<'static>>,
     cursor_hint: Option<CursorHint>,
 }
 
-struct CursorHint {
-    line_in_field: usize,
-    value_width: u16,
+pub(crate) struct CursorHint {
+    pub(crate) line_in_field: usize,
+    pub(crate) value_width: u16,
 }
 
 fn build_field_render(field: &FieldState, is_selected: bool, max_width: u16) -> FieldRender {
@@ -181,13 +188,15 @@ fn build_field_render(field: &FieldState, is_selected: bool, max_width: u16) ->
     FieldRender { lines, cursor_hint }
 }
 
-fn info_line(field: &FieldState, is_selected: bool) -> Line<'static> {
+pub(crate) fn info_line(field: &FieldState, is_selected: bool) -> Line<'static> {
     let mut label = field.schema.display_label();
     if field.schema.required {
         label.push_str(" *");
     }
 
-    let label_style = if is_selected {
+    let label_style = if field.is_read_only() {
+        Style::default().fg(Color::DarkGray)
+    } else if is_selected {
         Style::default()
             .fg(Color::Yellow)
             .add_modifier(Modifier::BOLD)
@@ -198,6 +207,31 @@ fn info_line(field: &FieldState, is_selected: bool) -> Line<'static> {
     };
     let mut spans = vec![Span::styled(label, label_style)];
 
+    if field.is_read_only() {
+        spans.push(Span::styled(
+            "  ·read-only",
+            Style::default().fg(Color::DarkGray),
+        ));
+    }
+
+    if field.schema.is_secret() {
+        let tag = if field.is_masked() {
+            "  ·secret (Ctrl+R reveal)"
+        } else {
+            "  ·secret (revealed)"
+        };
+        spans.push(Span::styled(tag, Style::default().fg(Color::Magenta)));
+    }
+
+    if field.schema.nullable && !field.is_read_only() {
+        let tag = if field.is_null() {
+            "  ·null (Ctrl+X restore)"
+        } else {
+            "  ·nullable (Ctrl+X null)"
+        };
+        spans.push(Span::styled(tag, Style::default().fg(Color::Blue)));
+    }
+
     if field.dirty {
         spans.push(Span::styled("  ·dirty", Style::default().fg(Color::Yellow)));
     }
@@ -212,88 +246,240 @@ fn info_line(field: &FieldState, is_selected: bool) -> Line<'static> {
     Line::from(spans)
 }
 
-fn value_panel_lines(
+pub(crate) fn value_panel_lines(
     field: &FieldState,
     is_selected: bool,
     max_width: u16,
 ) -> (Vec<Line<'static>>, Option<CursorHint>) {
     let clamp_width = max_width.max(4) as usize;
-    let value_text = field.display_value();
-    let mut wrapped_value = wrap_preserving_spaces(&value_text, clamp_width);
-    if wrapped_value.is_empty() {
-        wrapped_value.push(String::new());
-    }
+    let raw = field.display_value();
+    let masked = field.is_masked();
+    let display = if masked {
+        mask_value(&raw)
+    } else {
+        raw.clone()
+    };
+    let placeholder = match &field.schema.ui.placeholder {
+        _ if field.is_null() => Some(display.clone()),
+        Some(text) if display.is_empty() => Some(text.clone()),
+        _ => None,
+    };
+    // Offsets into `raw`, moved onto the masked text when it is hidden.
+    let to_display = |offset: usize| {
+        let offset = offset.min(raw.len());
+        if masked {
+            mask_value(&raw[..offset]).len()
+        } else {
+            offset
+        }
+    };
+    let text_cursor = field.text_cursor().filter(|_| placeholder.is_none());
+    let selection = text_cursor
+        .as_ref()
+        .and_then(|cursor| cursor.selection.clone())
+        .map(|range| to_display(range.start)..to_display(range.end));
+    let caret_offset = match (&placeholder, &text_cursor) {
+        (Some(_), _) => 0,
+        (None, Some(cursor)) => to_display(cursor.offset),
+        (None, None) => display.len(),
+    };
+    let value_text = placeholder.clone().unwrap_or(display);
+    let placeholder_style = Style::default()
+        .fg(Color::DarkGray)
+        .add_modifier(Modifier::ITALIC);
+    // Multi-line editors get a line-number gutter and scroll with the caret.
+    let numbered = placeholder.is_none()
+        && matches!(
+            field.component_kind(),
+            ComponentKind::Textarea | ComponentKind::Json
+        );
+    let gutter_width = if numbered {
+        value_text.split('\n').count().to_string().len() + 1
+    } else {
+        0
+    };
+    let wrapped_value =
+        wrap_preserving_spaces(&value_text, clamp_width.saturating_sub(gutter_width).max(1));
+    let gutters = gutter_labels(&value_text, &wrapped_value, gutter_width);
+    let caret_row = wrapped_value
+        .iter()
+        .rposition(|(start, _)| *start <= caret_offset)
+        .unwrap_or(0);
+    let visible = visible_rows(wrapped_value.len(), caret_row, is_selected, numbered);
+    let gutter_style = Style::default().fg(Color::DarkGray);
     let inner_width = wrapped_value
         .iter()
-        .map(|line| UnicodeWidthStr::width(line.as_str()))
+        .map(|(_, line)| UnicodeWidthStr::width(line.as_str()))
         .max()
         .unwrap_or(0);
-    let last_line_width = wrapped_value
-        .last()
-        .map(|line| UnicodeWidthStr::width(line.as_str()))
-        .unwrap_or(0);
     let mut cursor_hint = None;
     let mut lines = Vec::new();
 
     if is_selected {
-        let border_width = inner_width.saturating_add(2);
+        let border_width = inner_width.saturating_add(gutter_width + 2);
         let border_line = "─".repeat(border_width);
-        let border_style = Style::default().fg(Color::Yellow);
-        let value_style = Style::default()
-            .fg(Color::White)
-            .add_modifier(Modifier::BOLD);
+        let (border_style, value_style) = if field.is_read_only() {
+            let dimmed = Style::default().fg(Color::DarkGray);
+            (dimmed, dimmed)
+        } else {
+            let value_style = if placeholder.is_some() {
+                placeholder_style
+            } else {
+                Style::default()
+                    .fg(Color::White)
+                    .add_modifier(Modifier::BOLD)
+            };
+            (Style::default().fg(Color::Yellow), value_style)
+        };
 
         lines.push(Line::from(Span::styled(
             format!("┌{}┐", border_line),
             border_style,
         )));
         let content_start = lines.len();
-        for segment in &wrapped_value {
-            let mut content = segment.clone();
-            let mut width = UnicodeWidthStr::width(content.as_str());
-            while width < inner_width {
-                content.push(' ');
-                width += 1;
+        let rows = wrapped_value[visible.clone()]
+            .iter()
+            .zip(&gutters[visible.clone()]);
+        for ((start, segment), gutter) in rows {
+            let padding = inner_width.saturating_sub(UnicodeWidthStr::width(segment.as_str()));
+            let mut spans = vec![Span::styled("│ ", border_style)];
+            if numbered {
+                spans.push(Span::styled(gutter.clone(), gutter_style));
             }
-            lines.push(Line::from(vec![
-                Span::styled("│ ", border_style),
-                Span::styled(content, value_style),
-                Span::styled(" │", border_style),
-            ]));
+            let local = selection.as_ref().map(|range| {
+                let clamp = |offset: usize| offset.clamp(*start, start + segment.len()) - start;
+                clamp(range.start)..clamp(range.end)
+            });
+            match local.filter(|range| !range.is_empty()) {
+                Some(range) => {
+                    spans.push(Span::styled(
+                        segment[..range.start].to_string(),
+                        value_style,
+                    ));
+                    spans.push(Span::styled(
+                        segment[range.clone()].to_string(),
+                        value_style.add_modifier(Modifier::REVERSED),
+                    ));
+                    spans.push(Span::styled(
+                        format!("{}{}", &segment[range.end..], " ".repeat(padding)),
+                        value_style,
+                    ));
+                }
+                None => spans.push(Span::styled(
+                    format!("{segment}{}", " ".repeat(padding)),
+                    value_style,
+                )),
+            }
+            spans.push(Span::styled(" │", border_style));
+            lines.push(Line::from(spans));
         }
         lines.push(Line::from(Span::styled(
             format!("└{}┘", border_line),
             border_style,
         )));
-        let caret_line = content_start + wrapped_value.len().saturating_sub(1);
-        let trailing_spaces = count_trailing_spaces(&value_text);
-        let mut caret_width = last_line_width + trailing_spaces;
-        if caret_width > inner_width {
-            caret_width = inner_width;
+        let (row_start, row_text) = &wrapped_value[caret_row];
+        let column = caret_offset.saturating_sub(*row_start).min(row_text.len());
+        let caret_width = UnicodeWidthStr::width(&row_text[..column]).min(inner_width);
+        if !field.is_read_only() {
+            cursor_hint = Some(CursorHint {
+                line_in_field: content_start + caret_row - visible.start,
+                value_width: (gutter_width + caret_width) as u16,
+            });
         }
-        cursor_hint = Some(CursorHint {
-            line_in_field: caret_line,
-            value_width: caret_width as u16,
-        });
     } else {
-        for segment in wrapped_value {
-            lines.push(Line::from(vec![
-                Span::styled("  ", Style::default()),
-                Span::styled(segment, Style::default().fg(Color::White)),
-            ]));
+        let value_style = if placeholder.is_some() {
+            placeholder_style
+        } else if field.is_read_only() {
+            Style::default().fg(Color::DarkGray)
+        } else {
+            Style::default().fg(Color::White)
+        };
+        let hidden_from = wrapped_value.get(visible.end).map(|(start, _)| *start);
+        for ((_, segment), gutter) in wrapped_value.into_iter().zip(gutters).take(visible.end) {
+            let mut spans = vec![Span::styled("  ", Style::default())];
+            if numbered {
+                spans.push(Span::styled(gutter, gutter_style));
+            }
+            spans.push(Span::styled(segment, value_style));
+            lines.push(Line::from(spans));
+        }
+        if let Some(start) = hidden_from {
+            let more = value_text[start..].split('\n').count();
+            lines.push(Line::from(Span::styled(
+                format!("  … {more} more lines"),
+                placeholder_style,
+            )));
         }
     }
 
     (lines, cursor_hint)
 }
 
+/// Line numbers for the first row of every line of `text`, blank on wrapped
+/// continuation rows; empty strings without a gutter.
+fn gutter_labels(text: &str, rows: &[(usize, String)], gutter_width: usize) -> Vec<String> {
+    let mut number = 0;
+    rows.iter()
+        .map(|(start, _)| {
+            if gutter_width == 0 {
+                String::new()
+            } else if *start == 0 || text.as_bytes()[start - 1] == b'\n' {
+                number += 1;
+                format!("{number:>width$} ", width = gutter_width - 1)
+            } else {
+                " ".repeat(gutter_width)
+            }
+        })
+        .collect()
+}
+
+/// Rows of a multi-line editor that fit its window: centred on the caret
+/// while focused, the first few otherwise.
+fn visible_rows(total: usize, caret_row: usize, is_selected: bool, numbered: bool) -> Range<usize> {
+    let limit = match (numbered, is_selected) {
+        (false, _) => return 0..total,
+        (true, true) => MULTI_LINE_ROWS,
+        (true, false) => MULTI_LINE_PREVIEW_ROWS,
+    };
+    if total <= limit {
+        return 0..total;
+    }
+    let first = if is_selected {
+        caret_row.saturating_sub(limit / 2).min(total - limit)
+    } else {
+        0
+    };
+    first..first + limit
+}
+
+/// Keeps the cursor on the right column while hiding every character.
+fn mask_value(value: &str) -> String {
+    value
+        .chars()
+        .map(|ch| if ch == '\n' { ch } else { '•' })
+        .collect()
+}
+
 pub(crate) fn meta_lines(
     field: &FieldState,
     is_selected: bool,
     max_width: u16,
 ) -> Vec<Line<'static>> {
     let mut parts = Vec::new();
-    parts.push(format!("type: {}", field_type_label(&field.schema.kind)));
+    let nullable = if field.schema.nullable { "?" } else { "" };
+    parts.push(format!(
+        "type: {}{nullable}",
+        field_type_label(&field.schema.kind)
+    ));
+    if is_selected
+        && !field.is_read_only()
+        && let Some(hint) = field.editor_hint()
+    {
+        parts.push(hint);
+    }
+    if is_selected && let Some(help) = &field.schema.ui.help {
+        parts.push(format!("help: {help}"));
+    }
     if let Some(desc) = field
         .schema
         .description
@@ -343,14 +529,30 @@ fn error_lines(field: &FieldState, max_width: u16) -> Option<Vec<Line<'static>>>
 fn field_type_label(kind: &FieldKind) -> String {
     match kind {
         FieldKind::String => "string".to_string(),
+        FieldKind::Formatted(format) => format!("string<{}>", format.keyword()),
         FieldKind::Integer => "integer".to_string(),
         FieldKind::Number => "number".to_string(),
         FieldKind::Boolean => "boolean".to_string(),
         FieldKind::Enum(_) => "enum".to_string(),
         FieldKind::Array(inner) => format!("{}[]", field_type_label(inner)),
         FieldKind::Json => "object".to_string(),
+        FieldKind::Const(_) => "const".to_string(),
+        FieldKind::MultiType(kinds) => kinds
+            .iter()
+            .map(field_type_label)
+            .collect::<Vec<_>>()
+            .join("|"),
         FieldKind::Composite(_) => "composite".to_string(),
         FieldKind::KeyValue(_) => "map".to_string(),
+        FieldKind::Tuple(tuple) => format!(
+            "[{}]",
+            tuple
+                .items
+                .iter()
+                .map(|item| field_type_label(&item.kind))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
     }
 }
 
@@ -401,10 +603,18 @@ fn repeatable_summary_lines(field: &FieldState) -> Option<Vec<Line<'static>>> {
         }
         let mut lines = Vec::new();
         lines.push(Line::from("  Entries:"));
+        let duplicates = field.composite_list_duplicates();
         let max_render = 4usize;
         for (idx, entry) in entries.iter().enumerate().take(max_render) {
             let marker = if idx == selected { "»" } else { " " };
-            lines.push(Line::from(format!("  {marker} {entry}")));
+            if duplicates.contains(&idx) {
+                lines.push(Line::from(Span::styled(
+                    format!("  {marker} {entry} (duplicate)"),
+                    Style::default().fg(Color::Red),
+                )));
+            } else {
+                lines.push(Line::from(format!("  {marker} {entry}")));
+            }
         }
         if entries.len() > max_render {
             lines.push(Line::from(format!(
@@ -501,30 +711,32 @@ fn composite_selector_lines(field: &FieldState) -> Option<Vec<Line<'static>>> {
     Some(lines)
 }
 
-fn count_trailing_spaces(text: &str) -> usize {
-    text.chars().rev().take_while(|c| *c == ' ').count()
-}
-
-fn wrap_preserving_spaces(text: &str, width: usize) -> Vec<String> {
+/// Wraps on grapheme boundaries at `width` columns and on `\n`, keeping each
+/// row's byte offset into `text` so the caret can be placed.
+fn wrap_preserving_spaces(text: &str, width: usize) -> Vec<(usize, String)> {
     if width == 0 {
-        return vec![text.to_string()];
+        return vec![(0, text.to_string())];
     }
     let mut lines = Vec::new();
+    let mut start = 0usize;
     let mut current = String::new();
     let mut current_width = 0usize;
-    for ch in text.chars() {
-        let ch_width = UnicodeWidthChar::width(ch).unwrap_or(1);
-        if current_width + ch_width > width && !current.is_empty() {
-            lines.push(current);
-            current = String::new();
+    for (index, grapheme) in text.grapheme_indices(true) {
+        if grapheme == "\n" || grapheme == "\r\n" {
+            lines.push((start, std::mem::take(&mut current)));
+            start = index + grapheme.len();
             current_width = 0;
+            continue;
         }
-        current.push(ch);
-        current_width += ch_width;
-    }
-    lines.push(current);
-    if lines.is_empty() {
-        lines.push(String::new());
+        let grapheme_width = UnicodeWidthStr::width(grapheme);
+        if current_width + grapheme_width > width && !current.is_empty() {
+            lines.push((start, std::mem::take(&mut current)));
+            start = index;
+            current_width = 0;
+        }
+        current.push_str(grapheme);
+        current_width += grapheme_width;
     }
+    lines.push((start, current));
     lines
 }
@@ -4,9 +4,13 @@ mod footer;
 mod layout;
 mod overlay;
 mod popup;
+mod review;
 mod sections;
+mod warnings;
 
 pub use body::render_body;
 pub use footer::render_footer;
 pub use overlay::render_composite_overlay;
 pub use popup::render_popup;
+pub use review::render_review;
+pub use warnings::render_warnings;
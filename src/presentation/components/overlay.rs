@@ -46,6 +46,7 @@ pub fn render_composite_overlay(
             columns[0],
             entries,
             overlay.list_selected.unwrap_or(0),
+            &overlay.list_duplicates,
         );
         columns[1]
     } else {
@@ -82,13 +83,27 @@ pub fn render_composite_overlay(
     frame.render_widget(footer, layout[1]);
 }
 
-fn render_list_sidebar(frame: &mut Frame<'_>, area: Rect, entries: &[String], selected: usize) {
+fn render_list_sidebar(
+    frame: &mut Frame<'_>,
+    area: Rect,
+    entries: &[String],
+    selected: usize,
+    duplicates: &[usize],
+) {
     let items: Vec<ListItem<'_>> = if entries.is_empty() {
         vec![ListItem::new("No entries")]
     } else {
         entries
             .iter()
-            .map(|label| ListItem::new(label.clone()))
+            .enumerate()
+            .map(|(idx, label)| {
+                if duplicates.contains(&idx) {
+                    ListItem::new(format!("{label} (duplicate)"))
+                        .style(Style::default().fg(Color::Red))
+                } else {
+                    ListItem::new(label.clone())
+                }
+            })
             .collect()
     };
     let mut state = ListState::default();
@@ -0,0 +1,70 @@
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph},
+};
+
+use super::super::view::ReviewRender;
+use super::layout::popup_rect;
+
+/// The pre-save diff: one row per added, removed or changed pointer.
+pub fn render_review(frame: &mut Frame<'_>, review: ReviewRender<'_>) {
+    let base = frame.area();
+    let width = base.width.saturating_sub(base.width / 4).max(40);
+    let height = (review.rows.len() as u16)
+        .saturating_add(4)
+        .min(base.height.saturating_sub(2))
+        .max(6);
+    let area = popup_rect(base, width, height);
+    frame.render_widget(Clear, area);
+
+    let block = Block::default()
+        .title(format!("Review changes ({})", review.rows.len()))
+        .borders(Borders::ALL)
+        .style(
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        );
+    frame.render_widget(block.clone(), area);
+    let layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(1), Constraint::Length(1)])
+        .split(block.inner(area));
+
+    let items: Vec<ListItem<'static>> = review
+        .rows
+        .iter()
+        .map(|row| {
+            let color = match row.symbol {
+                '+' => Color::Green,
+                '-' => Color::Red,
+                _ => Color::Yellow,
+            };
+            ListItem::new(Line::from(vec![
+                Span::styled(format!("{} ", row.symbol), Style::default().fg(color)),
+                Span::styled(
+                    row.pointer.clone(),
+                    Style::default().add_modifier(Modifier::BOLD),
+                ),
+                Span::raw("  "),
+                Span::styled(row.detail.clone(), Style::default().fg(Color::Gray)),
+            ]))
+        })
+        .collect();
+    let mut state = ListState::default();
+    state.select(Some(
+        review.selected.min(review.rows.len().saturating_sub(1)),
+    ));
+    let list = List::new(items)
+        .style(Style::default().fg(Color::White))
+        .highlight_style(Style::default().add_modifier(Modifier::REVERSED))
+        .highlight_symbol("» ");
+    frame.render_stateful_widget(list, layout[0], &mut state);
+
+    let footer = Paragraph::new("Ctrl+S save • Enter go to field • Esc back")
+        .style(Style::default().fg(Color::Yellow));
+    frame.render_widget(footer, layout[1]);
+}
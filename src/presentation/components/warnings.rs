@@ -0,0 +1,38 @@
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::Line,
+    widgets::{Block, Borders, Paragraph},
+};
+
+/// Schema diagnostics above the form; extra lines collapse into a count.
+pub fn render_warnings(frame: &mut Frame<'_>, area: Rect, warnings: &[String], max_rows: usize) {
+    let mut lines = Vec::new();
+    let overflow = warnings.len() > max_rows;
+    let shown = if overflow { max_rows - 1 } else { max_rows };
+    for warning in warnings.iter().take(shown) {
+        lines.push(Line::from(format!("⚠ {warning}")));
+    }
+    if overflow {
+        lines.push(Line::from(format!(
+            "… {} more (see SchemaUI::diagnostics)",
+            warnings.len() - shown
+        )));
+    }
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(format!(
+            "Schema warnings ({}) • Ctrl+P hide",
+            warnings.len()
+        ))
+        .style(
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        );
+    let widget = Paragraph::new(lines)
+        .block(block)
+        .style(Style::default().fg(Color::Yellow));
+    frame.render_widget(widget, area);
+}
@@ -1,4 +1,4 @@
 pub(crate) mod components;
 mod view;
 
-pub use view::{CompositeOverlay, PopupRender, UiContext, draw};
+pub use view::{CompositeOverlay, PopupRender, ReviewRender, ReviewRow, UiContext, draw};
@@ -5,7 +5,10 @@ use ratatui::{
 
 use crate::form::FormState;
 
-use super::components::{render_body, render_composite_overlay, render_footer, render_popup};
+use super::components::{
+    render_body, render_composite_overlay, render_footer, render_popup, render_review,
+    render_warnings,
+};
 
 pub struct UiContext<'a> {
     pub status_message: &'a str,
@@ -13,9 +16,12 @@ pub struct UiContext<'a> {
     pub error_count: usize,
     pub help: Option<&'a str>,
     pub global_errors: &'a [String],
+    /// Schema diagnostics, when the warning panel is open.
+    pub warnings: Option<&'a [String]>,
     pub focus_label: Option<String>,
     pub popup: Option<PopupRender<'a>>,
     pub composite_overlay: Option<CompositeOverlay>,
+    pub review: Option<ReviewRender<'a>>,
 }
 
 pub struct PopupRender<'a> {
@@ -26,6 +32,21 @@ pub struct PopupRender<'a> {
     pub active: Option<&'a [bool]>,
 }
 
+/// Pending changes shown before a save.
+pub struct ReviewRender<'a> {
+    pub rows: &'a [ReviewRow],
+    pub selected: usize,
+}
+
+/// `symbol` is `+` (added), `-` (removed) or `~` (changed); `detail` holds
+/// the value, or `old → new`.
+#[derive(Debug, Clone)]
+pub struct ReviewRow {
+    pub symbol: char,
+    pub pointer: String,
+    pub detail: String,
+}
+
 #[derive(Debug, Clone)]
 pub struct CompositeOverlay {
     pub title: String,
@@ -34,8 +55,12 @@ pub struct CompositeOverlay {
     pub instructions: String,
     pub list_entries: Option<Vec<String>>,
     pub list_selected: Option<usize>,
+    /// Entries flagged by `uniqueItems`.
+    pub list_duplicates: Vec<usize>,
 }
 
+const MAX_WARNING_ROWS: usize = 5;
+
 pub fn draw(
     frame: &mut Frame<'_>,
     form_state: &mut FormState,
@@ -47,8 +72,23 @@ pub fn draw(
         .constraints([Constraint::Min(7), Constraint::Length(3)])
         .split(frame.area());
 
-    let cursor_enabled = ctx.popup.is_none() && ctx.composite_overlay.is_none();
-    render_body(frame, chunks[0], form_state, cursor_enabled);
+    let cursor_enabled =
+        ctx.popup.is_none() && ctx.composite_overlay.is_none() && ctx.review.is_none();
+    let body_area = match ctx.warnings.filter(|warnings| !warnings.is_empty()) {
+        Some(warnings) => {
+            let rows = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([
+                    Constraint::Length(warnings.len().min(MAX_WARNING_ROWS) as u16 + 2),
+                    Constraint::Min(5),
+                ])
+                .split(chunks[0]);
+            render_warnings(frame, rows[0], warnings, MAX_WARNING_ROWS);
+            rows[1]
+        }
+        None => chunks[0],
+    };
+    render_body(frame, body_area, form_state, cursor_enabled);
     render_footer(frame, chunks[1], &ctx);
 
     if let Some(popup) = ctx.popup {
@@ -58,4 +98,8 @@ pub fn draw(
     if let (Some(meta), Some(overlay_state)) = (ctx.composite_overlay.as_ref(), overlay_form) {
         render_composite_overlay(frame, meta, overlay_state);
     }
+
+    if let Some(review) = ctx.review {
+        render_review(frame, review);
+    }
 }
@@ -0,0 +1,277 @@
+use std::collections::{HashMap, HashSet};
+
+use anyhow::{Context, Result, anyhow, bail};
+use serde_json::{Map, Value};
+use url::Url;
+
+use super::retriever::SchemaRetriever;
+
+/// Inline every document reachable through a non-local `$ref` under the root
+/// `definitions` and rewrite all references (`$ref`, `$dynamicRef`, anchors,
+/// and refs relative to a nested `$id`) into plain `#/...` pointers. Layout,
+/// composite sub-forms and the validator can then treat the bundle like a
+/// single-file draft-07 schema.
+pub fn bundle_external_refs(
+    schema: &Value,
+    base: Option<&Url>,
+    retriever: &dyn SchemaRetriever,
+) -> Result<Value> {
+    let mut root = schema.clone();
+    let reserved = root
+        .get("definitions")
+        .and_then(Value::as_object)
+        .map(|defs| defs.keys().cloned().collect())
+        .unwrap_or_default();
+    let mut bundler = Bundler {
+        retriever,
+        index: ReferenceIndex::default(),
+        documents: HashMap::new(),
+        reserved,
+        pending: Vec::new(),
+    };
+    let base = base.map(without_fragment);
+    bundler.index.add_document(&root, base.clone(), "");
+    bundler.rewrite(&mut root, base, false)?;
+
+    let mut embedded = Map::new();
+    while let Some((uri, key, mut document)) = bundler.pending.pop() {
+        bundler.rewrite(&mut document, Some(uri), true)?;
+        embedded.insert(key, document);
+    }
+    if embedded.is_empty() {
+        return Ok(root);
+    }
+
+    let map = root
+        .as_object_mut()
+        .ok_or_else(|| anyhow!("root schema must be an object"))?;
+    let Value::Object(definitions) = map
+        .entry("definitions")
+        .or_insert_with(|| Value::Object(Map::new()))
+    else {
+        bail!("root 'definitions' must be an object");
+    };
+    definitions.extend(embedded);
+    Ok(root)
+}
+
+/// Maps schema resources (`$id`) and anchors (`$anchor`, `$dynamicAnchor`,
+/// draft-07 `"$id": "#name"`) to pointers inside the bundled document.
+#[derive(Debug, Default)]
+struct ReferenceIndex {
+    resources: HashMap<Option<Url>, String>,
+    anchors: HashMap<(Option<Url>, String), String>,
+    dynamic_anchors: HashMap<String, String>,
+}
+
+impl ReferenceIndex {
+    fn add_document(&mut self, document: &Value, uri: Option<Url>, pointer: &str) {
+        self.resources.insert(uri.clone(), pointer.to_string());
+        self.walk(document, uri, pointer.to_string());
+    }
+
+    fn walk(&mut self, value: &Value, mut base: Option<Url>, pointer: String) {
+        match value {
+            Value::Object(map) => {
+                if let Some(id) = map.get("$id").and_then(Value::as_str) {
+                    if let Some(anchor) = id.strip_prefix('#') {
+                        self.anchors
+                            .insert((base.clone(), anchor.to_string()), pointer.clone());
+                    } else if let Some(resource) = join(base.as_ref(), id) {
+                        base = Some(without_fragment(&resource));
+                        self.resources.insert(base.clone(), pointer.clone());
+                    }
+                }
+                if let Some(anchor) = map.get("$anchor").and_then(Value::as_str) {
+                    self.anchors
+                        .insert((base.clone(), anchor.to_string()), pointer.clone());
+                }
+                if let Some(anchor) = map.get("$dynamicAnchor").and_then(Value::as_str) {
+                    self.anchors
+                        .entry((base.clone(), anchor.to_string()))
+                        .or_insert_with(|| pointer.clone());
+                    self.dynamic_anchors
+                        .entry(anchor.to_string())
+                        .or_insert_with(|| pointer.clone());
+                }
+                for (name, child) in map {
+                    if !is_data_keyword(name) {
+                        self.walk(child, base.clone(), child_pointer(&pointer, name));
+                    }
+                }
+            }
+            Value::Array(items) => {
+                for (index, item) in items.iter().enumerate() {
+                    self.walk(item, base.clone(), format!("{pointer}/{index}"));
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+struct Bundler<'r> {
+    retriever: &'r dyn SchemaRetriever,
+    index: ReferenceIndex,
+    documents: HashMap<Url, String>,
+    reserved: HashSet<String>,
+    pending: Vec<(Url, String, Value)>,
+}
+
+impl Bundler<'_> {
+    /// Rewrites the references below `value`. In `nested` subschemas the
+    /// `$id`s and anchors are dropped once read: every reference to them is
+    /// now a root pointer, and a leftover `$id` would make validators resolve
+    /// those pointers against the nested resource instead.
+    fn rewrite(&mut self, value: &mut Value, mut base: Option<Url>, nested: bool) -> Result<()> {
+        match value {
+            Value::Object(map) => {
+                if let Some(id) = map.get("$id").and_then(Value::as_str)
+                    && !id.starts_with('#')
+                    && let Some(resource) = join(base.as_ref(), id)
+                {
+                    base = Some(without_fragment(&resource));
+                }
+                if nested {
+                    for keyword in ["$id", "$anchor", "$dynamicAnchor"] {
+                        map.remove(keyword);
+                    }
+                }
+                if let Some(Value::String(reference)) = map.get_mut("$ref") {
+                    *reference = self.rebase(reference, base.as_ref())?;
+                }
+                if let Some(Value::String(reference)) = map.get("$dynamicRef") {
+                    let target = self.dynamic_target(reference, base.as_ref())?;
+                    map.remove("$dynamicRef");
+                    map.insert("$ref".to_string(), Value::String(target));
+                }
+                for (name, child) in map.iter_mut() {
+                    if name != "$ref" && !is_data_keyword(name) {
+                        self.rewrite(child, base.clone(), true)?;
+                    }
+                }
+            }
+            Value::Array(items) => {
+                for item in items {
+                    self.rewrite(item, base.clone(), true)?;
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn rebase(&mut self, reference: &str, base: Option<&Url>) -> Result<String> {
+        let (resource, fragment) = match reference.strip_prefix('#') {
+            Some(fragment) => (base.cloned(), fragment.to_string()),
+            None => {
+                let target = match Url::parse(reference) {
+                    Ok(url) => url,
+                    Err(url::ParseError::RelativeUrlWithoutBase) => base
+                        .with_context(|| {
+                            format!("relative reference '{reference}' requires a schema location")
+                        })?
+                        .join(reference)
+                        .with_context(|| format!("invalid reference '{reference}'"))?,
+                    Err(err) => {
+                        return Err(err)
+                            .with_context(|| format!("invalid reference '{reference}'"));
+                    }
+                };
+                let fragment = target.fragment().unwrap_or_default().to_string();
+                (Some(without_fragment(&target)), fragment)
+            }
+        };
+
+        let resource_pointer = match self.index.resources.get(&resource) {
+            Some(pointer) => pointer.clone(),
+            None => {
+                let uri = resource
+                    .clone()
+                    .with_context(|| format!("cannot resolve '{reference}'"))?;
+                self.register(uri)?
+            }
+        };
+        if fragment.is_empty() || fragment.starts_with('/') {
+            return Ok(format!("#{resource_pointer}{fragment}"));
+        }
+        self.index
+            .anchors
+            .get(&(resource, fragment.clone()))
+            .map(|pointer| format!("#{pointer}"))
+            .with_context(|| format!("anchor '#{fragment}' referenced by '{reference}' not found"))
+    }
+
+    /// `$dynamicRef` is resolved statically: the outermost `$dynamicAnchor`
+    /// with a matching name wins, falling back to a plain `$ref` lookup.
+    fn dynamic_target(&mut self, reference: &str, base: Option<&Url>) -> Result<String> {
+        if let Some(name) = reference.strip_prefix('#')
+            && let Some(pointer) = self.index.dynamic_anchors.get(name)
+        {
+            return Ok(format!("#{pointer}"));
+        }
+        self.rebase(reference, base)
+    }
+
+    fn register(&mut self, document: Url) -> Result<String> {
+        if let Some(key) = self.documents.get(&document) {
+            return Ok(format!("/definitions/{key}"));
+        }
+        let stem: String = document
+            .path_segments()
+            .and_then(|mut segments| segments.next_back())
+            .filter(|name| !name.is_empty())
+            .unwrap_or("external")
+            .chars()
+            .map(|ch| {
+                if ch.is_ascii_alphanumeric() || matches!(ch, '.' | '_' | '-') {
+                    ch
+                } else {
+                    '_'
+                }
+            })
+            .collect();
+        let mut key = stem.clone();
+        let mut suffix = 2;
+        while self.reserved.contains(&key) {
+            key = format!("{stem}_{suffix}");
+            suffix += 1;
+        }
+
+        let contents = self
+            .retriever
+            .retrieve(document.as_str())
+            .with_context(|| format!("failed to retrieve referenced schema {document}"))?;
+        let pointer = format!("/definitions/{key}");
+        self.index
+            .add_document(&contents, Some(document.clone()), &pointer);
+        self.reserved.insert(key.clone());
+        self.documents.insert(document.clone(), key.clone());
+        self.pending.push((document, key, contents));
+        Ok(pointer)
+    }
+}
+
+fn is_data_keyword(name: &str) -> bool {
+    matches!(name, "const" | "enum" | "default" | "examples")
+}
+
+fn child_pointer(pointer: &str, segment: &str) -> String {
+    format!(
+        "{pointer}/{}",
+        segment.replace('~', "~0").replace('/', "~1")
+    )
+}
+
+fn join(base: Option<&Url>, reference: &str) -> Option<Url> {
+    match base {
+        Some(base) => base.join(reference).ok(),
+        None => Url::parse(reference).ok(),
+    }
+}
+
+fn without_fragment(url: &Url) -> Url {
+    let mut url = url.clone();
+    url.set_fragment(None);
+    url
+}
@@ -1,4 +1,4 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
 use anyhow::{Context, Result, anyhow, bail};
 use indexmap::IndexMap;
@@ -8,16 +8,20 @@ use schemars::schema::{
 use serde_json::Value;
 
 use crate::domain::{
-    CompositeField, CompositeMode, CompositeVariant, FieldKind, FieldSchema, FormSchema,
-    FormSection, KeyValueField, RootSection,
+    CompositeField, CompositeMode, CompositeVariant, EnumOption, FieldCondition, FieldKind,
+    FieldSchema, FormSchema, FormSection, ItemBounds, KeyPattern, KeyValueField, KeyValueRoute,
+    RootSection, SchemaDiagnostic, StringFormat, TupleField,
 };
 
 use super::{
+    bundle::bundle_external_refs,
     loader::load_root_schema,
     metadata::{
-        SectionInfo, general_section_info, metadata_map, prettify_label, section_info_for_object,
+        SectionInfo, field_ui, general_section_info, metadata_map, prettify_label,
+        section_info_for_object,
     },
     resolver::SchemaResolver,
+    retriever::FileSystemRetriever,
 };
 
 #[derive(Debug, Clone)]
@@ -50,34 +54,51 @@ impl RootBuilder {
 }
 
 pub fn build_form_schema(schema_value: &Value) -> Result<FormSchema> {
+    let schema_value = &bundle_external_refs(schema_value, None, &FileSystemRetriever)?;
     let root = load_root_schema(schema_value)?;
     let resolver = SchemaResolver::new(schema_value, &root);
     let root_object = resolver
         .root_object()
         .cloned()
         .ok_or_else(|| anyhow!("root schema must be an object"))?;
+    let root_object = resolver.resolve_schema(&Schema::Object(root_object))?;
     ensure_object_schema(&root_object)?;
+    report_ignored_keywords(&resolver, &root_object);
 
     let mut roots: IndexMap<String, RootBuilder> = IndexMap::new();
     let mut general_fields: Vec<(usize, FieldSchema)> = Vec::new();
     let mut order_counter = 0usize;
+    let mut diagnostics = Vec::new();
+    record_conflicts(&resolver, "", &mut diagnostics);
     let object = root_object
         .object
         .as_ref()
         .context("root schema must define properties")?;
     let required = required_set(object);
+    let mut conditionals = conditional_fields(&resolver, &root_object, &[])?;
+    record_conflicts(&resolver, "", &mut diagnostics);
 
     for (name, property_schema) in &object.properties {
         let path = vec![name.clone()];
         let resolved = resolver.resolve_schema(property_schema)?;
-        if should_descend(&resolved) {
+        record_conflicts(&resolver, &to_pointer(&path), &mut diagnostics);
+        if should_descend(&resolved) && !resolver.is_recursive(property_schema) {
             let entry = roots
                 .entry(name.clone())
                 .or_insert_with(|| RootBuilder::new(name, &resolved));
-            let section = build_section_tree(&resolver, &resolved, path, None, &mut order_counter)?;
+            let section = resolver.expand(property_schema, || {
+                build_section_tree(
+                    &resolver,
+                    &resolved,
+                    path,
+                    None,
+                    &mut order_counter,
+                    &mut diagnostics,
+                )
+            })?;
             entry.sections.push(section);
         } else {
-            let field = build_field_schema(
+            let mut field = build_field_schema(
                 &resolver,
                 &resolved,
                 name,
@@ -85,26 +106,47 @@ pub fn build_form_schema(schema_value: &Value) -> Result<FormSchema> {
                 general_section_info(),
                 required.contains(name),
             )?;
+            record_conflicts(&resolver, &field.pointer, &mut diagnostics);
+            if should_descend(&resolved) {
+                field.kind = recursive_kind(&resolver, property_schema, &resolved)?;
+            }
+            field.required_when = conditionals.required_for(name);
             general_fields.push((order_counter, field));
             order_counter += 1;
         }
     }
 
-    if let Some(additional) = object.additional_properties.as_ref()
-        && let Some(resolved) = resolve_additional_properties(&resolver, additional)?
-    {
-        let field = build_field_schema(
+    let mut declared: Vec<String> = object.properties.keys().cloned().collect();
+    for (name, property_schema, condition) in std::mem::take(&mut conditionals.extra) {
+        declared.push(name.clone());
+        let resolved = resolver.resolve_schema(&property_schema)?;
+        let mut field = build_field_schema(
             &resolver,
             &resolved,
-            "additional",
-            Vec::new(),
+            &name,
+            vec![name.clone()],
             general_section_info(),
             false,
         )?;
+        record_conflicts(&resolver, &field.pointer, &mut diagnostics);
+        field.required_when = conditionals.required_for(&name);
+        field.visible_when = vec![condition];
         general_fields.push((order_counter, field));
+        order_counter += 1;
     }
 
-    general_fields.sort_by_key(|(order, _)| *order);
+    if let Some(field) = extra_entries_field(
+        &resolver,
+        &root_object,
+        Vec::new(),
+        general_section_info(),
+        declared,
+    )? {
+        record_conflicts(&resolver, &field.pointer, &mut diagnostics);
+        general_fields.push((order_counter, field));
+    }
+
+    general_fields.sort_by_key(|(order, field)| ui_order(field, *order));
 
     let mut roots_out = Vec::new();
     if !general_fields.is_empty() {
@@ -153,6 +195,7 @@ pub fn build_form_schema(schema_value: &Value) -> Result<FormSchema> {
             .as_ref()
             .and_then(|m| m.description.clone()),
         roots: roots_out,
+        diagnostics,
     })
 }
 
@@ -162,6 +205,7 @@ fn build_section_tree(
     path: Vec<String>,
     parent_section: Option<&SectionInfo>,
     order: &mut usize,
+    diagnostics: &mut Vec<SchemaDiagnostic>,
 ) -> Result<FormSection> {
     let name = path
         .last()
@@ -173,6 +217,9 @@ fn build_section_tree(
         .as_ref()
         .context("object schema must define properties")?;
     let required = required_set(object);
+    let mut conditionals = conditional_fields(resolver, schema, &path)?;
+    report_ignored_keywords(resolver, schema);
+    record_conflicts(resolver, &to_pointer(&path), diagnostics);
 
     let mut fields: Vec<(usize, FieldSchema)> = Vec::new();
     let mut children = Vec::new();
@@ -181,12 +228,21 @@ fn build_section_tree(
         let mut next_path = path.clone();
         next_path.push(child_name.clone());
         let resolved = resolver.resolve_schema(child_schema)?;
-        if should_descend(&resolved) {
-            let child =
-                build_section_tree(resolver, &resolved, next_path, Some(&section_info), order)?;
+        record_conflicts(resolver, &to_pointer(&next_path), diagnostics);
+        if should_descend(&resolved) && !resolver.is_recursive(child_schema) {
+            let child = resolver.expand(child_schema, || {
+                build_section_tree(
+                    resolver,
+                    &resolved,
+                    next_path,
+                    Some(&section_info),
+                    order,
+                    diagnostics,
+                )
+            })?;
             children.push(child);
         } else {
-            let field = build_field_schema(
+            let mut field = build_field_schema(
                 resolver,
                 &resolved,
                 child_name,
@@ -194,31 +250,50 @@ fn build_section_tree(
                 section_info.clone(),
                 required.contains(child_name),
             )?;
+            record_conflicts(resolver, &field.pointer, diagnostics);
+            if should_descend(&resolved) {
+                field.kind = recursive_kind(resolver, child_schema, &resolved)?;
+            }
+            field.required_when = conditionals.required_for(child_name);
             fields.push((*order, field));
             *order += 1;
         }
     }
 
-    if let Some(additional) = object.additional_properties.as_ref()
-        && let Some(resolved) = resolve_additional_properties(resolver, additional)?
-    {
-        let field_name = path
-            .last()
-            .cloned()
-            .unwrap_or_else(|| "additional".to_string());
-        let field = build_field_schema(
+    let mut declared: Vec<String> = object.properties.keys().cloned().collect();
+    for (child_name, child_schema, condition) in std::mem::take(&mut conditionals.extra) {
+        declared.push(child_name.clone());
+        let mut next_path = path.clone();
+        next_path.push(child_name.clone());
+        let resolved = resolver.resolve_schema(&child_schema)?;
+        let mut field = build_field_schema(
             resolver,
             &resolved,
-            &field_name,
-            path.clone(),
+            &child_name,
+            next_path,
             section_info.clone(),
             false,
         )?;
+        record_conflicts(resolver, &field.pointer, diagnostics);
+        field.required_when = conditionals.required_for(&child_name);
+        field.visible_when = vec![condition];
+        fields.push((*order, field));
+        *order += 1;
+    }
+
+    if let Some(field) = extra_entries_field(
+        resolver,
+        schema,
+        path.clone(),
+        section_info.clone(),
+        declared,
+    )? {
+        record_conflicts(resolver, &field.pointer, diagnostics);
         fields.push((*order, field));
         *order += 1;
     }
 
-    fields.sort_by_key(|(pos, _)| *pos);
+    fields.sort_by_key(|(pos, field)| ui_order(field, *pos));
 
     Ok(FormSection {
         id: section_info.id,
@@ -230,19 +305,246 @@ fn build_section_tree(
     })
 }
 
-fn resolve_additional_properties(
+/// Properties and `required` entries contributed by `then`/`else` and by
+/// property dependencies, keyed to the condition that activates them.
+#[derive(Debug, Default)]
+struct ConditionalFields {
+    extra: Vec<(String, Schema, FieldCondition)>,
+    required: HashMap<String, Vec<FieldCondition>>,
+}
+
+impl ConditionalFields {
+    fn required_for(&self, name: &str) -> Vec<FieldCondition> {
+        self.required.get(name).cloned().unwrap_or_default()
+    }
+
+    fn require(&mut self, name: &str, rule: &FieldCondition) {
+        self.required
+            .entry(name.to_string())
+            .or_default()
+            .push(rule.clone());
+    }
+
+    fn add_branch(
+        &mut self,
+        declared: Option<&ObjectValidation>,
+        branch: &SchemaObject,
+        rule: &FieldCondition,
+    ) {
+        let Some(object) = branch.object.as_ref() else {
+            return;
+        };
+        for name in &object.required {
+            self.require(name, rule);
+        }
+        for (name, property) in &object.properties {
+            // `then`/`else` may both declare a property; any other overlap keeps
+            // the first declaration.
+            let claimed = self
+                .extra
+                .iter()
+                .any(|(existing, _, other)| existing == name && other.when != rule.when);
+            if claimed || declared.is_some_and(|props| props.properties.contains_key(name)) {
+                continue;
+            }
+            self.extra
+                .push((name.clone(), property.clone(), rule.clone()));
+        }
+    }
+}
+
+fn conditional_fields(
     resolver: &SchemaResolver<'_>,
-    schema: &Schema,
-) -> Result<Option<SchemaObject>> {
-    match schema {
-        Schema::Bool(false) => Ok(None),
-        Schema::Bool(true) => Ok(None),
-        other => resolver.resolve_schema(other).map(Some),
+    schema: &SchemaObject,
+    scope: &[String],
+) -> Result<ConditionalFields> {
+    let mut out = ConditionalFields::default();
+    let declared = schema.object.as_deref();
+
+    // Conditionals beyond the first are left in `allOf` by the merge.
+    let extra = schema
+        .subschemas
+        .iter()
+        .flat_map(|subs| subs.all_of.iter().flatten())
+        .filter_map(|branch| match branch {
+            Schema::Object(object) => object.subschemas.as_deref(),
+            Schema::Bool(_) => None,
+        });
+    for subschemas in schema.subschemas.as_deref().into_iter().chain(extra) {
+        let Some(condition) = subschemas.if_schema.as_ref() else {
+            continue;
+        };
+        let resolved = resolver.resolve_schema(condition)?;
+        let when = with_definitions(
+            resolver,
+            schema_object_to_value(&resolved).context("failed to serialize 'if' schema")?,
+        );
+        let rule = match FieldCondition::new(scope.to_vec(), when, true) {
+            Ok(rule) => rule,
+            Err(err) => {
+                resolver.report(format!(
+                    "`if` schema cannot be compiled ({err}); its `then`/`else` properties are left out"
+                ));
+                continue;
+            }
+        };
+        let branches = [
+            (subschemas.then_schema.as_ref(), rule.clone()),
+            (subschemas.else_schema.as_ref(), rule.negated()),
+        ];
+        for (branch, rule) in branches {
+            let Some(branch) = branch else {
+                continue;
+            };
+            out.add_branch(declared, &resolver.resolve_schema(branch)?, &rule);
+        }
+    }
+
+    for keyword in ["dependencies", "dependentRequired", "dependentSchemas"] {
+        let Some(Value::Object(entries)) = schema.extensions.get(keyword) else {
+            continue;
+        };
+        for (trigger, dependency) in entries {
+            let rule = FieldCondition::new(
+                scope.to_vec(),
+                serde_json::json!({ "required": [trigger] }),
+                true,
+            )
+            .expect("a `required` schema always compiles");
+            if let Value::Array(names) = dependency {
+                for name in names.iter().filter_map(Value::as_str) {
+                    out.require(name, &rule);
+                }
+                continue;
+            }
+            let dependent: Schema = serde_json::from_value(dependency.clone())
+                .with_context(|| format!("invalid {keyword} schema for '{trigger}'"))?;
+            let resolved = resolver.resolve_schema(&dependent)?;
+            out.add_branch(declared, &resolved, &rule);
+        }
+    }
+    Ok(out)
+}
+
+fn with_definitions(resolver: &SchemaResolver<'_>, mut schema: Value) -> Value {
+    if let Value::Object(ref mut map) = schema {
+        for (keyword, definitions) in resolver.definitions_snapshot() {
+            map.entry(keyword).or_insert(definitions);
+        }
+    }
+    schema
+}
+
+fn record_conflicts(
+    resolver: &SchemaResolver<'_>,
+    pointer: &str,
+    diagnostics: &mut Vec<SchemaDiagnostic>,
+) {
+    diagnostics.extend(
+        resolver
+            .take_conflicts()
+            .into_iter()
+            .map(|message| SchemaDiagnostic {
+                pointer: pointer.to_string(),
+                message,
+            }),
+    );
+}
+
+/// Key/value editor for the `additionalProperties` and `patternProperties` of
+/// an object laid out as a section. Its entries are merged into the object
+/// next to the `declared` properties, whose names it rejects as keys.
+fn extra_entries_field(
+    resolver: &SchemaResolver<'_>,
+    schema: &SchemaObject,
+    path: Vec<String>,
+    section: SectionInfo,
+    declared: Vec<String>,
+) -> Result<Option<FieldSchema>> {
+    let Some(object) = schema.object.as_deref() else {
+        return Ok(None);
+    };
+    let name = match object.additional_properties.as_deref() {
+        Some(Schema::Object(_)) => "additionalProperties",
+        _ if !object.pattern_properties.is_empty() => "patternProperties",
+        _ => return Ok(None),
+    };
+    let Some(mut map) = map_field(resolver, schema)? else {
+        return Ok(None);
+    };
+    map.inline = true;
+    map.reserved_keys = declared;
+    // The keyword names the field so it does not share the object's pointer.
+    let pointer = format!("{}/{name}", to_pointer(&path));
+    Ok(Some(FieldSchema {
+        name: name.to_string(),
+        pointer,
+        path,
+        title: "Extra entries".to_string(),
+        description: None,
+        section_id: section.id,
+        kind: FieldKind::KeyValue(Box::new(map)),
+        required: false,
+        default: None,
+        read_only: false,
+        write_only: false,
+        nullable: false,
+        item_bounds: Default::default(),
+        ui: Default::default(),
+        metadata: Default::default(),
+        visible_when: Vec::new(),
+        required_when: Vec::new(),
+    }))
+}
+
+/// Keywords the form has no control for. They are still enforced by the
+/// validator, so only a diagnostic is recorded.
+fn report_ignored_keywords(resolver: &SchemaResolver<'_>, schema: &SchemaObject) {
+    let mut ignored = Vec::new();
+    if schema
+        .subschemas
+        .as_ref()
+        .is_some_and(|subs| subs.not.is_some())
+    {
+        ignored.push("not");
+    }
+    if schema
+        .array
+        .as_ref()
+        .is_some_and(|array| array.contains.is_some())
+    {
+        ignored.push("contains");
+    }
+    if let Some(object) = schema.object.as_deref() {
+        if object.min_properties.is_some() {
+            ignored.push("minProperties");
+        }
+        if object.max_properties.is_some() {
+            ignored.push("maxProperties");
+        }
+    }
+    for keyword in [
+        "minContains",
+        "maxContains",
+        "unevaluatedProperties",
+        "unevaluatedItems",
+        "$dynamicRef",
+        "$recursiveRef",
+    ] {
+        if schema.extensions.contains_key(keyword) {
+            ignored.push(keyword);
+        }
+    }
+    for keyword in ignored {
+        resolver.report(format!(
+            "`{keyword}` has no form control; it is only checked on validation"
+        ));
     }
 }
 
 fn should_descend(schema: &SchemaObject) -> bool {
     is_object_schema(schema)
+        && instance_types(schema).len() <= 1
         && schema
             .object
             .as_ref()
@@ -251,6 +553,11 @@ fn should_descend(schema: &SchemaObject) -> bool {
         && !has_composite_subschemas(schema)
 }
 
+/// Fields with `x-order` come first (ascending); the rest keep schema order.
+fn ui_order(field: &FieldSchema, position: usize) -> (i64, usize) {
+    (field.ui.order.unwrap_or(i64::MAX), position)
+}
+
 fn build_field_schema(
     resolver: &SchemaResolver<'_>,
     schema: &SchemaObject,
@@ -259,16 +566,45 @@ fn build_field_schema(
     section: SectionInfo,
     required: bool,
 ) -> Result<FieldSchema> {
+    let unwrapped = nullable_branch(resolver, schema)?;
+    let schema = unwrapped.as_ref().map_or(schema, |(_, inner)| inner);
     let metadata = metadata_map(schema);
-    let kind = detect_kind(resolver, schema)
-        .with_context(|| format!("unsupported schema for field '{name}'"))?;
+    let kind = match unwrapped
+        .as_ref()
+        .filter(|(_, inner)| should_descend(inner))
+    {
+        Some((raw, inner)) => inline_object_composite(resolver, raw, inner).map(|composite| {
+            FieldKind::Composite(Box::new(
+                composite.expect("object schemas become composites"),
+            ))
+        }),
+        None => detect_kind(resolver, schema),
+    }
+    .unwrap_or_else(|err| {
+        resolver.report(format!(
+            "unsupported schema ({err:#}); field '{name}' falls back to a raw JSON editor"
+        ));
+        FieldKind::Json
+    });
+    report_ignored_keywords(resolver, schema);
     let title = schema
         .metadata
         .as_ref()
         .and_then(|m| m.title.clone())
         .unwrap_or_else(|| prettify_label(name));
-    let default = schema.metadata.as_ref().and_then(|m| m.default.clone());
+    let mut default = schema.metadata.as_ref().and_then(|m| m.default.clone());
     let description = schema.metadata.as_ref().and_then(|m| m.description.clone());
+    let read_only = schema.metadata.as_ref().is_some_and(|m| m.read_only);
+    let write_only = schema.metadata.as_ref().is_some_and(|m| m.write_only);
+    let nullable = unwrapped.is_some()
+        || match &schema.instance_type {
+            Some(SingleOrVec::Vec(types)) => types.contains(&InstanceType::Null),
+            _ => false,
+        };
+    if unwrapped.is_some() && !required && default.is_none() {
+        // An optional `Option<T>` starts out unset rather than half-filled.
+        default = Some(Value::Null);
+    }
 
     Ok(FieldSchema {
         name: name.to_string(),
@@ -280,89 +616,259 @@ fn build_field_schema(
         kind,
         required,
         default,
+        read_only,
+        write_only,
+        nullable,
+        item_bounds: item_bounds(schema),
+        ui: field_ui(schema),
         metadata,
+        visible_when: Vec::new(),
+        required_when: Vec::new(),
     })
 }
 
+/// The non-null branch of an `anyOf`/`oneOf` pairing a schema with
+/// `{"type": "null"}` (how schemars writes `Option<T>`), carrying the outer
+/// annotations. The field is then laid out as a nullable `T`.
+fn nullable_branch<'s>(
+    resolver: &SchemaResolver<'_>,
+    schema: &'s SchemaObject,
+) -> Result<Option<(&'s Schema, SchemaObject)>> {
+    let Some(branches) = schema
+        .subschemas
+        .as_ref()
+        .and_then(|subs| subs.any_of.as_ref().or(subs.one_of.as_ref()))
+    else {
+        return Ok(None);
+    };
+    let [first, second] = branches.as_slice() else {
+        return Ok(None);
+    };
+    let is_null = |branch: &SchemaObject| matches!(&branch.instance_type, Some(SingleOrVec::Single(kind)) if **kind == InstanceType::Null);
+    let (first_resolved, second_resolved) = (
+        resolver.resolve_schema(first)?,
+        resolver.resolve_schema(second)?,
+    );
+    let (raw, mut inner) = if is_null(&second_resolved) {
+        (first, first_resolved)
+    } else if is_null(&first_resolved) {
+        (second, second_resolved)
+    } else {
+        return Ok(None);
+    };
+    if let Some(outer) = schema.metadata.as_deref() {
+        let metadata = inner.metadata();
+        metadata.title = outer.title.clone().or(metadata.title.take());
+        metadata.description = outer.description.clone().or(metadata.description.take());
+        metadata.default = outer.default.clone().or(metadata.default.take());
+        metadata.read_only |= outer.read_only;
+        metadata.write_only |= outer.write_only;
+    }
+    inner.extensions.extend(schema.extensions.clone());
+    Ok(Some((raw, inner)))
+}
+
+fn item_bounds(schema: &SchemaObject) -> ItemBounds {
+    let Some(array) = schema.array.as_deref() else {
+        return ItemBounds::default();
+    };
+    ItemBounds {
+        min: array.min_items.map(|min| min as usize),
+        max: array.max_items.map(|max| max as usize),
+        unique: array.unique_items.unwrap_or(false),
+    }
+}
+
 fn detect_kind(resolver: &SchemaResolver<'_>, schema: &SchemaObject) -> Result<FieldKind> {
     if let Some(key_value) = key_value_field(resolver, schema)? {
         return Ok(FieldKind::KeyValue(Box::new(key_value)));
     }
+    if let Some(value) = &schema.const_value {
+        return Ok(FieldKind::Const(value.clone()));
+    }
+    if let Some(options) = enum_options(resolver, schema)? {
+        return Ok(FieldKind::Enum(options));
+    }
     if let Some(composite) = composite_field(resolver, schema)? {
         return Ok(FieldKind::Composite(Box::new(composite)));
     }
-    if let Some(options) = &schema.enum_values {
-        let enum_values = options
-            .iter()
-            .map(|value| match value {
-                Value::String(s) => Ok(s.clone()),
-                other => Ok(other.to_string()),
+    let types = instance_types(schema);
+    if types.len() > 1 {
+        let kinds = types
+            .into_iter()
+            .map(|kind| {
+                let mut single = schema.clone();
+                single.instance_type = Some(SingleOrVec::Single(Box::new(kind)));
+                detect_kind(resolver, &single)
             })
-            .collect::<Result<Vec<_>, anyhow::Error>>()?;
-        return Ok(FieldKind::Enum(enum_values));
+            .collect::<Result<Vec<_>>>()?;
+        return Ok(FieldKind::MultiType(kinds));
     }
 
     match instance_type(schema) {
-        Some(InstanceType::String) | None => Ok(FieldKind::String),
+        Some(InstanceType::String) | None => Ok(schema
+            .format
+            .as_deref()
+            .and_then(StringFormat::from_keyword)
+            .map_or(FieldKind::String, FieldKind::Formatted)),
         Some(InstanceType::Integer) => Ok(FieldKind::Integer),
         Some(InstanceType::Number) => Ok(FieldKind::Number),
         Some(InstanceType::Boolean) => Ok(FieldKind::Boolean),
         Some(InstanceType::Object) => Ok(FieldKind::Json),
-        Some(InstanceType::Array) => match schema.array.as_ref() {
-            Some(array) if array.items.is_some() => {
-                let inner = resolve_array_items(resolver, array)?;
-                let inner_kind = detect_kind(resolver, &inner)?;
-                match inner_kind {
-                    FieldKind::String
-                    | FieldKind::Integer
-                    | FieldKind::Number
-                    | FieldKind::Boolean
-                    | FieldKind::Enum(_)
-                    | FieldKind::Composite(_) => Ok(FieldKind::Array(Box::new(inner_kind))),
-                    FieldKind::Json => {
-                        if let Some(composite) = inline_object_composite(&inner)? {
-                            Ok(FieldKind::Array(Box::new(FieldKind::Composite(Box::new(
-                                composite,
-                            )))))
-                        } else {
-                            Ok(FieldKind::Array(Box::new(FieldKind::Json)))
+        Some(InstanceType::Array) => {
+            if let Some(tuple) = tuple_field(resolver, schema)? {
+                return Ok(FieldKind::Tuple(Box::new(tuple)));
+            }
+            match schema.array.as_ref() {
+                Some(array) if array.items.is_some() => {
+                    let (items, inner) = resolve_array_items(resolver, array)?;
+                    let inner_kind = nested_kind(resolver, items, &inner)?;
+                    match inner_kind {
+                        FieldKind::Json => {
+                            if let Some(composite) =
+                                inline_object_composite(resolver, items, &inner)?
+                            {
+                                Ok(FieldKind::Array(Box::new(FieldKind::Composite(Box::new(
+                                    composite,
+                                )))))
+                            } else {
+                                Ok(FieldKind::Array(Box::new(FieldKind::Json)))
+                            }
                         }
+                        other => Ok(FieldKind::Array(Box::new(other))),
                     }
-                    FieldKind::KeyValue(_) => bail!("arrays of key/value maps are not supported"),
-                    FieldKind::Array(_) => bail!("nested arrays are not supported"),
                 }
+                _ => Ok(FieldKind::Array(Box::new(FieldKind::Json))),
             }
-            _ => Ok(FieldKind::Array(Box::new(FieldKind::Json))),
-        },
+        }
         Some(other) => bail!("unsupported field type {other:?}"),
     }
 }
 
-fn key_value_field(
+/// Options come from `enum` (labelled by `x-enumNames` when present) or from a
+/// `oneOf`/`anyOf` whose every branch is a `const`, labelled by branch `title`.
+fn enum_options(
     resolver: &SchemaResolver<'_>,
     schema: &SchemaObject,
-) -> Result<Option<KeyValueField>> {
-    let Some(object) = schema.object.as_ref() else {
+) -> Result<Option<Vec<EnumOption>>> {
+    if let Some(values) = &schema.enum_values {
+        let names = schema
+            .extensions
+            .get("x-enumNames")
+            .and_then(Value::as_array);
+        let options = values
+            .iter()
+            .enumerate()
+            .map(|(index, value)| {
+                let mut option = EnumOption::new(value.clone());
+                if let Some(name) = names.and_then(|names| names.get(index)?.as_str()) {
+                    option.label = name.to_string();
+                }
+                option
+            })
+            .collect();
+        return Ok(Some(options));
+    }
+
+    let Some(branches) = schema
+        .subschemas
+        .as_ref()
+        .and_then(|subs| subs.one_of.as_ref().or(subs.any_of.as_ref()))
+    else {
         return Ok(None);
     };
-    if !object.properties.is_empty() {
-        return Ok(None);
+    let mut options = Vec::with_capacity(branches.len());
+    for branch in branches {
+        let resolved = resolver.resolve_schema(branch)?;
+        let value = match (&resolved.const_value, resolved.enum_values.as_deref()) {
+            (Some(value), _) => value.clone(),
+            (None, Some([value])) => value.clone(),
+            _ => return Ok(None),
+        };
+        let mut option = EnumOption::new(value);
+        if let Some(title) = resolved.metadata.as_ref().and_then(|m| m.title.clone()) {
+            option.label = title;
+        }
+        options.push(option);
     }
+    Ok((!options.is_empty()).then_some(options))
+}
 
-    if let Some(additional) = object.additional_properties.as_ref() {
-        return build_key_value_from_schema(resolver, schema, additional, None);
+fn key_value_field(
+    resolver: &SchemaResolver<'_>,
+    schema: &SchemaObject,
+) -> Result<Option<KeyValueField>> {
+    match schema.object.as_ref() {
+        Some(object) if object.properties.is_empty() => map_field(resolver, schema),
+        _ => Ok(None),
     }
+}
 
-    if let Some((pattern, pattern_schema)) = object.pattern_properties.iter().next() {
-        let key_schema = serde_json::json!({
-            "type": "string",
-            "pattern": pattern,
-            "title": "Key",
+/// Entries matching a `patternProperties` pattern are routed to its value
+/// schema; the rest use `additionalProperties`. Without an
+/// `additionalProperties` schema, keys must match one of the patterns.
+fn map_field(
+    resolver: &SchemaResolver<'_>,
+    schema: &SchemaObject,
+) -> Result<Option<KeyValueField>> {
+    let object = schema.object.as_ref().expect("object schema");
+    let patterns = &object.pattern_properties;
+    let mut map = match (object.additional_properties.as_deref(), patterns.first()) {
+        (Some(additional @ Schema::Object(_)), _) | (Some(additional), None) => {
+            build_key_value_from_schema(resolver, schema, additional, None)?
+        }
+        (_, Some((first_pattern, first))) => {
+            let pattern = if patterns.len() == 1 {
+                first_pattern.clone()
+            } else {
+                patterns
+                    .keys()
+                    .map(|pattern| format!("(?:{pattern})"))
+                    .collect::<Vec<_>>()
+                    .join("|")
+            };
+            let key_schema = serde_json::json!({
+                "type": "string",
+                "pattern": pattern,
+                "title": "Key",
+            });
+            build_key_value_from_schema(resolver, schema, first, Some(key_schema))?
+        }
+        (None, None) => return Ok(None),
+    };
+    for (pattern, pattern_schema) in patterns {
+        let compiled = match KeyPattern::new(pattern) {
+            Ok(compiled) => compiled,
+            Err(err) => {
+                resolver.report(format!(
+                    "patternProperties pattern `{pattern}` cannot be compiled ({err}); \
+                     matching keys use the default value schema"
+                ));
+                continue;
+            }
+        };
+        let resolved = resolver.resolve_schema(pattern_schema)?;
+        let value_kind = nested_kind(resolver, pattern_schema, &resolved)?;
+        let value_schema = with_definitions(
+            resolver,
+            schema_object_to_value(&resolved).context("failed to serialize value schema")?,
+        );
+        let (value_title, value_description, value_default) = schema_titles(&resolved, "Value");
+        let entry_schema = with_definitions(
+            resolver,
+            key_value_entry_schema(&map.key_schema, &value_schema),
+        );
+        map.routes.push(KeyValueRoute {
+            pattern: compiled,
+            value_title,
+            value_description,
+            value_default,
+            value_schema,
+            value_kind: Box::new(value_kind),
+            entry_schema,
         });
-        return build_key_value_from_schema(resolver, schema, pattern_schema, Some(key_schema));
     }
-
-    Ok(None)
+    Ok(Some(map))
 }
 
 fn build_key_value_from_schema(
@@ -370,12 +876,14 @@ fn build_key_value_from_schema(
     schema: &SchemaObject,
     value_schema: &Schema,
     key_override: Option<Value>,
-) -> Result<Option<KeyValueField>> {
+) -> Result<KeyValueField> {
     let object = schema.object.as_ref().expect("object schema");
     let value_resolved = resolver.resolve_schema(value_schema)?;
-    let value_kind = detect_kind(resolver, &value_resolved)?;
-    let value_schema =
-        schema_object_to_value(&value_resolved).context("failed to serialize value schema")?;
+    let value_kind = nested_kind(resolver, value_schema, &value_resolved)?;
+    let value_schema = with_definitions(
+        resolver,
+        schema_object_to_value(&value_resolved).context("failed to serialize value schema")?,
+    );
     let (value_title, value_description, value_default) = schema_titles(&value_resolved, "Value");
 
     let (key_schema_value, key_title, key_description, key_default) =
@@ -396,9 +904,12 @@ fn build_key_value_from_schema(
             )
         };
 
-    let entry_schema = key_value_entry_schema(&key_schema_value, &value_schema);
+    let entry_schema = with_definitions(
+        resolver,
+        key_value_entry_schema(&key_schema_value, &value_schema),
+    );
 
-    Ok(Some(KeyValueField {
+    Ok(KeyValueField {
         key_title,
         key_description,
         key_default,
@@ -409,7 +920,10 @@ fn build_key_value_from_schema(
         value_schema,
         value_kind: Box::new(value_kind),
         entry_schema,
-    }))
+        inline: false,
+        reserved_keys: Vec::new(),
+        routes: Vec::new(),
+    })
 }
 
 fn composite_field(
@@ -419,11 +933,12 @@ fn composite_field(
     let Some(subschemas) = schema.subschemas.as_ref() else {
         return Ok(None);
     };
+    let discriminator = schema.extensions.get("discriminator");
     if let Some(one_of) = subschemas.one_of.as_ref() {
-        return build_composite(resolver, CompositeMode::OneOf, one_of);
+        return build_composite(resolver, CompositeMode::OneOf, one_of, discriminator);
     }
     if let Some(any_of) = subschemas.any_of.as_ref() {
-        return build_composite(resolver, CompositeMode::AnyOf, any_of);
+        return build_composite(resolver, CompositeMode::AnyOf, any_of, discriminator);
     }
     Ok(None)
 }
@@ -432,22 +947,24 @@ fn build_composite(
     resolver: &SchemaResolver<'_>,
     mode: CompositeMode,
     schemas: &[Schema],
+    discriminator: Option<&Value>,
 ) -> Result<Option<CompositeField>> {
     if schemas.is_empty() {
         return Ok(None);
     }
+    let mapping = discriminator
+        .and_then(|d| d.get("mapping"))
+        .and_then(Value::as_object);
 
     let mut variants = Vec::new();
     for (index, variant) in schemas.iter().enumerate() {
         let resolved = resolver.resolve_schema(variant)?;
-        ensure_object_schema(&resolved)?;
-        let mut schema_value = serde_json::to_value(Schema::Object(resolved.clone()))
-            .context("failed to serialize composite variant schema")?;
-        if let Some(definitions) = resolver.definitions_snapshot()
-            && let Value::Object(ref mut map) = schema_value
-        {
-            map.entry("definitions".to_string()).or_insert(definitions);
-        }
+        ensure_object_schema(&resolved).context("oneOf/anyOf variants must be objects")?;
+        let schema_value = with_definitions(
+            resolver,
+            serde_json::to_value(Schema::Object(resolved.clone()))
+                .context("failed to serialize composite variant schema")?,
+        );
         let title = resolved
             .metadata
             .as_ref()
@@ -457,32 +974,177 @@ fn build_composite(
             .metadata
             .as_ref()
             .and_then(|m| m.description.clone());
+        let mut discriminator_values = Vec::new();
+        if let Schema::Object(SchemaObject {
+            reference: Some(reference),
+            ..
+        }) = variant
+        {
+            let name = reference.rsplit('/').next().unwrap_or(reference);
+            for (value, target) in mapping.into_iter().flatten() {
+                if target.as_str().is_some_and(|target| {
+                    target == reference || target.rsplit('/').next() == Some(name)
+                }) {
+                    discriminator_values.push(value.clone());
+                }
+            }
+            if discriminator_values.is_empty() {
+                discriminator_values.push(name.to_string());
+            }
+        }
         variants.push(CompositeVariant {
             id: format!("variant_{}", index),
             title,
             description,
             schema: schema_value,
+            discriminator_values,
         });
     }
 
-    Ok(Some(CompositeField { mode, variants }))
+    Ok(Some(CompositeField {
+        mode,
+        variants,
+        discriminator: discriminator
+            .and_then(|d| d.get("propertyName"))
+            .and_then(Value::as_str)
+            .map(str::to_string),
+        recursive: false,
+    }))
+}
+
+fn tuple_field(resolver: &SchemaResolver<'_>, schema: &SchemaObject) -> Result<Option<TupleField>> {
+    let array = schema.array.as_deref();
+    let (positions, additional) = match schema.extensions.get("prefixItems") {
+        Some(prefix) => {
+            let positions: Vec<Schema> = serde_json::from_value(prefix.clone())
+                .context("prefixItems must be an array of schemas")?;
+            let additional = match array.and_then(|array| array.items.as_ref()) {
+                Some(SingleOrVec::Single(items)) => Some(items.as_ref()),
+                _ => None,
+            };
+            (positions, additional)
+        }
+        None => match array.and_then(|array| array.items.as_ref()) {
+            Some(SingleOrVec::Vec(positions)) => (
+                positions.clone(),
+                array.and_then(|array| array.additional_items.as_deref()),
+            ),
+            _ => return Ok(None),
+        },
+    };
+    if positions.is_empty() {
+        return Ok(None);
+    }
+
+    let min_items = array.and_then(|array| array.min_items).unwrap_or(0) as usize;
+    let mut items = Vec::with_capacity(positions.len());
+    let mut properties = serde_json::Map::new();
+    for (index, position) in positions.iter().enumerate() {
+        let name = index.to_string();
+        let resolved = resolver.resolve_schema(position)?;
+        let mut field = build_field_schema(
+            resolver,
+            &resolved,
+            &name,
+            vec![name.clone()],
+            general_section_info(),
+            index < min_items,
+        )?;
+        if resolved.metadata.as_ref().is_none_or(|m| m.title.is_none()) {
+            field.title = format!("Item {}", index + 1);
+        }
+        properties.insert(
+            name,
+            schema_object_to_value(&resolved).context("failed to serialize tuple item schema")?,
+        );
+        items.push(field);
+    }
+    let required = (0..min_items.min(items.len()))
+        .map(|index| index.to_string())
+        .collect::<Vec<_>>();
+    let entry_schema = with_definitions(
+        resolver,
+        serde_json::json!({
+            "type": "object",
+            "properties": properties,
+            "required": required,
+        }),
+    );
+
+    Ok(Some(TupleField {
+        items,
+        allow_additional: !matches!(additional, Some(Schema::Bool(false))),
+        entry_schema,
+    }))
+}
+
+/// Kind of a schema nested inside another field. A `$ref` back to an
+/// ancestor stops at `Json` instead of being inspected again.
+fn nested_kind(
+    resolver: &SchemaResolver<'_>,
+    raw: &Schema,
+    resolved: &SchemaObject,
+) -> Result<FieldKind> {
+    if resolver.is_recursive(raw) {
+        return Ok(FieldKind::Json);
+    }
+    resolver.expand(raw, || detect_kind(resolver, resolved))
+}
+
+/// Lazily built sub-form for an object property that refers back to an
+/// ancestor; it stays unset until opened.
+fn recursive_kind(
+    resolver: &SchemaResolver<'_>,
+    raw: &Schema,
+    resolved: &SchemaObject,
+) -> Result<FieldKind> {
+    let title = resolved
+        .metadata
+        .as_ref()
+        .and_then(|m| m.title.clone())
+        .or_else(|| {
+            let Schema::Object(object) = raw else {
+                return None;
+            };
+            let reference = object.reference.as_deref()?;
+            Some(prettify_label(reference.rsplit('/').next()?))
+        })
+        .filter(|title| !title.is_empty())
+        .unwrap_or_else(|| "Entry".to_string());
+    let variant = CompositeVariant {
+        id: "variant_0".to_string(),
+        title,
+        description: resolved
+            .metadata
+            .as_ref()
+            .and_then(|m| m.description.clone()),
+        schema: resolver.detached_schema(raw, resolved)?,
+        discriminator_values: Vec::new(),
+    };
+    Ok(FieldKind::Composite(Box::new(CompositeField {
+        mode: CompositeMode::OneOf,
+        variants: vec![variant],
+        discriminator: None,
+        recursive: true,
+    })))
 }
 
-fn resolve_array_items(
+fn resolve_array_items<'s>(
     resolver: &SchemaResolver<'_>,
-    array: &ArrayValidation,
-) -> Result<SchemaObject> {
+    array: &'s ArrayValidation,
+) -> Result<(&'s Schema, SchemaObject)> {
     let items = array
         .items
         .as_ref()
         .context("array schema must define items")?;
-    match items {
-        SingleOrVec::Single(schema) => resolver.resolve_schema(schema),
+    let first = match items {
+        SingleOrVec::Single(schema) => schema.as_ref(),
         SingleOrVec::Vec(list) => match list.first() {
-            Some(first) => resolver.resolve_schema(first),
+            Some(first) => first,
             None => bail!("tuple arrays without items are not supported"),
         },
-    }
+    };
+    Ok((first, resolver.resolve_schema(first)?))
 }
 
 fn schema_object_to_value(schema: &SchemaObject) -> Result<Value> {
@@ -512,11 +1174,15 @@ fn key_value_entry_schema(key_schema: &Value, value_schema: &Value) -> Value {
     })
 }
 
-fn inline_object_composite(schema: &SchemaObject) -> Result<Option<CompositeField>> {
+fn inline_object_composite(
+    resolver: &SchemaResolver<'_>,
+    raw: &Schema,
+    schema: &SchemaObject,
+) -> Result<Option<CompositeField>> {
     if !is_object_schema(schema) {
         return Ok(None);
     }
-    let schema_value = schema_object_to_value(schema)?;
+    let schema_value = resolver.detached_schema(raw, schema)?;
     let title = schema
         .metadata
         .as_ref()
@@ -528,10 +1194,13 @@ fn inline_object_composite(schema: &SchemaObject) -> Result<Option<CompositeFiel
         title,
         description,
         schema: schema_value,
+        discriminator_values: Vec::new(),
     };
     Ok(Some(CompositeField {
         mode: CompositeMode::OneOf,
         variants: vec![variant],
+        discriminator: None,
+        recursive: false,
     }))
 }
 
@@ -539,7 +1208,7 @@ fn required_set(object: &ObjectValidation) -> HashSet<String> {
     object.required.iter().cloned().collect()
 }
 
-fn to_pointer(path: &[String]) -> String {
+pub(crate) fn to_pointer(path: &[String]) -> String {
     if path.is_empty() {
         return String::new();
     }
@@ -562,13 +1231,20 @@ fn is_object_schema(schema: &SchemaObject) -> bool {
 }
 
 fn instance_type(schema: &SchemaObject) -> Option<InstanceType> {
-    schema.instance_type.as_ref().and_then(|kind| match kind {
-        SingleOrVec::Single(single) => Some(**single),
-        SingleOrVec::Vec(items) => items
+    instance_types(schema).into_iter().next()
+}
+
+/// Declared non-null types, in schema order.
+fn instance_types(schema: &SchemaObject) -> Vec<InstanceType> {
+    match schema.instance_type.as_ref() {
+        Some(SingleOrVec::Single(single)) => vec![**single],
+        Some(SingleOrVec::Vec(items)) => items
             .iter()
-            .cloned()
-            .find(|item| *item != InstanceType::Null),
-    })
+            .copied()
+            .filter(|item| *item != InstanceType::Null)
+            .collect(),
+        None => Vec::new(),
+    }
 }
 
 fn ensure_object_schema(schema: &SchemaObject) -> Result<()> {
@@ -0,0 +1,421 @@
+use anyhow::Result;
+use schemars::schema::{
+    ArrayValidation, InstanceType, Metadata, NumberValidation, ObjectValidation, Schema,
+    SchemaObject, SingleOrVec, StringValidation, SubschemaValidation,
+};
+use serde_json::{Map, Value, json};
+
+use super::resolver::SchemaResolver;
+
+const DEPENDENCY_KEYWORDS: [&str; 3] = ["dependencies", "dependentRequired", "dependentSchemas"];
+
+/// Fold every `allOf` branch into `schema`, returning the list of conflicts
+/// that could not be reconciled. Conflicting keywords keep the value that was
+/// seen first (the parent schema, then branches in declaration order).
+/// Conditionals never conflict: the first `if`/`then`/`else` stays on the
+/// schema and later ones are kept as `allOf` entries of their own.
+pub fn merge_all_of(
+    resolver: &SchemaResolver<'_>,
+    mut schema: SchemaObject,
+    conflicts: &mut Vec<String>,
+) -> Result<SchemaObject> {
+    let Some(branches) = schema
+        .subschemas
+        .as_mut()
+        .and_then(|subs| subs.all_of.take())
+    else {
+        return Ok(schema);
+    };
+    if schema
+        .subschemas
+        .as_ref()
+        .is_some_and(|subs| **subs == SubschemaValidation::default())
+    {
+        schema.subschemas = None;
+    }
+
+    for branch in &branches {
+        let resolved = resolver.resolve_schema(branch)?;
+        merge_into(resolver, &mut schema, resolved, conflicts)?;
+    }
+    Ok(schema)
+}
+
+fn merge_into(
+    resolver: &SchemaResolver<'_>,
+    base: &mut SchemaObject,
+    other: SchemaObject,
+    conflicts: &mut Vec<String>,
+) -> Result<()> {
+    let SchemaObject {
+        metadata,
+        instance_type,
+        format,
+        enum_values,
+        const_value,
+        subschemas,
+        number,
+        string,
+        array,
+        object,
+        reference: _,
+        extensions,
+    } = other;
+
+    if let Some(metadata) = metadata {
+        merge_metadata(
+            base.metadata.get_or_insert_with(Default::default),
+            *metadata,
+        );
+    }
+    merge_instance_type(&mut base.instance_type, instance_type, conflicts);
+    merge_exact(&mut base.format, format, "format", conflicts);
+    merge_exact(&mut base.const_value, const_value, "const", conflicts);
+    merge_enum(&mut base.enum_values, enum_values, conflicts);
+    if let Some(subschemas) = subschemas {
+        merge_subschemas(
+            base.subschemas.get_or_insert_with(Default::default),
+            *subschemas,
+            conflicts,
+        );
+    }
+    if let Some(number) = number {
+        merge_number(
+            base.number.get_or_insert_with(Default::default),
+            *number,
+            conflicts,
+        );
+    }
+    if let Some(string) = string {
+        merge_string(
+            base.string.get_or_insert_with(Default::default),
+            *string,
+            conflicts,
+        );
+    }
+    if let Some(array) = array {
+        merge_array(
+            base.array.get_or_insert_with(Default::default),
+            *array,
+            conflicts,
+        );
+    }
+    if let Some(object) = object {
+        merge_object(
+            resolver,
+            base.object.get_or_insert_with(Default::default),
+            *object,
+            conflicts,
+        )?;
+    }
+    for (key, value) in extensions {
+        match (base.extensions.get_mut(&key), value) {
+            (Some(Value::Object(current)), Value::Object(entries))
+                if DEPENDENCY_KEYWORDS.contains(&key.as_str()) =>
+            {
+                merge_dependencies(current, entries);
+            }
+            (Some(_), _) => {}
+            (None, value) => {
+                base.extensions.insert(key, value);
+            }
+        }
+    }
+    Ok(())
+}
+
+fn merge_metadata(base: &mut Metadata, other: Metadata) {
+    if base.id.is_none() {
+        base.id = other.id;
+    }
+    if base.title.is_none() {
+        base.title = other.title;
+    }
+    if base.description.is_none() {
+        base.description = other.description;
+    }
+    if base.default.is_none() {
+        base.default = other.default;
+    }
+    base.deprecated |= other.deprecated;
+    base.read_only |= other.read_only;
+    base.write_only |= other.write_only;
+    if base.examples.is_empty() {
+        base.examples = other.examples;
+    }
+}
+
+fn merge_instance_type(
+    base: &mut Option<SingleOrVec<InstanceType>>,
+    other: Option<SingleOrVec<InstanceType>>,
+    conflicts: &mut Vec<String>,
+) {
+    let Some(other) = other else {
+        return;
+    };
+    let Some(current) = base.as_ref() else {
+        *base = Some(other);
+        return;
+    };
+    let left = type_list(current);
+    let right = type_list(&other);
+    let mut shared = Vec::new();
+    for kind in &left {
+        let overlap = if right.contains(kind) {
+            Some(*kind)
+        } else if matches!(kind, InstanceType::Integer | InstanceType::Number)
+            && (right.contains(&InstanceType::Integer) || right.contains(&InstanceType::Number))
+        {
+            Some(InstanceType::Integer)
+        } else {
+            None
+        };
+        if let Some(kind) = overlap
+            && !shared.contains(&kind)
+        {
+            shared.push(kind);
+        }
+    }
+    match shared.len() {
+        0 => conflicts.push(format!(
+            "allOf declares incompatible types ({} vs {})",
+            type_names(&left),
+            type_names(&right)
+        )),
+        1 => *base = Some(SingleOrVec::Single(Box::new(shared[0]))),
+        _ => *base = Some(SingleOrVec::Vec(shared)),
+    }
+}
+
+fn type_list(types: &SingleOrVec<InstanceType>) -> Vec<InstanceType> {
+    match types {
+        SingleOrVec::Single(single) => vec![**single],
+        SingleOrVec::Vec(items) => items.clone(),
+    }
+}
+
+fn type_names(types: &[InstanceType]) -> String {
+    types
+        .iter()
+        .map(|kind| format!("{kind:?}").to_ascii_lowercase())
+        .collect::<Vec<_>>()
+        .join("|")
+}
+
+fn merge_exact<T: PartialEq>(
+    base: &mut Option<T>,
+    other: Option<T>,
+    keyword: &str,
+    conflicts: &mut Vec<String>,
+) {
+    match (base.as_ref(), other) {
+        (_, None) => {}
+        (None, Some(value)) => *base = Some(value),
+        (Some(current), Some(value)) => {
+            if *current != value {
+                conflicts.push(format!("allOf declares conflicting '{keyword}'"));
+            }
+        }
+    }
+}
+
+fn merge_enum(
+    base: &mut Option<Vec<Value>>,
+    other: Option<Vec<Value>>,
+    conflicts: &mut Vec<String>,
+) {
+    let Some(other) = other else {
+        return;
+    };
+    let Some(current) = base.as_mut() else {
+        *base = Some(other);
+        return;
+    };
+    let shared = current
+        .iter()
+        .filter(|value| other.contains(value))
+        .cloned()
+        .collect::<Vec<_>>();
+    if shared.is_empty() {
+        conflicts.push("allOf declares disjoint 'enum' lists".to_string());
+    } else {
+        *current = shared;
+    }
+}
+
+fn merge_subschemas(
+    base: &mut SubschemaValidation,
+    other: SubschemaValidation,
+    conflicts: &mut Vec<String>,
+) {
+    merge_exact(&mut base.one_of, other.one_of, "oneOf", conflicts);
+    merge_exact(&mut base.any_of, other.any_of, "anyOf", conflicts);
+    merge_exact(&mut base.not, other.not, "not", conflicts);
+    let conditional = SubschemaValidation {
+        if_schema: other.if_schema,
+        then_schema: other.then_schema,
+        else_schema: other.else_schema,
+        ..Default::default()
+    };
+    let extra = base.all_of.get_or_insert_with(Vec::new);
+    extra.extend(other.all_of.unwrap_or_default());
+    if conditional != SubschemaValidation::default() {
+        if base.if_schema.is_none() && base.then_schema.is_none() && base.else_schema.is_none() {
+            base.if_schema = conditional.if_schema;
+            base.then_schema = conditional.then_schema;
+            base.else_schema = conditional.else_schema;
+        } else {
+            extra.push(Schema::Object(SchemaObject {
+                subschemas: Some(Box::new(conditional)),
+                ..Default::default()
+            }));
+        }
+    }
+    if base.all_of.as_ref().is_some_and(Vec::is_empty) {
+        base.all_of = None;
+    }
+}
+
+/// Unions two `dependencies`-style maps. A trigger declared by both keeps
+/// every requirement: name lists are joined, schemas combined under `allOf`.
+fn merge_dependencies(base: &mut Map<String, Value>, other: Map<String, Value>) {
+    for (trigger, dependency) in other {
+        let Some(current) = base.get_mut(&trigger) else {
+            base.insert(trigger, dependency);
+            continue;
+        };
+        match (current, dependency) {
+            (Value::Array(names), Value::Array(more)) => {
+                for name in more {
+                    if !names.contains(&name) {
+                        names.push(name);
+                    }
+                }
+            }
+            (current, dependency) if *current != dependency => {
+                let as_schema = |value: Value| match value {
+                    Value::Array(names) => json!({ "required": names }),
+                    schema => schema,
+                };
+                let left = as_schema(current.take());
+                *current = json!({ "allOf": [left, as_schema(dependency)] });
+            }
+            _ => {}
+        }
+    }
+}
+
+fn merge_number(base: &mut NumberValidation, other: NumberValidation, conflicts: &mut Vec<String>) {
+    merge_exact(
+        &mut base.multiple_of,
+        other.multiple_of,
+        "multipleOf",
+        conflicts,
+    );
+    base.minimum = stricter(base.minimum, other.minimum, f64::max);
+    base.exclusive_minimum = stricter(base.exclusive_minimum, other.exclusive_minimum, f64::max);
+    base.maximum = stricter(base.maximum, other.maximum, f64::min);
+    base.exclusive_maximum = stricter(base.exclusive_maximum, other.exclusive_maximum, f64::min);
+    if let (Some(min), Some(max)) = (base.minimum, base.maximum)
+        && min > max
+    {
+        conflicts.push(format!(
+            "allOf leaves an empty range (minimum {min} > maximum {max})"
+        ));
+    }
+}
+
+fn merge_string(base: &mut StringValidation, other: StringValidation, conflicts: &mut Vec<String>) {
+    base.min_length = stricter(base.min_length, other.min_length, u32::max);
+    base.max_length = stricter(base.max_length, other.max_length, u32::min);
+    merge_exact(&mut base.pattern, other.pattern, "pattern", conflicts);
+    if let (Some(min), Some(max)) = (base.min_length, base.max_length)
+        && min > max
+    {
+        conflicts.push(format!(
+            "allOf leaves an empty range (minLength {min} > maxLength {max})"
+        ));
+    }
+}
+
+fn merge_array(base: &mut ArrayValidation, other: ArrayValidation, conflicts: &mut Vec<String>) {
+    merge_exact(&mut base.items, other.items, "items", conflicts);
+    merge_exact(
+        &mut base.additional_items,
+        other.additional_items,
+        "additionalItems",
+        conflicts,
+    );
+    merge_exact(&mut base.contains, other.contains, "contains", conflicts);
+    base.min_items = stricter(base.min_items, other.min_items, u32::max);
+    base.max_items = stricter(base.max_items, other.max_items, u32::min);
+    base.unique_items = match (base.unique_items, other.unique_items) {
+        (Some(left), Some(right)) => Some(left || right),
+        (left, right) => left.or(right),
+    };
+    if let (Some(min), Some(max)) = (base.min_items, base.max_items)
+        && min > max
+    {
+        conflicts.push(format!(
+            "allOf leaves an empty range (minItems {min} > maxItems {max})"
+        ));
+    }
+}
+
+fn merge_object(
+    resolver: &SchemaResolver<'_>,
+    base: &mut ObjectValidation,
+    other: ObjectValidation,
+    conflicts: &mut Vec<String>,
+) -> Result<()> {
+    base.min_properties = stricter(base.min_properties, other.min_properties, u32::max);
+    base.max_properties = stricter(base.max_properties, other.max_properties, u32::min);
+    base.required.extend(other.required);
+    for (name, schema) in other.properties {
+        match base.properties.get_mut(&name) {
+            Some(existing) if *existing != schema => {
+                let mut merged = resolver.resolve_schema(existing)?;
+                let mut nested = Vec::new();
+                merge_into(
+                    resolver,
+                    &mut merged,
+                    resolver.resolve_schema(&schema)?,
+                    &mut nested,
+                )?;
+                conflicts.extend(
+                    nested
+                        .into_iter()
+                        .map(|message| format!("property '{name}': {message}")),
+                );
+                *existing = Schema::Object(merged);
+            }
+            Some(_) => {}
+            None => {
+                base.properties.insert(name, schema);
+            }
+        }
+    }
+    for (pattern, schema) in other.pattern_properties {
+        base.pattern_properties.entry(pattern).or_insert(schema);
+    }
+    merge_exact(
+        &mut base.additional_properties,
+        other.additional_properties,
+        "additionalProperties",
+        conflicts,
+    );
+    merge_exact(
+        &mut base.property_names,
+        other.property_names,
+        "propertyNames",
+        conflicts,
+    );
+    Ok(())
+}
+
+fn stricter<T: Copy>(left: Option<T>, right: Option<T>, pick: fn(T, T) -> T) -> Option<T> {
+    match (left, right) {
+        (Some(a), Some(b)) => Some(pick(a, b)),
+        (a, b) => a.or(b),
+    }
+}
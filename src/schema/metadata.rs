@@ -3,6 +3,8 @@ use std::collections::HashMap;
 use schemars::schema::SchemaObject;
 use serde_json::Value;
 
+use crate::domain::{FieldUi, Widget};
+
 #[derive(Debug, Clone)]
 pub struct SectionInfo {
     pub id: String,
@@ -49,15 +51,84 @@ pub fn section_info_for_object(
     }
 }
 
+/// `x-` keys schemaui reads itself; they are left out of [`metadata_map`].
+const KNOWN_EXTENSIONS: [&str; 10] = [
+    "x-order",
+    "x-hidden",
+    "x-widget",
+    "x-placeholder",
+    "x-help",
+    "x-advanced",
+    "x-enumNames",
+    "x-group",
+    "x-group-title",
+    "x-group-description",
+];
+
+/// The `x-*` keys nothing in schemaui reads, kept for custom renderers.
 pub fn metadata_map(schema: &SchemaObject) -> HashMap<String, Value> {
     schema
         .extensions
         .iter()
-        .filter(|(key, _)| key.starts_with("x-"))
+        .filter(|(key, _)| key.starts_with("x-") && !KNOWN_EXTENSIONS.contains(&key.as_str()))
         .map(|(key, value)| (key.clone(), value.clone()))
         .collect()
 }
 
+/// Strings allowed to be longer than this many characters (`maxLength`) are
+/// edited in a textarea unless `x-widget` says otherwise.
+pub const TEXTAREA_MIN_MAX_LENGTH: u32 = 256;
+
+/// Reads the `x-order`, `x-hidden`, `x-widget`, `x-placeholder`, `x-help` and
+/// `x-advanced` vocabulary. Unknown widget names are ignored. Without an
+/// `x-widget`, a `contentMediaType` or a long `maxLength` picks the textarea.
+pub fn field_ui(schema: &SchemaObject) -> FieldUi {
+    let flag = |key: &str| {
+        schema
+            .extensions
+            .get(key)
+            .and_then(Value::as_bool)
+            .unwrap_or(false)
+    };
+    let widget = extension_string(schema, "x-widget").and_then(|name| match name.as_str() {
+        "textarea" => Some(Widget::Textarea),
+        "password" => Some(Widget::Password),
+        "radio" => Some(Widget::Radio),
+        "slider" => {
+            let number = schema.number.as_deref().cloned().unwrap_or_default();
+            let step = number.multiple_of.filter(|step| *step > 0.0).unwrap_or(1.0);
+            // Exclusive bounds are stepped inward so the slider never lands on them.
+            let min = [number.minimum, number.exclusive_minimum.map(|b| b + step)]
+                .into_iter()
+                .flatten()
+                .reduce(f64::max)
+                .unwrap_or(0.0);
+            let max = [number.maximum, number.exclusive_maximum.map(|b| b - step)]
+                .into_iter()
+                .flatten()
+                .reduce(f64::min)
+                .unwrap_or(100.0);
+            Some(Widget::Slider { min, max, step })
+        }
+        _ => None,
+    });
+    let long_text = schema.extensions.contains_key("contentMediaType")
+        || schema
+            .string
+            .as_ref()
+            .and_then(|string| string.max_length)
+            .is_some_and(|max| max > TEXTAREA_MIN_MAX_LENGTH);
+    let widget = widget.or_else(|| long_text.then_some(Widget::Textarea));
+    FieldUi {
+        order: schema.extensions.get("x-order").and_then(Value::as_i64),
+        hidden: flag("x-hidden"),
+        widget,
+        placeholder: extension_string(schema, "x-placeholder"),
+        help: extension_string(schema, "x-help"),
+        advanced: flag("x-advanced"),
+    }
+}
+
 pub fn extension_string(schema: &SchemaObject, key: &str) -> Option<String> {
     schema
         .extensions
@@ -1,6 +1,9 @@
+pub mod bundle;
 pub mod layout;
 pub mod loader;
+pub mod merge;
 pub mod metadata;
 pub mod resolver;
+pub mod retriever;
 
 pub use layout::build_form_schema;
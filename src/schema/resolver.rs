@@ -1,17 +1,36 @@
+use std::cell::RefCell;
+
 use anyhow::{Context, Result, bail};
 use percent_encoding::percent_decode_str;
 use schemars::schema::{RootSchema, Schema, SchemaObject};
-use serde_json::Value;
+use serde_json::{Map, Value};
+
+use super::merge::merge_all_of;
 
 #[derive(Debug)]
 pub struct SchemaResolver<'a> {
     raw: &'a Value,
     root: &'a RootSchema,
+    conflicts: RefCell<Vec<String>>,
+    /// `$ref` chain currently being followed, to reject `A -> B -> A` aliases.
+    following: RefCell<Vec<String>>,
+    /// `$ref` targets whose properties are being laid out, innermost last.
+    expanding: RefCell<Vec<String>>,
 }
 
 impl<'a> SchemaResolver<'a> {
     pub fn new(raw: &'a Value, root: &'a RootSchema) -> Self {
-        Self { raw, root }
+        Self {
+            raw,
+            root,
+            conflicts: RefCell::new(Vec::new()),
+            following: RefCell::new(Vec::new()),
+            expanding: RefCell::new(
+                std::iter::once("#".to_string())
+                    .chain(root.schema.reference.clone())
+                    .collect(),
+            ),
+        }
     }
 
     pub fn root_object(&self) -> Option<&SchemaObject> {
@@ -25,21 +44,98 @@ impl<'a> SchemaResolver<'a> {
                 if let Some(reference) = &object.reference {
                     self.follow_reference(reference)
                 } else {
-                    Ok(object.clone())
+                    let mut conflicts = Vec::new();
+                    let merged = merge_all_of(self, object.clone(), &mut conflicts)?;
+                    self.conflicts.borrow_mut().extend(conflicts);
+                    Ok(merged)
                 }
             }
         }
     }
 
-    pub fn definitions_snapshot(&self) -> Option<Value> {
-        self.raw
-            .as_object()
-            .and_then(|obj| obj.get("definitions"))
-            .cloned()
+    /// Drain the `allOf` conflicts and other reports collected since the
+    /// last call.
+    pub fn take_conflicts(&self) -> Vec<String> {
+        std::mem::take(&mut *self.conflicts.borrow_mut())
+    }
+
+    /// Queue a diagnostic for the schema currently being laid out.
+    pub fn report(&self, message: impl Into<String>) {
+        self.conflicts.borrow_mut().push(message.into());
+    }
+
+    /// Whether `schema` is a `$ref` back to an object that is already being
+    /// expanded, i.e. laying it out again would never terminate.
+    pub fn is_recursive(&self, schema: &Schema) -> bool {
+        reference_of(schema).is_some_and(|reference| {
+            self.expanding
+                .borrow()
+                .iter()
+                .any(|ancestor| ancestor == reference)
+        })
+    }
+
+    /// Runs `build` with the `$ref` target of `schema` (if any) marked as
+    /// being expanded, so nested references to it are seen by `is_recursive`.
+    pub fn expand<T>(&self, schema: &Schema, build: impl FnOnce() -> Result<T>) -> Result<T> {
+        let Some(reference) = reference_of(schema) else {
+            return build();
+        };
+        self.expanding.borrow_mut().push(reference.to_string());
+        let result = build();
+        self.expanding.borrow_mut().pop();
+        result
+    }
+
+    /// Standalone schema for a sub-form that is laid out later, on demand.
+    /// Definition references stay references so the detached form knows
+    /// which target it is expanding; `#` reuses the whole document.
+    pub fn detached_schema(&self, raw: &Schema, resolved: &SchemaObject) -> Result<Value> {
+        let mut value = match reference_of(raw) {
+            Some("#") => return Ok(self.raw.clone()),
+            Some(reference)
+                if reference.starts_with("#/definitions/") || reference.starts_with("#/$defs/") =>
+            {
+                serde_json::json!({ "$ref": reference })
+            }
+            _ => serde_json::to_value(Schema::Object(resolved.clone()))
+                .context("failed to serialize sub-form schema")?,
+        };
+        if let Value::Object(map) = &mut value {
+            for (keyword, definitions) in self.definitions_snapshot() {
+                map.entry(keyword).or_insert(definitions);
+            }
+        }
+        Ok(value)
+    }
+
+    /// Root `definitions` and `$defs`, for embedding into sub-schemas that are
+    /// compiled or laid out on their own.
+    pub fn definitions_snapshot(&self) -> Map<String, Value> {
+        let Some(root) = self.raw.as_object() else {
+            return Map::new();
+        };
+        ["definitions", "$defs"]
+            .into_iter()
+            .filter_map(|keyword| Some((keyword.to_string(), root.get(keyword)?.clone())))
+            .collect()
     }
 
     fn follow_reference(&self, reference: &str) -> Result<SchemaObject> {
-        if let Some(key) = reference.strip_prefix("#/definitions/") {
+        if self.following.borrow().iter().any(|seen| seen == reference) {
+            let chain = self.following.borrow().join(" -> ");
+            bail!("circular $ref: {chain} -> {reference}");
+        }
+        self.following.borrow_mut().push(reference.to_string());
+        let result = self.follow_reference_inner(reference);
+        self.following.borrow_mut().pop();
+        result
+    }
+
+    fn follow_reference_inner(&self, reference: &str) -> Result<SchemaObject> {
+        if let Some(key) = reference.strip_prefix("#/definitions/")
+            && !key.contains('/')
+        {
             let target = self
                 .root
                 .definitions
@@ -71,3 +167,10 @@ impl<'a> SchemaResolver<'a> {
         bail!("unsupported reference {reference}")
     }
 }
+
+fn reference_of(schema: &Schema) -> Option<&str> {
+    match schema {
+        Schema::Object(object) => object.reference.as_deref(),
+        Schema::Bool(_) => None,
+    }
+}
@@ -0,0 +1,137 @@
+use std::{fmt, fs, path::Path, sync::Arc};
+
+use anyhow::{Context, Result, bail};
+use serde_json::Value;
+use url::Url;
+
+use crate::io::{DocumentFormat, input::parse_document_str};
+
+/// Fetches the external documents referenced by `$ref`.
+///
+/// `uri` is always absolute (relative references are joined against the
+/// location of the document that contains them) and carries no fragment.
+pub trait SchemaRetriever: Send + Sync {
+    fn retrieve(&self, uri: &str) -> Result<Value>;
+}
+
+/// Reads `file://` documents from disk. This is the default retriever.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FileSystemRetriever;
+
+impl SchemaRetriever for FileSystemRetriever {
+    fn retrieve(&self, uri: &str) -> Result<Value> {
+        let url = Url::parse(uri).with_context(|| format!("invalid schema uri '{uri}'"))?;
+        if url.scheme() != "file" {
+            bail!("cannot retrieve '{uri}': only file:// references are enabled");
+        }
+        let path = url
+            .to_file_path()
+            .map_err(|_| anyhow::anyhow!("'{uri}' is not a valid file path"))?;
+        let contents = fs::read_to_string(&path)
+            .with_context(|| format!("failed to read schema {}", path.display()))?;
+        parse_by_extension(&contents, &path)
+    }
+}
+
+/// Fetches `http(s)://` documents and keeps a copy of each one under
+/// `cache_dir`; `file://` references are delegated to [`FileSystemRetriever`].
+#[cfg(feature = "remote_refs")]
+#[derive(Debug, Clone)]
+pub struct HttpRetriever {
+    cache_dir: std::path::PathBuf,
+}
+
+#[cfg(feature = "remote_refs")]
+impl HttpRetriever {
+    pub fn new(cache_dir: impl Into<std::path::PathBuf>) -> Self {
+        Self {
+            cache_dir: cache_dir.into(),
+        }
+    }
+
+    /// A readable prefix of the URI followed by a hash of all of it, so
+    /// distinct URIs never share a file and names stay short.
+    pub(crate) fn cache_path(&self, uri: &str) -> std::path::PathBuf {
+        let prefix: String = uri
+            .chars()
+            .filter_map(|ch| match ch {
+                ch if ch.is_ascii_alphanumeric() || ch == '.' => Some(ch),
+                ':' => None,
+                _ => Some('_'),
+            })
+            .take(CACHE_PREFIX_LEN)
+            .collect();
+        self.cache_dir
+            .join(format!("{prefix}-{:016x}.cache", fnv1a(uri.as_bytes())))
+    }
+}
+
+#[cfg(feature = "remote_refs")]
+const CACHE_PREFIX_LEN: usize = 48;
+
+/// 64-bit FNV-1a: stable across builds, unlike `DefaultHasher`.
+#[cfg(feature = "remote_refs")]
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+#[cfg(feature = "remote_refs")]
+impl SchemaRetriever for HttpRetriever {
+    fn retrieve(&self, uri: &str) -> Result<Value> {
+        let url = Url::parse(uri).with_context(|| format!("invalid schema uri '{uri}'"))?;
+        match url.scheme() {
+            "file" => return FileSystemRetriever.retrieve(uri),
+            "http" | "https" => {}
+            other => bail!("cannot retrieve '{uri}': unsupported scheme '{other}'"),
+        }
+        let cached = self.cache_path(uri);
+        let contents = match fs::read_to_string(&cached) {
+            Ok(contents) => contents,
+            Err(_) => {
+                let contents = reqwest::blocking::get(url.as_str())
+                    .and_then(|response| response.error_for_status())
+                    .and_then(|response| response.text())
+                    .with_context(|| format!("failed to download {uri}"))?;
+                fs::create_dir_all(&self.cache_dir).with_context(|| {
+                    format!("failed to create cache dir {}", self.cache_dir.display())
+                })?;
+                fs::write(&cached, &contents).with_context(|| format!("failed to cache {uri}"))?;
+                contents
+            }
+        };
+        parse_by_extension(&contents, Path::new(url.path()))
+    }
+}
+
+fn parse_by_extension(contents: &str, path: &Path) -> Result<Value> {
+    let format = DocumentFormat::from_extension(path).unwrap_or_default();
+    parse_document_str(contents, format)
+        .with_context(|| format!("failed to parse schema {}", path.display()))
+}
+
+/// Shares one retriever between layout and the `jsonschema` validator.
+#[derive(Clone)]
+pub(crate) struct SharedRetriever(pub(crate) Arc<dyn SchemaRetriever>);
+
+impl Default for SharedRetriever {
+    fn default() -> Self {
+        Self(Arc::new(FileSystemRetriever))
+    }
+}
+
+impl fmt::Debug for SharedRetriever {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("SharedRetriever")
+    }
+}
+
+impl jsonschema::Retrieve for SharedRetriever {
+    fn retrieve(
+        &self,
+        uri: &jsonschema::Uri<String>,
+    ) -> Result<Value, Box<dyn std::error::Error + Send + Sync>> {
+        self.0.retrieve(uri.as_str()).map_err(Into::into)
+    }
+}
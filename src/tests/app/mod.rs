@@ -1 +1,3 @@
 mod input_tests;
+mod review_tests;
+mod typed_tests;
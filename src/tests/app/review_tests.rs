@@ -0,0 +1,82 @@
+use serde_json::{Value, json};
+
+use crate::{
+    app::review::{ChangeKind, ReviewState, ValueChange, diff_values},
+    tests::form::state_for,
+};
+
+fn change(pointer: &str, kind: ChangeKind, old: Option<Value>, new: Option<Value>) -> ValueChange {
+    ValueChange {
+        pointer: pointer.to_string(),
+        kind,
+        old,
+        new,
+    }
+}
+
+#[test]
+fn diff_lists_added_removed_and_changed_pointers() {
+    let before = json!({
+        "port": 8080,
+        "debug": true,
+        "tags": ["a", "b"],
+        "log": {"level": "info"},
+        "a/b": 1
+    });
+    let after = json!({
+        "port": 9090,
+        "tags": ["a"],
+        "log": {"level": "info", "file": "/var/log/app"},
+        "a/b": "1"
+    });
+    assert_eq!(
+        diff_values(&before, &after),
+        vec![
+            change(
+                "/a~1b",
+                ChangeKind::Changed,
+                Some(json!(1)),
+                Some(json!("1"))
+            ),
+            change("/debug", ChangeKind::Removed, Some(json!(true)), None),
+            change(
+                "/log/file",
+                ChangeKind::Added,
+                None,
+                Some(json!("/var/log/app"))
+            ),
+            change(
+                "/port",
+                ChangeKind::Changed,
+                Some(json!(8080)),
+                Some(json!(9090))
+            ),
+            change("/tags/1", ChangeKind::Removed, Some(json!("b")), None),
+        ]
+    );
+    assert!(diff_values(&after, &after).is_empty());
+}
+
+#[test]
+fn review_rows_mask_secrets_and_focus_the_owning_field() {
+    let schema = json!({
+        "type": "object",
+        "properties": {
+            "password": {"type": "string", "writeOnly": true},
+            "tags": {"type": "array", "items": {"type": "string"}}
+        }
+    });
+    let mut state = state_for(&schema);
+    let after = json!({"password": "hunter2", "tags": ["x"]});
+    let review = ReviewState::new(after.clone(), diff_values(&json!({}), &after), &state);
+
+    let rows = review.as_render().rows;
+    assert_eq!(rows[0].pointer, "/password");
+    assert_eq!(rows[0].symbol, '+');
+    assert!(!rows[0].detail.contains("hunter2"), "{}", rows[0].detail);
+    assert_eq!(rows[1].detail, "[\"x\"]");
+
+    assert!(state.focus_pointer("/tags/0"));
+    assert_eq!(state.focused_field().unwrap().schema.pointer, "/tags");
+    assert!(!state.focus_pointer("/tag"));
+}
@@ -0,0 +1,131 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::{SchemaUI, form::FormState, schema::layout::build_form_schema};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+enum Level {
+    Debug,
+    Info,
+    Warn,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+struct Logging {
+    level: Level,
+    file: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+/// Service configuration
+struct ServiceConfig {
+    host: String,
+    port: u16,
+    public: bool,
+    features: Vec<String>,
+    log: Logging,
+}
+
+fn sample() -> ServiceConfig {
+    ServiceConfig {
+        host: "0.0.0.0".into(),
+        port: 9000,
+        public: true,
+        features: vec!["metrics".into(), "tracing".into()],
+        log: Logging {
+            level: Level::Warn,
+            file: Some("/var/log/service.log".into()),
+        },
+    }
+}
+
+fn build_value(ui: &SchemaUI) -> serde_json::Value {
+    let form = build_form_schema(ui.schema()).expect("layout");
+    FormState::from_schema(&form)
+        .try_build_value()
+        .expect("value")
+}
+
+#[test]
+fn for_type_lays_out_the_struct_without_diagnostics() {
+    let ui = SchemaUI::for_type::<ServiceConfig>();
+    assert_eq!(ui.schema()["title"], "ServiceConfig");
+    assert!(ui.diagnostics().expect("report").is_empty());
+
+    let form = ui.form_schema().expect("layout");
+    let mut pointers = form
+        .roots
+        .iter()
+        .flat_map(|root| &root.sections)
+        .flat_map(|section| &section.fields)
+        .map(|field| (field.pointer.as_str(), field.required))
+        .collect::<Vec<_>>();
+    pointers.sort();
+    assert_eq!(
+        pointers,
+        [
+            ("/features", true),
+            ("/host", true),
+            ("/log/file", false),
+            ("/log/level", true),
+            ("/port", true),
+            ("/public", true),
+        ]
+    );
+}
+
+#[test]
+fn initial_value_round_trips_into_the_type() {
+    let config = sample();
+    let ui = SchemaUI::for_type::<ServiceConfig>()
+        .with_initial(&config)
+        .expect("seeded");
+    let value = build_value(&ui);
+    let back: ServiceConfig = serde_json::from_value(value).expect("typed value");
+    assert_eq!(back, config);
+}
+
+#[test]
+fn optional_fields_come_back_as_none() {
+    let mut config = sample();
+    config.log.file = None;
+    let ui = SchemaUI::for_type::<ServiceConfig>()
+        .with_initial(&config)
+        .expect("seeded");
+    let back: ServiceConfig = serde_json::from_value(build_value(&ui)).expect("typed value");
+    assert_eq!(back.log.file, None);
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+struct Overrides {
+    log: Option<Logging>,
+    level: Option<Level>,
+}
+
+#[test]
+fn option_wrappers_become_nullable_fields() {
+    let ui = SchemaUI::for_type::<Overrides>();
+    assert!(ui.diagnostics().expect("report").is_empty());
+    let unset: Overrides = serde_json::from_value(build_value(&ui)).expect("typed value");
+    assert_eq!(
+        unset,
+        Overrides {
+            log: None,
+            level: None
+        }
+    );
+
+    let set = Overrides {
+        log: Some(Logging {
+            level: Level::Debug,
+            file: None,
+        }),
+        level: Some(Level::Info),
+    };
+    let ui = SchemaUI::for_type::<Overrides>()
+        .with_initial(&set)
+        .expect("seeded");
+    let back: Overrides = serde_json::from_value(build_value(&ui)).expect("typed value");
+    assert_eq!(back, set);
+}
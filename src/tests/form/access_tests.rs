@@ -0,0 +1,93 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use serde_json::{Value, json};
+
+use crate::{form::FieldState, schema::layout::build_form_schema};
+
+use super::state_for;
+
+fn schema() -> Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "id": {"type": "string", "readOnly": true, "default": "abc"},
+            "enabled": {"type": "boolean", "readOnly": true},
+            "password": {"type": "string", "writeOnly": true}
+        }
+    })
+}
+
+#[test]
+fn annotations_are_lifted_onto_field_schema() {
+    let form = build_form_schema(&schema()).unwrap();
+    let fields = &form.roots[0].sections[0].fields;
+    let id = fields.iter().find(|field| field.name == "id").unwrap();
+    let password = fields
+        .iter()
+        .find(|field| field.name == "password")
+        .unwrap();
+    assert!(id.read_only && !id.write_only);
+    assert!(password.write_only && !password.read_only);
+}
+
+#[test]
+fn read_only_fields_ignore_edits_but_keep_their_value() {
+    let mut state = state_for(&schema());
+    let id = state.field_mut_by_pointer("/id").unwrap();
+    let key = KeyEvent::new(KeyCode::Char('x'), KeyModifiers::NONE);
+    assert!(!id.handle_key(&key));
+    assert!(!id.dirty);
+
+    let enabled = state.field_mut_by_pointer("/enabled").unwrap();
+    enabled.set_bool(true);
+    assert!(!enabled.dirty);
+
+    assert_eq!(state.try_build_value().unwrap()["id"], json!("abc"));
+}
+
+#[test]
+fn write_only_fields_toggle_between_masked_and_revealed() {
+    let mut state = state_for(&schema());
+    let password = state.field_mut_by_pointer("/password").unwrap();
+    assert!(password.is_masked());
+    assert!(password.toggle_reveal());
+    assert!(!password.is_masked());
+
+    let id = state.field_mut_by_pointer("/id").unwrap();
+    assert!(!id.toggle_reveal());
+    assert!(!id.is_masked());
+}
+
+#[test]
+fn composite_summaries_mask_secrets_until_revealed() {
+    let mut state = state_for(&json!({
+        "type": "object",
+        "properties": {
+            "auth": {
+                "oneOf": [{
+                    "title": "Token",
+                    "type": "object",
+                    "properties": {
+                        "token": {"type": "string", "x-widget": "password"}
+                    }
+                }]
+            }
+        }
+    }));
+    state.seed_from_value(&json!({"auth": {"token": "s3cret"}}));
+    let auth = state.field_mut_by_pointer("/auth").unwrap();
+    let lines = |auth: &FieldState| {
+        auth.composite_variant_summaries()
+            .unwrap()
+            .into_iter()
+            .flat_map(|summary| summary.lines)
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+    assert!(lines(auth).contains("Token = ••••••"), "{}", lines(auth));
+
+    let mut session = auth.open_composite_editor(0).unwrap();
+    let token = session.form_state.field_mut_by_pointer("/token").unwrap();
+    assert!(token.toggle_reveal());
+    auth.close_composite_editor(session, true);
+    assert!(lines(auth).contains("Token = s3cret"), "{}", lines(auth));
+}
@@ -15,7 +15,14 @@ fn integer_schema() -> FieldSchema {
         kind: FieldKind::Integer,
         required: false,
         default: None,
+        read_only: false,
+        write_only: false,
+        nullable: false,
+        item_bounds: Default::default(),
+        ui: Default::default(),
         metadata: Default::default(),
+        visible_when: Vec::new(),
+        required_when: Vec::new(),
     }
 }
 
@@ -0,0 +1,153 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use serde_json::json;
+
+use crate::{form::FormState, schema::layout::build_form_schema};
+
+fn deployment_schema() -> serde_json::Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "deploy": {
+                "type": "object",
+                "required": ["mode"],
+                "properties": {
+                    "mode": {"type": "string", "enum": ["single", "cluster"]},
+                    "region": {"type": "string"}
+                },
+                "if": {
+                    "required": ["mode"],
+                    "properties": {"mode": {"const": "cluster"}}
+                },
+                "then": {
+                    "required": ["region", "replicas"],
+                    "properties": {"replicas": {"type": "integer", "minimum": 1}}
+                },
+                "else": {
+                    "properties": {"host": {"type": "string"}}
+                }
+            }
+        }
+    })
+}
+
+fn visible_names(state: &FormState) -> Vec<String> {
+    state.roots[0].sections[0]
+        .fields
+        .iter()
+        .map(|field| field.schema.name.clone())
+        .collect()
+}
+
+#[test]
+fn layout_records_conditional_branches() {
+    let form = build_form_schema(&deployment_schema()).expect("schema parsed");
+    let section = &form.roots[0].sections[0];
+    let names: Vec<_> = section.fields.iter().map(|f| f.name.as_str()).collect();
+    assert_eq!(names, vec!["mode", "region", "replicas", "host"]);
+
+    let region = &section.fields[1];
+    assert!(region.visible_when.is_empty());
+    assert_eq!(region.required_when.len(), 1);
+    assert!(region.required_when[0].matches);
+    assert_eq!(region.required_when[0].scope, vec!["deploy".to_string()]);
+
+    let replicas = &section.fields[2];
+    assert!(replicas.visible_when[0].matches);
+    let host = &section.fields[3];
+    assert!(!host.visible_when[0].matches);
+}
+
+#[test]
+fn controlling_value_toggles_visibility_and_required() {
+    let form = build_form_schema(&deployment_schema()).expect("schema parsed");
+    let mut state = FormState::from_schema(&form);
+    assert_eq!(visible_names(&state), vec!["mode", "region", "host"]);
+
+    state.seed_from_value(&json!({"deploy": {"mode": "cluster", "replicas": 3}}));
+    assert_eq!(visible_names(&state), vec!["mode", "region", "replicas"]);
+    let region = state.field_by_pointer("/deploy/region").expect("region");
+    assert!(region.schema.required);
+
+    state.seed_from_value(&json!({"deploy": {"mode": "single"}}));
+    assert_eq!(visible_names(&state), vec!["mode", "region", "host"]);
+    let region = state.field_by_pointer("/deploy/region").expect("region");
+    assert!(!region.schema.required);
+}
+
+#[test]
+fn inactive_branch_values_are_left_out() {
+    let form = build_form_schema(&deployment_schema()).expect("schema parsed");
+    let mut state = FormState::from_schema(&form);
+    state.seed_from_value(&json!({
+        "deploy": {"mode": "single", "host": "db01", "replicas": 4, "region": "eu"}
+    }));
+    let value = state.try_build_value().expect("value");
+    assert_eq!(
+        value,
+        json!({"deploy": {"mode": "single", "region": "eu", "host": "db01"}})
+    );
+
+    state.seed_from_value(&json!({"deploy": {"mode": "cluster"}}));
+    let value = state.try_build_value().expect("value");
+    assert_eq!(
+        value,
+        json!({"deploy": {"mode": "cluster", "region": "eu", "replicas": 4}})
+    );
+}
+
+#[test]
+fn focus_follows_field_when_branch_changes() {
+    let schema = json!({
+        "type": "object",
+        "properties": {
+            "kind": {"type": "string"},
+            "note": {"type": "string"}
+        },
+        "if": {"properties": {"kind": {"const": "x"}}, "required": ["kind"]},
+        "then": {"properties": {"extra": {"type": "string"}}}
+    });
+    let form = build_form_schema(&schema).expect("schema parsed");
+    let mut state = FormState::from_schema(&form);
+    state.focus_next_field();
+    assert_eq!(
+        state.focused_field().map(|f| f.schema.name.as_str()),
+        Some("note")
+    );
+
+    let field = state.field_mut_by_pointer("/kind").expect("kind");
+    field.handle_key(&KeyEvent::new(KeyCode::Char('x'), KeyModifiers::NONE));
+    state.refresh_conditions();
+    assert_eq!(visible_names(&state), vec!["kind", "note", "extra"]);
+    assert_eq!(
+        state.focused_field().map(|f| f.schema.name.as_str()),
+        Some("note")
+    );
+}
+
+#[test]
+fn uncompilable_conditions_are_reported() {
+    let schema = json!({
+        "type": "object",
+        "properties": {
+            "mode": {"type": "string"}
+        },
+        "if": {"properties": {"mode": {"pattern": "(unclosed"}}},
+        "then": {"properties": {"region": {"type": "string"}}}
+    });
+    let form = build_form_schema(&schema).expect("schema parsed");
+    assert_eq!(form.diagnostics.len(), 1, "{:?}", form.diagnostics);
+    assert_eq!(form.diagnostics[0].pointer, "");
+    assert!(
+        form.diagnostics[0]
+            .message
+            .starts_with("`if` schema cannot be compiled"),
+        "{}",
+        form.diagnostics[0].message
+    );
+    let names: Vec<_> = form.roots[0].sections[0]
+        .fields
+        .iter()
+        .map(|field| field.name.as_str())
+        .collect();
+    assert_eq!(names, vec!["mode"]);
+}
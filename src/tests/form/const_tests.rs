@@ -0,0 +1,100 @@
+use serde_json::json;
+
+use crate::{domain::FieldKind, schema::layout::build_form_schema};
+
+use super::state_for;
+
+#[test]
+fn const_fields_are_locked_and_always_emitted() {
+    let schema = json!({
+        "type": "object",
+        "properties": {
+            "version": {"const": 2},
+            "name": {"type": "string"}
+        }
+    });
+    let form = build_form_schema(&schema).unwrap();
+    assert_eq!(
+        form.roots[0].sections[0].fields[1].kind,
+        FieldKind::Const(json!(2))
+    );
+
+    let mut state = state_for(&schema);
+    state.seed_from_value(&json!({"version": 3}));
+    let version = state.field_mut_by_pointer("/version").unwrap();
+    assert_eq!(version.display_value(), "2 (locked)");
+    assert_eq!(state.try_build_value().unwrap(), json!({"version": 2}));
+}
+
+#[test]
+fn discriminator_mapping_selects_variant() {
+    let schema = json!({
+        "type": "object",
+        "properties": {
+            "pet": {
+                "oneOf": [
+                    {"$ref": "#/definitions/Cat"},
+                    {"$ref": "#/definitions/Dog"}
+                ],
+                "discriminator": {
+                    "propertyName": "kind",
+                    "mapping": {"kitty": "#/definitions/Cat"}
+                }
+            }
+        },
+        "definitions": {
+            "Cat": {
+                "type": "object",
+                "properties": {"kind": {"type": "string"}, "lives": {"type": "integer"}}
+            },
+            "Dog": {
+                "type": "object",
+                "properties": {"kind": {"type": "string"}, "bark": {"type": "boolean"}}
+            }
+        }
+    });
+    let mut state = state_for(&schema);
+    for (value, expected) in [
+        (json!({"kind": "Dog", "bark": true}), 1),
+        (json!({"kind": "kitty", "lives": 9}), 0),
+    ] {
+        state.seed_from_value(&json!({"pet": value}));
+        let pet = state.field_by_pointer("/pet").unwrap();
+        assert_eq!(pet.active_composite_variants(), vec![expected]);
+        assert_eq!(state.try_build_value().unwrap(), json!({"pet": value}));
+    }
+}
+
+#[test]
+fn const_properties_pick_most_specific_variant() {
+    let schema = json!({
+        "type": "object",
+        "properties": {
+            "source": {
+                "oneOf": [
+                    {
+                        "title": "Any",
+                        "type": "object",
+                        "properties": {"path": {"type": "string"}}
+                    },
+                    {
+                        "title": "Git",
+                        "type": "object",
+                        "properties": {
+                            "type": {"const": "git"},
+                            "path": {"type": "string"}
+                        }
+                    }
+                ]
+            }
+        }
+    });
+    let mut state = state_for(&schema);
+    state.seed_from_value(&json!({"source": {"type": "git", "path": "repo"}}));
+    let source = state.field_by_pointer("/source").unwrap();
+    assert_eq!(source.active_composite_variants(), vec![1]);
+    assert_eq!(
+        state.try_build_value().unwrap(),
+        json!({"source": {"type": "git", "path": "repo"}})
+    );
+}
@@ -0,0 +1,113 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use serde_json::json;
+
+use crate::{form::FormState, schema::layout::build_form_schema};
+
+fn tls_schema() -> serde_json::Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "tls": {
+                "type": "object",
+                "properties": {
+                    "cert": {"type": "string"},
+                    "key": {"type": "string"}
+                },
+                "dependentRequired": {"cert": ["key"]},
+                "dependentSchemas": {
+                    "cert": {
+                        "properties": {"ca": {"type": "string", "title": "CA bundle"}}
+                    }
+                }
+            }
+        }
+    })
+}
+
+fn type_into(state: &mut FormState, pointer: &str, text: &str) {
+    let field = state.field_mut_by_pointer(pointer).expect("field");
+    for ch in text.chars() {
+        field.handle_key(&KeyEvent::new(KeyCode::Char(ch), KeyModifiers::NONE));
+    }
+    state.refresh_conditions();
+}
+
+fn visible_names(state: &FormState) -> Vec<String> {
+    state.roots[0].sections[0]
+        .fields
+        .iter()
+        .map(|field| field.schema.name.clone())
+        .collect()
+}
+
+#[test]
+fn filling_trigger_requires_and_reveals_dependents() {
+    let form = build_form_schema(&tls_schema()).expect("schema parsed");
+    let mut state = FormState::from_schema(&form);
+    assert_eq!(visible_names(&state), vec!["cert", "key"]);
+    assert!(
+        !state
+            .field_by_pointer("/tls/key")
+            .expect("key")
+            .schema
+            .required
+    );
+
+    type_into(&mut state, "/tls/cert", "server.pem");
+    assert_eq!(visible_names(&state), vec!["cert", "key", "ca"]);
+    assert!(
+        state
+            .field_by_pointer("/tls/key")
+            .expect("key")
+            .schema
+            .required
+    );
+
+    let field = state.field_mut_by_pointer("/tls/cert").expect("cert");
+    for _ in 0.."server.pem".len() {
+        field.handle_key(&KeyEvent::new(KeyCode::Backspace, KeyModifiers::NONE));
+    }
+    state.refresh_conditions();
+    assert_eq!(visible_names(&state), vec!["cert", "key"]);
+    assert!(
+        !state
+            .field_by_pointer("/tls/key")
+            .expect("key")
+            .schema
+            .required
+    );
+}
+
+#[test]
+fn legacy_dependencies_keyword_is_supported() {
+    let schema = json!({
+        "type": "object",
+        "properties": {
+            "proxy": {"type": "string"},
+            "port": {"type": "integer"}
+        },
+        "dependencies": {
+            "proxy": ["port"],
+            "port": {"properties": {"timeout": {"type": "integer"}}}
+        }
+    });
+    let form = build_form_schema(&schema).expect("schema parsed");
+    let mut state = FormState::from_schema(&form);
+    assert_eq!(visible_names(&state), vec!["port", "proxy"]);
+
+    type_into(&mut state, "/proxy", "http://localhost");
+    assert!(
+        state
+            .field_by_pointer("/port")
+            .expect("port")
+            .schema
+            .required
+    );
+
+    type_into(&mut state, "/port", "8080");
+    assert_eq!(visible_names(&state), vec!["port", "proxy", "timeout"]);
+    assert_eq!(
+        state.try_build_value().expect("value"),
+        json!({"proxy": "http://localhost", "port": 8080})
+    );
+}
@@ -0,0 +1,87 @@
+use serde_json::{Value, json};
+
+use crate::{
+    domain::{EnumOption, FieldKind},
+    schema::layout::build_form_schema,
+};
+
+use super::state_for;
+
+fn tuning_schema() -> Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "level": {
+                "type": "integer",
+                "enum": [1, 2, 4],
+                "x-enumNames": ["Low", "Medium", "High"],
+                "default": 2
+            },
+            "mode": {
+                "oneOf": [
+                    {"const": "fast", "title": "Fast path"},
+                    {"const": "safe", "title": "Safe path"}
+                ]
+            },
+            "toggle": {"enum": [true, null]},
+            "weights": {
+                "type": "array",
+                "items": {"type": "number", "enum": [0.5, 1.5]}
+            }
+        }
+    })
+}
+
+fn option(value: Value, label: &str) -> EnumOption {
+    EnumOption {
+        value,
+        label: label.to_string(),
+    }
+}
+
+#[test]
+fn enum_options_keep_values_and_labels() {
+    let form = build_form_schema(&tuning_schema()).expect("schema parsed");
+    let fields = &form.roots[0].sections[0].fields;
+    assert_eq!(
+        fields[0].kind,
+        FieldKind::Enum(vec![
+            option(json!(1), "Low"),
+            option(json!(2), "Medium"),
+            option(json!(4), "High"),
+        ])
+    );
+    assert_eq!(
+        fields[1].kind,
+        FieldKind::Enum(vec![
+            option(json!("fast"), "Fast path"),
+            option(json!("safe"), "Safe path"),
+        ])
+    );
+    assert_eq!(
+        fields[2].kind,
+        FieldKind::Enum(vec![
+            option(json!(true), "true"),
+            option(Value::Null, "null")
+        ])
+    );
+}
+
+#[test]
+fn enum_fields_emit_typed_values() {
+    let schema = tuning_schema();
+    let mut state = state_for(&schema);
+    let level = state.field_by_pointer("/level").unwrap();
+    assert_eq!(level.display_value(), "Medium");
+
+    let value = json!({
+        "level": 4,
+        "mode": "safe",
+        "toggle": null,
+        "weights": [1.5]
+    });
+    state.seed_from_value(&value);
+    let built = state.try_build_value().unwrap();
+    assert_eq!(built, value);
+    assert!(jsonschema::validator_for(&schema).unwrap().is_valid(&built));
+}
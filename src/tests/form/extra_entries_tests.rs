@@ -0,0 +1,159 @@
+use serde_json::{Value, json};
+
+use crate::{
+    app::validation::{ValidationOutcome, validate_form},
+    domain::FieldKind,
+    form::FormState,
+    schema::layout::build_form_schema,
+};
+
+use super::state_for;
+
+fn schema() -> Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "server": {
+                "type": "object",
+                "properties": {
+                    "host": {"type": "string", "default": "localhost"},
+                    "port": {"type": "integer"}
+                },
+                "additionalProperties": {"type": "string"}
+            }
+        }
+    })
+}
+
+#[test]
+fn mixed_objects_get_an_extra_entries_map() {
+    let form = build_form_schema(&schema()).unwrap();
+    let section = &form.roots[0].sections[0];
+    let names: Vec<_> = section.fields.iter().map(|f| f.name.as_str()).collect();
+    assert_eq!(names, vec!["host", "port", "additionalProperties"]);
+
+    let extra = &section.fields[2];
+    assert_eq!(extra.path, vec!["server"]);
+    assert_eq!(extra.pointer, "/server/additionalProperties");
+    let FieldKind::KeyValue(map) = &extra.kind else {
+        panic!("expected a key/value field, got {:?}", extra.kind);
+    };
+    assert!(map.inline);
+    assert_eq!(map.reserved_keys, vec!["host", "port"]);
+    assert_eq!(*map.value_kind, FieldKind::String);
+}
+
+#[test]
+fn extra_entries_merge_into_the_object() {
+    let mut state = state_for(&schema());
+    assert_eq!(
+        state.try_build_value().unwrap(),
+        json!({"server": {"host": "localhost"}})
+    );
+
+    state.seed_from_value(&json!({
+        "server": {"host": "example.com", "port": 8080, "region": "eu", "zone": "a"}
+    }));
+    let extra = state
+        .field_by_pointer("/server/additionalProperties")
+        .unwrap();
+    assert_eq!(
+        extra
+            .composite_list_panel()
+            .map(|(entries, _)| entries.len()),
+        Some(2),
+        "declared properties are not seeded as extra entries"
+    );
+    assert_eq!(
+        state.try_build_value().unwrap(),
+        json!({"server": {"host": "example.com", "port": 8080, "region": "eu", "zone": "a"}})
+    );
+}
+
+#[test]
+fn declared_names_are_rejected_as_keys() {
+    let mut state = state_for(&schema());
+    state.seed_from_value(&json!({"server": {"region": "eu"}}));
+    let extra = state
+        .field_mut_by_pointer("/server/additionalProperties")
+        .unwrap();
+    let mut context = extra.open_key_value_editor().unwrap();
+    context
+        .session
+        .form_state
+        .field_mut_by_pointer("/key")
+        .unwrap()
+        .seed_value(&json!("host"));
+
+    let err = extra
+        .close_key_value_editor(context.entry_index, &context.session, true)
+        .unwrap_err();
+    assert_eq!(err.pointer, "/server/host");
+    assert_eq!(err.message, "key 'host' is a declared property");
+    assert_eq!(
+        state.try_build_value().unwrap()["server"]["region"],
+        json!("eu")
+    );
+}
+
+#[test]
+fn root_maps_keep_working() {
+    let mut state = state_for(&json!({
+        "type": "object",
+        "additionalProperties": {"type": "integer"}
+    }));
+    state.seed_from_value(&json!({"a": 1, "b": 2}));
+    assert_eq!(state.try_build_value().unwrap(), json!({"a": 1, "b": 2}));
+}
+
+#[test]
+fn object_errors_stay_global() {
+    let schema = json!({
+        "type": "object",
+        "required": ["port"],
+        "properties": {
+            "port": {"type": "integer"}
+        },
+        "additionalProperties": {"type": "string"}
+    });
+    let mut state = state_for(&schema);
+    let validator = jsonschema::validator_for(&schema).unwrap();
+    let ValidationOutcome::Invalid {
+        issues,
+        global_errors,
+    } = validate_form(&mut state, &validator)
+    else {
+        panic!("expected a missing `port`");
+    };
+    assert_eq!(issues, 1);
+    assert_eq!(global_errors.len(), 1);
+    assert!(
+        global_errors[0].starts_with("<root>: "),
+        "{global_errors:?}"
+    );
+    let extra = state.field_by_pointer("/additionalProperties").unwrap();
+    assert_eq!(extra.schema.title, "Extra entries");
+    assert_eq!(extra.error, None);
+}
+
+#[test]
+fn extra_entry_pointers_lead_to_the_map() {
+    let mut state = state_for(&schema());
+    state.seed_from_value(&json!({"server": {"region": "eu"}}));
+    let owner = |state: &FormState, pointer: &str| {
+        state
+            .field_for_pointer(pointer)
+            .map(|field| field.schema.pointer.clone())
+    };
+    assert_eq!(
+        owner(&state, "/server/region").as_deref(),
+        Some("/server/additionalProperties")
+    );
+    assert_eq!(
+        owner(&state, "/server/port").as_deref(),
+        Some("/server/port")
+    );
+    assert_eq!(owner(&state, "/server"), None, "the object has no field");
+    assert!(state.focus_pointer("/server/region"));
+    assert_eq!(state.focused_field().unwrap().schema.title, "Extra entries");
+}
@@ -0,0 +1,95 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use serde_json::{Value, json};
+
+use crate::{
+    domain::{FieldKind, StringFormat},
+    form::FieldState,
+    schema::layout::build_form_schema,
+};
+
+use super::state_for;
+
+fn press(field: &mut FieldState, code: KeyCode, modifiers: KeyModifiers) -> bool {
+    field.handle_key(&KeyEvent::new(code, modifiers))
+}
+
+fn schema() -> Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "released": {"type": "string", "format": "date", "default": "2024-01-31"},
+            "host": {"type": "string", "format": "ipv4", "default": "10.0.0.255"},
+            "id": {"type": "string", "format": "uuid"},
+            "contact": {"type": "string", "format": "email"},
+            "slug": {"type": "string", "format": "hostname"}
+        }
+    })
+}
+
+#[test]
+fn known_formats_are_recorded_on_the_kind() {
+    let form = build_form_schema(&schema()).unwrap();
+    let kind_of = |name: &str| {
+        form.roots[0].sections[0]
+            .fields
+            .iter()
+            .find(|field| field.name == name)
+            .map(|field| field.kind.clone())
+            .unwrap()
+    };
+    assert_eq!(
+        kind_of("released"),
+        FieldKind::Formatted(StringFormat::Date)
+    );
+    assert_eq!(kind_of("host"), FieldKind::Formatted(StringFormat::Ipv4));
+    assert_eq!(kind_of("slug"), FieldKind::String);
+}
+
+#[test]
+fn date_segments_step_and_clamp_to_month_length() {
+    let mut state = state_for(&schema());
+    let released = state.field_mut_by_pointer("/released").unwrap();
+    assert!(!press(released, KeyCode::Right, KeyModifiers::NONE));
+    assert!(press(released, KeyCode::Up, KeyModifiers::SHIFT));
+    assert_eq!(released.display_value(), "2024-02-29");
+    press(released, KeyCode::Left, KeyModifiers::NONE);
+    assert!(press(released, KeyCode::Down, KeyModifiers::SHIFT));
+    assert_eq!(released.display_value(), "2023-02-28");
+}
+
+#[test]
+fn ip_octets_wrap_and_typing_is_checked_live() {
+    let mut state = state_for(&schema());
+    let host = state.field_mut_by_pointer("/host").unwrap();
+    for _ in 0..3 {
+        press(host, KeyCode::Right, KeyModifiers::NONE);
+    }
+    assert!(press(host, KeyCode::Up, KeyModifiers::SHIFT));
+    assert_eq!(host.display_value(), "10.0.0.0");
+
+    press(host, KeyCode::Char('x'), KeyModifiers::NONE);
+    assert!(host.error.as_deref().unwrap().contains("IPv4"));
+    assert!(host.current_value().is_err());
+}
+
+#[test]
+fn uuid_shortcut_generates_a_valid_value() {
+    let mut state = state_for(&schema());
+    let id = state.field_mut_by_pointer("/id").unwrap();
+    assert!(press(id, KeyCode::Char('g'), KeyModifiers::CONTROL));
+    assert!(id.error.is_none());
+    let value = id.current_value().unwrap().unwrap();
+    let text = value.as_str().unwrap();
+    assert_eq!(text.len(), 36);
+    assert_eq!(&text[14..15], "4");
+
+    let contact = state.field_mut_by_pointer("/contact").unwrap();
+    for ch in "ops@".chars() {
+        press(contact, KeyCode::Char(ch), KeyModifiers::NONE);
+    }
+    assert!(contact.error.is_some());
+    for ch in "example.com".chars() {
+        press(contact, KeyCode::Char(ch), KeyModifiers::NONE);
+    }
+    assert!(contact.error.is_none());
+}
@@ -0,0 +1,108 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use serde_json::{Value, json};
+
+use crate::form::{FormCommand, FormState, apply_command};
+
+use super::state_for;
+
+fn state() -> FormState {
+    let schema = json!({
+        "type": "object",
+        "properties": {
+            "name": {"type": "string"},
+            "tags": {"type": "array", "items": {"type": "string"}, "default": ["a", "b"]}
+        }
+    });
+    state_for(&schema)
+}
+
+fn type_text(state: &mut FormState, text: &str) {
+    for ch in text.chars() {
+        let before = state.checkpoint();
+        let field = state.field_mut_by_pointer("/name").unwrap();
+        field.handle_key(&KeyEvent::new(KeyCode::Char(ch), KeyModifiers::NONE));
+        state.record_change(before, Some("/name"));
+    }
+}
+
+fn value(state: &FormState) -> Value {
+    state.try_build_value().unwrap()
+}
+
+#[test]
+fn typing_in_one_field_is_one_step() {
+    let mut state = state();
+    type_text(&mut state, "api");
+    apply_command(&mut state, FormCommand::FocusNextField);
+    apply_command(&mut state, FormCommand::FocusPrevField);
+    type_text(&mut state, "-v2");
+    assert_eq!(value(&state)["name"], json!("api-v2"));
+
+    assert!(state.undo());
+    assert_eq!(
+        value(&state)["name"],
+        json!("api"),
+        "leaving the field closed the step"
+    );
+    assert!(state.undo());
+    assert_eq!(value(&state).get("name"), None);
+    assert!(!state.can_undo());
+
+    assert!(state.redo());
+    assert!(state.redo());
+    assert_eq!(value(&state)["name"], json!("api-v2"));
+    assert!(!state.redo());
+}
+
+#[test]
+fn list_removal_is_undone_and_new_changes_drop_redo() {
+    let mut state = state();
+    let before = state.checkpoint();
+    let tags = state.field_mut_by_pointer("/tags").unwrap();
+    assert!(tags.composite_list_remove_entry());
+    state.record_change(before, None);
+    assert_eq!(value(&state)["tags"], json!(["b"]));
+
+    assert!(state.undo());
+    assert_eq!(value(&state)["tags"], json!(["a", "b"]));
+    assert!(state.can_redo());
+    type_text(&mut state, "x");
+    assert!(!state.can_redo(), "a new change forgets undone ones");
+    assert_eq!(value(&state)["tags"], json!(["a", "b"]));
+}
+
+#[test]
+fn field_steps_restore_the_field_and_stored_sub_forms_forget_theirs() {
+    let schema = json!({
+        "type": "object",
+        "properties": {
+            "endpoint": {
+                "type": "array",
+                "prefixItems": [{"type": "string"}, {"type": "integer"}]
+            }
+        }
+    });
+    let mut state = state_for(&schema);
+    let field = state.focused_field_mut().expect("tuple field");
+    let mut session = field.open_composite_editor(0).expect("session");
+    let form = &mut session.form_state;
+    for ch in "db".chars() {
+        let before = form.field_checkpoint().expect("focused position");
+        let position = form.focused_field_mut().unwrap();
+        assert!(position.handle_key(&KeyEvent::new(KeyCode::Char(ch), KeyModifiers::NONE)));
+        form.record_field_change(before);
+    }
+    assert_eq!(form.try_build_value().unwrap()["0"], json!("db"));
+    assert!(form.undo());
+    assert_eq!(form.try_build_value().unwrap().get("0"), None);
+    assert!(form.redo());
+    assert!(!form.can_redo());
+
+    let field = state.focused_field_mut().expect("tuple field");
+    field.close_composite_editor(session, true);
+    let session = field.open_composite_editor(0).expect("session");
+    assert!(
+        !session.form_state.can_undo(),
+        "the stored sub-form is not kept in host snapshots with its steps"
+    );
+}
@@ -0,0 +1,91 @@
+use serde_json::{Value, json};
+
+use crate::{domain::ItemBounds, schema::layout::build_form_schema};
+
+use super::state_for;
+
+fn schema() -> Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "tags": {
+                "type": "array",
+                "items": {"type": "string"},
+                "minItems": 1,
+                "maxItems": 2,
+                "uniqueItems": true
+            },
+            "hosts": {
+                "type": "array",
+                "items": {
+                    "type": "object",
+                    "properties": {"name": {"type": "string"}}
+                },
+                "maxItems": 1,
+                "uniqueItems": true
+            }
+        }
+    })
+}
+
+#[test]
+fn layout_reads_item_bounds() {
+    let form = build_form_schema(&schema()).unwrap();
+    let tags = form.roots[0].sections[0]
+        .fields
+        .iter()
+        .find(|field| field.name == "tags")
+        .unwrap();
+    assert_eq!(
+        tags.item_bounds,
+        ItemBounds {
+            min: Some(1),
+            max: Some(2),
+            unique: true
+        }
+    );
+}
+
+#[test]
+fn scalar_lists_stop_at_max_and_min_items() {
+    let mut state = state_for(&schema());
+    let tags = state.field_mut_by_pointer("/tags").unwrap();
+    assert!(tags.composite_list_remove_blocked().is_none());
+    assert!(tags.composite_list_add_entry());
+    assert!(tags.composite_list_add_entry());
+    assert_eq!(
+        tags.composite_list_add_blocked().as_deref(),
+        Some("maxItems reached: at most 2 entries")
+    );
+    assert!(!tags.composite_list_add_entry());
+
+    assert!(tags.composite_list_remove_entry());
+    assert_eq!(
+        tags.composite_list_remove_blocked().as_deref(),
+        Some("minItems reached: at least 1 entries")
+    );
+    assert!(!tags.composite_list_remove_entry());
+    assert_eq!(state.try_build_value().unwrap()["tags"], json!([""]));
+}
+
+#[test]
+fn composite_lists_stop_at_max_items() {
+    let mut state = state_for(&schema());
+    let hosts = state.field_mut_by_pointer("/hosts").unwrap();
+    assert!(hosts.composite_list_add_entry());
+    assert!(hosts.composite_list_add_blocked().is_some());
+    assert!(!hosts.composite_list_add_entry());
+    assert!(hosts.composite_list_remove_entry(), "no minItems");
+}
+
+#[test]
+fn unique_lists_report_duplicates() {
+    let mut state = state_for(&schema());
+    state.seed_from_value(&json!({"tags": ["a", "a"]}));
+    let tags = state.field_by_pointer("/tags").unwrap();
+    assert_eq!(tags.composite_list_duplicates(), vec![0, 1]);
+
+    state.seed_from_value(&json!({"tags": ["a", "b"]}));
+    let tags = state.field_by_pointer("/tags").unwrap();
+    assert!(tags.composite_list_duplicates().is_empty());
+}
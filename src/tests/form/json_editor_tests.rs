@@ -0,0 +1,95 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use serde_json::{Value, json};
+
+use crate::form::{FormState, field::components::ComponentKind};
+
+use super::state_for;
+
+fn schema() -> Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "settings": {
+                "type": "object",
+                "default": {"retries": 3, "tags": ["a"]}
+            }
+        }
+    })
+}
+
+fn type_text(state: &mut FormState, text: &str) {
+    let field = state.field_mut_by_pointer("/settings").unwrap();
+    for ch in text.chars() {
+        field.handle_key(&KeyEvent::new(KeyCode::Char(ch), KeyModifiers::NONE));
+    }
+}
+
+fn clear(state: &mut FormState) {
+    let field = state.field_mut_by_pointer("/settings").unwrap();
+    while !field.display_value().is_empty() {
+        field.handle_key(&KeyEvent::new(KeyCode::Backspace, KeyModifiers::NONE));
+    }
+}
+
+#[test]
+fn free_form_objects_are_edited_as_pretty_json() {
+    let state = state_for(&schema());
+    let field = state.field_by_pointer("/settings").unwrap();
+    assert_eq!(field.component_kind(), ComponentKind::Json);
+    assert_eq!(
+        field.display_value(),
+        "{\n  \"retries\": 3,\n  \"tags\": [\n    \"a\"\n  ]\n}"
+    );
+    assert_eq!(
+        state.try_build_value().unwrap()["settings"],
+        json!({"retries": 3, "tags": ["a"]})
+    );
+}
+
+#[test]
+fn edits_produce_objects_not_strings() {
+    let mut state = state_for(&schema());
+    clear(&mut state);
+    type_text(&mut state, r#"{"mode": "fast"}"#);
+    assert_eq!(
+        state.try_build_value().unwrap()["settings"],
+        json!({"mode": "fast"})
+    );
+
+    state.seed_from_value(&json!({"settings": {"nested": {"on": true}}}));
+    assert_eq!(
+        state.field_by_pointer("/settings").unwrap().display_value(),
+        "{\n  \"nested\": {\n    \"on\": true\n  }\n}"
+    );
+}
+
+#[test]
+fn syntax_errors_report_line_and_column() {
+    let mut state = state_for(&schema());
+    clear(&mut state);
+    type_text(&mut state, "{");
+    let field = state.field_mut_by_pointer("/settings").unwrap();
+    field.handle_key(&KeyEvent::new(KeyCode::Enter, KeyModifiers::ALT));
+    type_text(&mut state, "\"a\" 1}");
+
+    let field = state.field_by_pointer("/settings").unwrap();
+    assert_eq!(field.display_value(), "{\n  \"a\" 1}");
+    let message = "invalid JSON at line 2, column 7: expected `:`";
+    assert_eq!(field.error.as_deref(), Some(message));
+    let err = state.try_build_value().unwrap_err();
+    assert_eq!(err.pointer, "/settings");
+    assert_eq!(err.message, message);
+}
+
+#[test]
+fn ctrl_f_reformats_the_buffer() {
+    let mut state = state_for(&schema());
+    clear(&mut state);
+    type_text(&mut state, r#"{"a":[1,2]}"#);
+    let field = state.field_mut_by_pointer("/settings").unwrap();
+    assert!(field.handle_key(&KeyEvent::new(KeyCode::Char('f'), KeyModifiers::CONTROL)));
+    assert_eq!(
+        field.display_value(),
+        "{\n  \"a\": [\n    1,\n    2\n  ]\n}"
+    );
+}
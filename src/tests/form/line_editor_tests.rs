@@ -0,0 +1,117 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+use crate::form::field::components::LineEditor;
+
+fn press(editor: &mut LineEditor, code: KeyCode, modifiers: KeyModifiers) -> bool {
+    editor.handle_key(&KeyEvent::new(code, modifiers))
+}
+
+fn type_text(editor: &mut LineEditor, text: &str) {
+    for ch in text.chars() {
+        press(editor, KeyCode::Char(ch), KeyModifiers::NONE);
+    }
+}
+
+#[test]
+fn edits_happen_at_the_caret() {
+    let mut editor = LineEditor::new("https://exmple.com");
+    for _ in 0.."mple.com".len() {
+        press(&mut editor, KeyCode::Left, KeyModifiers::NONE);
+    }
+    type_text(&mut editor, "a");
+    assert_eq!(editor.text(), "https://example.com");
+    assert_eq!(editor.cursor().offset, "https://exa".len());
+
+    press(&mut editor, KeyCode::Home, KeyModifiers::NONE);
+    assert!(press(&mut editor, KeyCode::Delete, KeyModifiers::NONE));
+    assert_eq!(editor.text(), "ttps://example.com");
+    press(&mut editor, KeyCode::End, KeyModifiers::NONE);
+    assert!(!press(&mut editor, KeyCode::Delete, KeyModifiers::NONE));
+    assert_eq!(editor.cursor().offset, editor.text().len());
+}
+
+#[test]
+fn graphemes_move_and_delete_as_one_unit() {
+    // "e" + combining acute, a family emoji sequence and a wide CJK character.
+    let family = "\u{1F468}\u{200D}\u{1F469}\u{200D}\u{1F467}";
+    let mut editor = LineEditor::new(format!("e\u{301}{family}漢"));
+    assert!(press(&mut editor, KeyCode::Backspace, KeyModifiers::NONE));
+    assert_eq!(editor.text(), format!("e\u{301}{family}"));
+    press(&mut editor, KeyCode::Left, KeyModifiers::NONE);
+    assert_eq!(editor.cursor().offset, "e\u{301}".len());
+    press(&mut editor, KeyCode::Left, KeyModifiers::NONE);
+    assert_eq!(editor.cursor().offset, 0);
+    assert!(press(&mut editor, KeyCode::Delete, KeyModifiers::NONE));
+    assert_eq!(editor.text(), family);
+}
+
+#[test]
+fn word_motions_and_kills() {
+    let mut editor = LineEditor::new("retry_count = 42");
+    press(&mut editor, KeyCode::Left, KeyModifiers::CONTROL);
+    assert_eq!(editor.cursor().offset, "retry_count = ".len());
+    press(&mut editor, KeyCode::Left, KeyModifiers::CONTROL);
+    assert_eq!(editor.cursor().offset, 0, "underscores belong to the word");
+    press(&mut editor, KeyCode::Right, KeyModifiers::ALT);
+    assert_eq!(editor.cursor().offset, "retry_count".len());
+
+    press(&mut editor, KeyCode::End, KeyModifiers::NONE);
+    assert!(press(
+        &mut editor,
+        KeyCode::Char('w'),
+        KeyModifiers::CONTROL
+    ));
+    assert_eq!(editor.text(), "retry_count = ");
+    assert!(press(
+        &mut editor,
+        KeyCode::Char('w'),
+        KeyModifiers::CONTROL
+    ));
+    assert_eq!(editor.text(), "");
+
+    let mut editor = LineEditor::new("first\nsecond line");
+    assert!(press(
+        &mut editor,
+        KeyCode::Char('u'),
+        KeyModifiers::CONTROL
+    ));
+    assert_eq!(editor.text(), "first\n", "Ctrl+U stops at the line start");
+}
+
+#[test]
+fn overwrite_mode_replaces_up_to_the_line_end() {
+    let mut editor = LineEditor::new("8080");
+    press(&mut editor, KeyCode::Home, KeyModifiers::NONE);
+    press(&mut editor, KeyCode::Insert, KeyModifiers::NONE);
+    assert!(editor.cursor().overwrite);
+    type_text(&mut editor, "90");
+    assert_eq!(editor.text(), "9080");
+    press(&mut editor, KeyCode::End, KeyModifiers::NONE);
+    type_text(&mut editor, "1");
+    assert_eq!(editor.text(), "90801");
+}
+
+#[test]
+fn typing_replaces_the_selection() {
+    let mut editor = LineEditor::new("host: localhost");
+    press(
+        &mut editor,
+        KeyCode::Left,
+        KeyModifiers::CONTROL | KeyModifiers::SHIFT,
+    );
+    assert_eq!(
+        editor.selection(),
+        Some("host: ".len().."host: localhost".len())
+    );
+    type_text(&mut editor, "db");
+    assert_eq!(editor.text(), "host: db");
+    assert_eq!(editor.selection(), None);
+
+    press(&mut editor, KeyCode::Home, KeyModifiers::SHIFT);
+    press(&mut editor, KeyCode::Right, KeyModifiers::NONE);
+    assert_eq!(editor.selection(), None, "an arrow collapses the selection");
+    assert_eq!(editor.cursor().offset, "host: db".len());
+    press(&mut editor, KeyCode::Home, KeyModifiers::SHIFT);
+    assert!(press(&mut editor, KeyCode::Backspace, KeyModifiers::NONE));
+    assert_eq!(editor.text(), "");
+}
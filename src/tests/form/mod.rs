@@ -1,4 +1,29 @@
+mod access_tests;
 mod component_collection_tests;
 mod component_text_tests;
+mod conditional_tests;
+mod const_tests;
+mod dependency_tests;
+mod enum_tests;
+mod extra_entries_tests;
+mod format_tests;
+mod history_tests;
+mod item_bounds_tests;
+mod json_editor_tests;
 mod key_value_tests;
+mod line_editor_tests;
+mod multi_type_tests;
+mod nested_collection_tests;
+mod pattern_routes_tests;
 mod state_tests;
+mod textarea_tests;
+mod tuple_tests;
+mod ui_tests;
+
+use serde_json::Value;
+
+use crate::{form::FormState, schema::layout::build_form_schema};
+
+pub(super) fn state_for(schema: &Value) -> FormState {
+    FormState::from_schema(&build_form_schema(schema).expect("schema parsed"))
+}
@@ -0,0 +1,91 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use serde_json::{Value, json};
+
+use crate::{domain::FieldKind, schema::layout::build_form_schema};
+
+use super::state_for;
+
+fn schema() -> Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "label": {"type": ["string", "null"], "default": "primary"},
+            "port": {"type": ["integer", "string"], "default": 8080},
+            "extra": {
+                "type": ["object", "string"],
+                "properties": {"key": {"type": "string"}}
+            }
+        }
+    })
+}
+
+#[test]
+fn type_lists_become_nullable_or_multi_type_fields() {
+    let form = build_form_schema(&schema()).unwrap();
+    assert_eq!(form.roots.len(), 1, "multi-type objects are not sections");
+    let fields = &form.roots[0].sections[0].fields;
+    let kind_of = |name: &str| {
+        let field = fields.iter().find(|field| field.name == name).unwrap();
+        (field.kind.clone(), field.nullable)
+    };
+    assert_eq!(kind_of("label"), (FieldKind::String, true));
+    assert_eq!(
+        kind_of("port"),
+        (
+            FieldKind::MultiType(vec![FieldKind::Integer, FieldKind::String]),
+            false
+        )
+    );
+    assert_eq!(
+        kind_of("extra"),
+        (
+            FieldKind::MultiType(vec![FieldKind::Json, FieldKind::String]),
+            false
+        )
+    );
+}
+
+#[test]
+fn nullable_fields_toggle_to_null_and_back() {
+    let mut state = state_for(&schema());
+    let label = state.field_mut_by_pointer("/label").unwrap();
+    assert!(label.toggle_null());
+    assert!(label.is_null());
+    assert_eq!(label.display_value(), "null");
+    assert_eq!(state.try_build_value().unwrap()["label"], Value::Null);
+
+    let label = state.field_mut_by_pointer("/label").unwrap();
+    label.handle_key(&KeyEvent::new(KeyCode::Char('!'), KeyModifiers::NONE));
+    assert!(!label.is_null(), "editing restores the value");
+    assert_eq!(state.try_build_value().unwrap()["label"], json!("primary!"));
+
+    let port = state.field_mut_by_pointer("/port").unwrap();
+    assert!(!port.toggle_null(), "port does not accept null");
+}
+
+#[test]
+fn seeding_null_marks_nullable_fields() {
+    let mut state = state_for(&schema());
+    state.seed_from_value(&json!({"label": null, "port": "auto"}));
+    assert!(state.field_by_pointer("/label").unwrap().is_null());
+    let value = state.try_build_value().unwrap();
+    assert_eq!(value["label"], Value::Null);
+    assert_eq!(value["port"], json!("auto"));
+}
+
+#[test]
+fn switching_types_coerces_the_value() {
+    let mut state = state_for(&schema());
+    assert_eq!(state.try_build_value().unwrap()["port"], json!(8080));
+
+    let port = state.field_mut_by_pointer("/port").unwrap();
+    let popup = port.composite_popup().expect("type chooser");
+    assert_eq!(popup.options, vec!["integer", "string"]);
+    port.apply_composite_selection(1, None);
+    assert!(port.dirty);
+    assert_eq!(state.try_build_value().unwrap()["port"], json!("8080"));
+
+    let port = state.field_mut_by_pointer("/port").unwrap();
+    port.apply_composite_selection(0, None);
+    assert_eq!(state.try_build_value().unwrap()["port"], json!(8080));
+}
@@ -0,0 +1,99 @@
+use serde_json::{Value, json};
+
+use crate::{domain::FieldKind, schema::layout::build_form_schema};
+
+use super::state_for;
+
+fn settings_schema() -> Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "matrix": {
+                "type": "array",
+                "items": {"type": "array", "items": {"type": "string"}}
+            },
+            "labels": {
+                "type": "array",
+                "items": {
+                    "type": "object",
+                    "additionalProperties": {"$ref": "#/definitions/label"}
+                }
+            }
+        },
+        "definitions": {
+            "label": {"type": "string", "minLength": 1}
+        }
+    })
+}
+
+#[test]
+fn nested_arrays_and_arrays_of_maps_are_detected() {
+    let form = build_form_schema(&settings_schema()).expect("schema parsed");
+    let fields = &form.roots[0].sections[0].fields;
+    assert_eq!(fields[0].name, "labels");
+    assert!(matches!(
+        &fields[0].kind,
+        FieldKind::Array(inner) if matches!(inner.as_ref(), FieldKind::KeyValue(_))
+    ));
+    assert_eq!(fields[1].name, "matrix");
+    assert_eq!(
+        fields[1].kind,
+        FieldKind::Array(Box::new(FieldKind::Array(Box::new(FieldKind::String))))
+    );
+}
+
+#[test]
+fn nested_entries_round_trip() {
+    let mut state = state_for(&settings_schema());
+    let value = json!({
+        "labels": [{"team": "core"}, {}],
+        "matrix": [["a", "b"], ["c"]]
+    });
+    state.seed_from_value(&value);
+    assert_eq!(state.try_build_value().unwrap(), value);
+}
+
+#[test]
+fn inner_collection_edits_flow_back_to_outer_entry() {
+    let mut state = state_for(&settings_schema());
+    state.seed_from_value(&json!({"matrix": [["a", "b"], ["c"]]}));
+
+    let matrix = state.field_mut_by_pointer("/matrix").expect("matrix field");
+    let mut outer = matrix.open_scalar_array_editor().expect("outer entry");
+    assert_eq!(outer.session.schema["properties"]["value"]["type"], "array");
+
+    let row = outer
+        .session
+        .form_state
+        .field_mut_by_pointer("/value")
+        .expect("row field");
+    assert!(row.is_composite_list());
+    assert!(row.composite_list_add_entry());
+    let mut inner = row.open_scalar_array_editor().expect("inner entry");
+    inner
+        .session
+        .form_state
+        .seed_from_value(&json!({"value": "z"}));
+    row.close_scalar_array_editor(inner.entry_index, &inner.session, true)
+        .expect("inner applied");
+
+    let matrix = state.field_mut_by_pointer("/matrix").expect("matrix field");
+    matrix
+        .close_scalar_array_editor(outer.entry_index, &outer.session, true)
+        .expect("outer applied");
+    assert_eq!(
+        state.try_build_value().unwrap(),
+        json!({"matrix": [["a", "b", "z"], ["c"]]})
+    );
+}
+
+#[test]
+fn map_entry_schema_keeps_definitions_resolvable() {
+    let mut state = state_for(&settings_schema());
+    state.seed_from_value(&json!({"labels": [{"team": "core"}]}));
+    let labels = state.field_mut_by_pointer("/labels").expect("labels field");
+    let context = labels.open_scalar_array_editor().expect("entry");
+    let validator = jsonschema::validator_for(&context.session.schema).expect("compiles");
+    assert!(validator.is_valid(&json!({"value": {"team": "core"}})));
+    assert!(!validator.is_valid(&json!({"value": {"team": ""}})));
+}
@@ -0,0 +1,120 @@
+use serde_json::{Value, json};
+
+use crate::{domain::FieldKind, form::FormState, schema::layout::build_form_schema};
+
+use super::state_for;
+
+fn schema() -> Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "labels": {
+                "type": "object",
+                "patternProperties": {
+                    "^x-": {"type": "string"},
+                    "^[a-z]+$": {"type": "integer"}
+                },
+                "additionalProperties": false
+            }
+        }
+    })
+}
+
+#[test]
+fn every_pattern_becomes_a_route() {
+    let form = build_form_schema(&schema()).unwrap();
+    assert!(form.diagnostics.is_empty(), "{:?}", form.diagnostics);
+    let field = &form.roots[0].sections[0].fields[0];
+    let FieldKind::KeyValue(map) = &field.kind else {
+        panic!("expected a key/value field, got {:?}", field.kind);
+    };
+    let routes: Vec<_> = map
+        .routes
+        .iter()
+        .map(|route| (route.pattern.as_str(), route.value_kind.as_ref().clone()))
+        .collect();
+    assert_eq!(
+        routes,
+        vec![("^[a-z]+$", FieldKind::Integer), ("^x-", FieldKind::String)]
+    );
+    assert_eq!(map.key_schema["pattern"], json!("(?:^[a-z]+$)|(?:^x-)"));
+    assert_eq!(map.route_for("replicas"), Some(0));
+    assert_eq!(map.route_for("x-team"), Some(1));
+    assert_eq!(map.route_for("Nope"), None);
+}
+
+#[test]
+fn changing_the_key_switches_the_value_editor() {
+    let mut state = state_for(&schema());
+    state.seed_from_value(&json!({"labels": {"replicas": 3, "x-team": "core"}}));
+    let labels = state.field_mut_by_pointer("/labels").unwrap();
+    let mut context = labels.open_key_value_editor().unwrap();
+    let session = &mut context.session;
+    let value_kind = |state: &FormState| {
+        state
+            .field_by_pointer("/value")
+            .unwrap()
+            .schema
+            .kind
+            .clone()
+    };
+    assert_eq!(value_kind(&session.form_state), FieldKind::Integer);
+    assert!(!session.sync_route(), "the key still matches its pattern");
+
+    session
+        .form_state
+        .field_mut_by_pointer("/key")
+        .unwrap()
+        .seed_value(&json!("x-replicas"));
+    assert!(session.sync_route());
+    assert_eq!(value_kind(&session.form_state), FieldKind::String);
+    assert_eq!(
+        session.schema["properties"]["value"]["type"],
+        json!("string")
+    );
+    let value = session.form_state.field_by_pointer("/value").unwrap();
+    assert_eq!(
+        value.schema.description.as_deref(),
+        Some("Value for keys matching `^x-`")
+    );
+
+    labels
+        .close_key_value_editor(context.entry_index, &context.session, true)
+        .unwrap();
+    assert_eq!(
+        state.try_build_value().unwrap()["labels"],
+        json!({"x-replicas": "3", "x-team": "core"})
+    );
+}
+
+#[test]
+fn uncompilable_patterns_are_reported() {
+    let form = build_form_schema(&json!({
+        "type": "object",
+        "properties": {
+            "labels": {
+                "type": "object",
+                "patternProperties": {
+                    "^(?!x-)[a-z]+$": {"type": "integer"},
+                    "^x-": {"type": "string"}
+                }
+            }
+        }
+    }))
+    .unwrap();
+    assert_eq!(form.diagnostics.len(), 1, "{:?}", form.diagnostics);
+    assert_eq!(form.diagnostics[0].pointer, "/labels");
+    assert!(
+        form.diagnostics[0]
+            .message
+            .contains("pattern `^(?!x-)[a-z]+$` cannot be compiled"),
+        "{}",
+        form.diagnostics[0].message
+    );
+    let FieldKind::KeyValue(map) = &form.roots[0].sections[0].fields[0].kind else {
+        panic!("expected a key/value field");
+    };
+    assert_eq!(map.routes.len(), 1);
+    assert_eq!(map.route_for("x-team"), Some(0));
+    assert_eq!(map.route_for("replicas"), None);
+}
@@ -14,7 +14,14 @@ fn mk_field(name: &str) -> FieldState {
         kind: FieldKind::String,
         required: false,
         default: None,
+        read_only: false,
+        write_only: false,
+        nullable: false,
+        item_bounds: Default::default(),
+        ui: Default::default(),
         metadata: Default::default(),
+        visible_when: Vec::new(),
+        required_when: Vec::new(),
     })
 }
 
@@ -26,6 +33,7 @@ fn mk_section(id: &str, fields: &[&str]) -> SectionState {
         path: vec![id.to_string()],
         depth: 0,
         fields: fields.iter().map(|name| mk_field(name)).collect(),
+        hidden_fields: Vec::new(),
         scroll_offset: 0,
     }
 }
@@ -49,6 +57,8 @@ fn field_navigation_wraps_across_roots() {
         root_index: 0,
         section_index: 0,
         field_index: 1,
+        show_advanced: false,
+        history: Default::default(),
     };
     state.focus_next_field();
     assert_eq!(state.root_index, 1);
@@ -82,6 +92,8 @@ fn field_navigation_wraps_backwards_across_roots() {
         root_index: 0,
         section_index: 0,
         field_index: 0,
+        show_advanced: false,
+        history: Default::default(),
     };
     state.focus_prev_field();
     assert_eq!(state.root_index, 1);
@@ -111,6 +123,8 @@ fn section_navigation_cycles_ordered_tree() {
         root_index: 0,
         section_index: 0,
         field_index: 0,
+        show_advanced: false,
+        history: Default::default(),
     };
     state.focus_next_section(1);
     assert_eq!(state.root_index, 0);
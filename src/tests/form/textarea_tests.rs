@@ -0,0 +1,63 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use serde_json::json;
+
+use crate::form::{FormState, field::components::ComponentKind};
+
+use super::state_for;
+
+fn press(state: &mut FormState, code: KeyCode) {
+    let field = state.field_mut_by_pointer("/script").unwrap();
+    field.handle_key(&KeyEvent::new(code, KeyModifiers::NONE));
+}
+
+#[test]
+fn long_strings_pick_the_textarea() {
+    let state = state_for(&json!({
+        "type": "object",
+        "properties": {
+            "widget": {"type": "string", "x-widget": "textarea"},
+            "pem": {"type": "string", "contentMediaType": "application/x-pem-file"},
+            "summary": {"type": "string", "maxLength": 2000},
+            "short": {"type": "string", "maxLength": 64},
+            "secret": {"type": "string", "x-widget": "password", "contentMediaType": "text/plain"}
+        }
+    }));
+    let kind = |pointer: &str| state.field_by_pointer(pointer).unwrap().component_kind();
+    assert_eq!(kind("/widget"), ComponentKind::Textarea);
+    assert_eq!(kind("/pem"), ComponentKind::Textarea);
+    assert_eq!(kind("/summary"), ComponentKind::Textarea);
+    assert_eq!(kind("/short"), ComponentKind::TextInput);
+    assert_eq!(
+        kind("/secret"),
+        ComponentKind::TextInput,
+        "an explicit x-widget wins"
+    );
+}
+
+#[test]
+fn enter_and_arrows_edit_across_lines() {
+    let mut state = state_for(&json!({
+        "type": "object",
+        "properties": {
+            "script": {"type": "string", "x-widget": "textarea", "default": "set -e\necho ok"}
+        }
+    }));
+    press(&mut state, KeyCode::Up);
+    press(&mut state, KeyCode::Enter);
+    for ch in "cd /srv".chars() {
+        press(&mut state, KeyCode::Char(ch));
+    }
+    assert_eq!(
+        state.try_build_value().unwrap()["script"],
+        json!("set -e\ncd /srv\necho ok")
+    );
+
+    press(&mut state, KeyCode::Down);
+    let field = state.field_by_pointer("/script").unwrap();
+    let offset = field.text_cursor().unwrap().offset;
+    assert_eq!(
+        offset,
+        "set -e\ncd /srv\necho ok".len(),
+        "the caret keeps its column"
+    );
+}
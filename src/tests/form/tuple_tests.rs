@@ -0,0 +1,114 @@
+use serde_json::{Value, json};
+
+use crate::{domain::FieldKind, schema::layout::build_form_schema};
+
+use super::state_for;
+
+fn endpoint_schema(tail: Value) -> Value {
+    let mut endpoint = json!({
+        "type": "array",
+        "title": "Endpoint",
+        "prefixItems": [
+            {"type": "string", "title": "Host"},
+            {"type": "integer", "minimum": 1}
+        ]
+    });
+    if let Value::Object(extra) = tail {
+        endpoint.as_object_mut().unwrap().extend(extra);
+    }
+    json!({
+        "type": "object",
+        "properties": {"endpoint": endpoint}
+    })
+}
+
+#[test]
+fn array_form_items_become_tuple() {
+    let schema = json!({
+        "type": "object",
+        "properties": {
+            "pair": {
+                "type": "array",
+                "items": [{"type": "string"}, {"type": "boolean", "title": "Enabled"}],
+                "additionalItems": false
+            }
+        }
+    });
+    let form = build_form_schema(&schema).expect("schema parsed");
+    let field = &form.roots[0].sections[0].fields[0];
+    let FieldKind::Tuple(tuple) = &field.kind else {
+        panic!("expected tuple, got {:?}", field.kind);
+    };
+    assert!(!tuple.allow_additional);
+    let items: Vec<_> = tuple
+        .items
+        .iter()
+        .map(|item| {
+            (
+                item.title.as_str(),
+                item.pointer.as_str(),
+                item.kind.clone(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        items,
+        vec![
+            ("Item 1", "/0", FieldKind::String),
+            ("Enabled", "/1", FieldKind::Boolean)
+        ]
+    );
+}
+
+#[test]
+fn prefix_items_respect_items_false() {
+    let closed = build_form_schema(&endpoint_schema(json!({"items": false}))).unwrap();
+    let open = build_form_schema(&endpoint_schema(json!({}))).unwrap();
+    for (form, expected) in [(closed, false), (open, true)] {
+        match &form.roots[0].sections[0].fields[0].kind {
+            FieldKind::Tuple(tuple) => assert_eq!(tuple.allow_additional, expected),
+            other => panic!("expected tuple, got {other:?}"),
+        }
+    }
+}
+
+#[test]
+fn tuple_builds_positional_array() {
+    let mut state = state_for(&endpoint_schema(json!({"items": false})));
+    state.seed_from_value(&json!({"endpoint": ["example.com", 8080, "dropped"]}));
+    assert_eq!(
+        state.try_build_value().unwrap(),
+        json!({"endpoint": ["example.com", 8080]})
+    );
+
+    let mut state = state_for(&endpoint_schema(json!({})));
+    state.seed_from_value(&json!({"endpoint": ["example.com", 8080, "kept"]}));
+    assert_eq!(
+        state.try_build_value().unwrap(),
+        json!({"endpoint": ["example.com", 8080, "kept"]})
+    );
+}
+
+#[test]
+fn tuple_editor_session_updates_positions() {
+    let mut state = state_for(&endpoint_schema(json!({"items": false})));
+    let field = state.focused_field_mut().expect("tuple field");
+    let mut session = field.open_composite_editor(0).expect("session");
+    assert_eq!(session.schema["properties"]["1"]["minimum"], json!(1.0));
+    session
+        .form_state
+        .seed_from_value(&json!({"0": "db.local", "1": 5432}));
+    field.close_composite_editor(session, true);
+    assert_eq!(
+        state.try_build_value().unwrap(),
+        json!({"endpoint": ["db.local", 5432]})
+    );
+
+    let field = state.focused_field_mut().expect("tuple field");
+    field.seed_value(&json!([]));
+    let mut session = field.open_composite_editor(0).expect("session");
+    session.form_state.seed_from_value(&json!({"1": 5432}));
+    field.close_composite_editor(session, true);
+    let err = state.try_build_value().expect_err("gap rejected");
+    assert_eq!(err.pointer, "/endpoint/0");
+}
@@ -0,0 +1,121 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use serde_json::{Value, json};
+
+use crate::{domain::Widget, form::FormState, schema::layout::build_form_schema};
+
+use super::state_for;
+
+fn schema() -> Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "name": {"type": "string", "x-order": 2, "x-placeholder": "my-service"},
+            "kind": {"type": "string", "const": "service", "x-hidden": true},
+            "port": {"type": "integer", "x-order": 1, "x-help": "Listening port"},
+            "workers": {
+                "type": "integer",
+                "minimum": 1,
+                "maximum": 16,
+                "default": 4,
+                "x-widget": "slider",
+                "x-advanced": true
+            },
+            "mode": {"enum": ["fast", "safe"], "x-widget": "radio"},
+            "token": {"type": "string", "x-widget": "password"}
+        }
+    })
+}
+
+fn visible_names(state: &FormState) -> Vec<String> {
+    state.roots[0].sections[0]
+        .fields
+        .iter()
+        .map(|field| field.schema.name.clone())
+        .collect()
+}
+
+#[test]
+fn vocabulary_is_parsed_and_fields_are_ordered() {
+    let form = build_form_schema(&schema()).unwrap();
+    let fields = &form.roots[0].sections[0].fields;
+    let names = fields
+        .iter()
+        .map(|field| field.name.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(names, ["port", "name", "kind", "mode", "token", "workers"]);
+    assert_eq!(fields[0].ui.help.as_deref(), Some("Listening port"));
+    assert_eq!(fields[1].ui.placeholder.as_deref(), Some("my-service"));
+    assert_eq!(
+        fields[5].ui.widget,
+        Some(Widget::Slider {
+            min: 1.0,
+            max: 16.0,
+            step: 1.0
+        })
+    );
+}
+
+#[test]
+fn hidden_and_advanced_fields_stay_in_the_output() {
+    let mut state = state_for(&schema());
+    assert_eq!(visible_names(&state), ["port", "name", "mode", "token"]);
+    assert_eq!(state.roots[0].sections[0].collapsed_advanced(), 1);
+
+    let value = state.try_build_value().unwrap();
+    assert_eq!(value["kind"], json!("service"));
+    assert_eq!(value["workers"], json!(4));
+
+    state.toggle_advanced();
+    assert_eq!(
+        visible_names(&state),
+        ["port", "name", "mode", "token", "workers"]
+    );
+}
+
+#[test]
+fn widgets_change_the_component() {
+    let mut state = state_for(&schema());
+    state.toggle_advanced();
+
+    let workers = state.field_mut_by_pointer("/workers").unwrap();
+    workers.handle_key(&KeyEvent::new(KeyCode::End, KeyModifiers::NONE));
+    assert!(workers.display_value().ends_with("] 16"));
+    workers.handle_key(&KeyEvent::new(KeyCode::Left, KeyModifiers::NONE));
+    assert_eq!(workers.current_value().unwrap(), Some(json!(15)));
+
+    let mode = state.field_mut_by_pointer("/mode").unwrap();
+    assert_eq!(mode.display_value(), "(•) fast  ( ) safe");
+
+    let token = state.field_mut_by_pointer("/token").unwrap();
+    assert!(token.is_masked());
+}
+
+#[test]
+fn slider_stays_inside_exclusive_bounds() {
+    let schema = json!({
+        "type": "object",
+        "properties": {
+            "ratio": {
+                "type": "number",
+                "exclusiveMinimum": 0,
+                "exclusiveMaximum": 1,
+                "multipleOf": 0.25,
+                "x-widget": "slider"
+            }
+        }
+    });
+    let mut state = state_for(&schema);
+    let ratio = state.field_mut_by_pointer("/ratio").unwrap();
+    assert_eq!(
+        ratio.schema.ui.widget,
+        Some(Widget::Slider {
+            min: 0.25,
+            max: 0.75,
+            step: 0.25
+        })
+    );
+    ratio.handle_key(&KeyEvent::new(KeyCode::End, KeyModifiers::NONE));
+    assert_eq!(ratio.current_value().unwrap(), Some(json!(0.75)));
+    ratio.handle_key(&KeyEvent::new(KeyCode::Home, KeyModifiers::NONE));
+    assert_eq!(ratio.current_value().unwrap(), Some(json!(0.25)));
+}
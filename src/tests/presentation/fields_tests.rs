@@ -1,8 +1,9 @@
 use crate::{
-    domain::{FieldKind, FieldSchema},
+    domain::{FieldKind, FieldSchema, Widget},
     form::FieldState,
-    presentation::components::fields::meta_lines,
+    presentation::components::fields::{info_line, meta_lines, value_panel_lines},
 };
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use ratatui::style::{Color, Modifier};
 
 fn make_field() -> FieldState {
@@ -16,7 +17,14 @@ fn make_field() -> FieldState {
         kind: FieldKind::String,
         required: false,
         default: None,
+        read_only: false,
+        write_only: false,
+        nullable: false,
+        item_bounds: Default::default(),
+        ui: Default::default(),
         metadata: Default::default(),
+        visible_when: Vec::new(),
+        required_when: Vec::new(),
     })
 }
 
@@ -43,3 +51,110 @@ fn meta_line_unselected_uses_gray() {
     assert_eq!(span.style.fg, Some(Color::DarkGray));
     assert!(!span.style.add_modifier.contains(Modifier::BOLD));
 }
+
+#[test]
+fn write_only_value_is_masked_until_revealed() {
+    let mut field = make_field();
+    field.schema.write_only = true;
+    field.seed_value(&"hunter2".into());
+
+    let text = |field: &FieldState| -> String {
+        value_panel_lines(field, false, 40)
+            .0
+            .iter()
+            .flat_map(|line| line.spans.iter())
+            .map(|span| span.content.to_string())
+            .collect()
+    };
+    assert_eq!(text(&field).trim(), "•••••••");
+    field.toggle_reveal();
+    assert_eq!(text(&field).trim(), "hunter2");
+}
+
+#[test]
+fn password_widget_offers_the_reveal_hint() {
+    let mut schema = make_field().schema;
+    schema.ui.widget = Some(Widget::Password);
+    let mut field = FieldState::from_schema(schema);
+    let text = |field: &FieldState| -> String {
+        info_line(field, false)
+            .spans
+            .iter()
+            .map(|span| span.content.to_string())
+            .collect()
+    };
+    assert!(text(&field).contains("·secret (Ctrl+R reveal)"));
+    field.toggle_reveal();
+    assert!(text(&field).contains("·secret (revealed)"));
+}
+
+#[test]
+fn read_only_value_is_dimmed_without_cursor() {
+    let mut field = make_field();
+    field.schema.read_only = true;
+    let (lines, cursor) = value_panel_lines(&field, true, 40);
+    assert!(cursor.is_none());
+    let span = lines
+        .get(1)
+        .and_then(|line| line.spans.get(1))
+        .expect("value span");
+    assert_eq!(span.style.fg, Some(Color::DarkGray));
+}
+
+#[test]
+fn placeholder_is_shown_dimmed_while_empty() {
+    let mut field = make_field();
+    field.schema.ui.placeholder = Some("e.g. api".into());
+    let (lines, cursor) = value_panel_lines(&field, true, 40);
+    let span = lines
+        .get(1)
+        .and_then(|line| line.spans.get(1))
+        .expect("value span");
+    assert_eq!(span.content.trim(), "e.g. api");
+    assert!(span.style.add_modifier.contains(Modifier::ITALIC));
+    assert_eq!(cursor.map(|hint| hint.value_width), Some(0));
+}
+
+#[test]
+fn caret_follows_the_cursor_across_wide_characters() {
+    let mut field = make_field();
+    field.seed_value(&"漢字ab".into());
+    let left = KeyEvent::new(KeyCode::Left, KeyModifiers::NONE);
+    field.handle_key(&left);
+    field.handle_key(&left);
+    let (_, cursor) = value_panel_lines(&field, true, 40);
+    assert_eq!(cursor.map(|hint| hint.value_width), Some(4));
+
+    let select = KeyEvent::new(KeyCode::Left, KeyModifiers::SHIFT);
+    field.handle_key(&select);
+    let (lines, cursor) = value_panel_lines(&field, true, 40);
+    assert_eq!(cursor.map(|hint| hint.value_width), Some(2));
+    let selected = &lines[1].spans[2];
+    assert_eq!(selected.content, "字");
+    assert!(selected.style.add_modifier.contains(Modifier::REVERSED));
+}
+
+#[test]
+fn textarea_numbers_lines_and_scrolls_with_the_caret() {
+    let mut schema = make_field().schema;
+    schema.ui.widget = Some(Widget::Textarea);
+    let mut field = FieldState::from_schema(schema);
+    let text: Vec<String> = (1..=12).map(|n| format!("line {n}")).collect();
+    field.seed_value(&text.join("\n").into());
+
+    let (lines, cursor) = value_panel_lines(&field, true, 40);
+    let rows: Vec<String> = lines[1..lines.len() - 1]
+        .iter()
+        .map(|line| line.spans[1].content.to_string())
+        .collect();
+    assert_eq!(rows.len(), 8, "the focused editor scrolls past 8 rows");
+    assert_eq!(rows.first().map(String::as_str), Some(" 5 "));
+    assert_eq!(rows.last().map(String::as_str), Some("12 "));
+    let cursor = cursor.expect("caret");
+    assert_eq!(cursor.line_in_field, 8);
+    assert_eq!(cursor.value_width as usize, "12 line 12".len());
+
+    let (preview, _) = value_panel_lines(&field, false, 40);
+    assert_eq!(preview.len(), 4);
+    assert_eq!(preview[3].spans[0].content, "  … 9 more lines");
+}
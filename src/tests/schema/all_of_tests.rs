@@ -0,0 +1,137 @@
+use crate::{domain::FieldKind, schema::layout::build_form_schema};
+use serde_json::json;
+
+#[test]
+fn all_of_branches_merge_into_one_section() {
+    let schema = json!({
+        "type": "object",
+        "definitions": {
+            "base": {
+                "type": "object",
+                "required": ["name"],
+                "properties": {
+                    "name": {"type": "string"}
+                }
+            }
+        },
+        "properties": {
+            "service": {
+                "title": "Service",
+                "allOf": [
+                    {"$ref": "#/definitions/base"},
+                    {
+                        "required": ["port"],
+                        "properties": {
+                            "port": {"type": "integer"}
+                        }
+                    }
+                ]
+            }
+        }
+    });
+    let form = build_form_schema(&schema).expect("schema parsed");
+    let service = form
+        .roots
+        .iter()
+        .find(|root| root.id == "service")
+        .expect("service root");
+    assert_eq!(service.title, "Service");
+    let section = service.sections.first().expect("section");
+    let fields: Vec<_> = section
+        .fields
+        .iter()
+        .map(|field| (field.name.as_str(), field.required))
+        .collect();
+    assert_eq!(fields, vec![("name", true), ("port", true)]);
+    assert!(matches!(section.fields[1].kind, FieldKind::Integer));
+    assert!(form.diagnostics.is_empty());
+}
+
+#[test]
+fn all_of_on_root_schema_is_merged() {
+    let schema = json!({
+        "allOf": [
+            {"type": "object", "properties": {"host": {"type": "string"}}},
+            {"properties": {"debug": {"type": "boolean"}}}
+        ]
+    });
+    let form = build_form_schema(&schema).expect("schema parsed");
+    let general = form.roots.first().expect("general root");
+    let names: Vec<_> = general.sections[0]
+        .fields
+        .iter()
+        .map(|field| field.name.as_str())
+        .collect();
+    assert_eq!(names, vec!["host", "debug"]);
+}
+
+#[test]
+fn all_of_conflicts_are_reported_as_diagnostics() {
+    let schema = json!({
+        "type": "object",
+        "properties": {
+            "limit": {
+                "allOf": [
+                    {"type": "integer", "minimum": 10},
+                    {"type": "string", "maximum": 5}
+                ]
+            }
+        }
+    });
+    let form = build_form_schema(&schema).expect("schema parsed");
+    let pointers: Vec<_> = form
+        .diagnostics
+        .iter()
+        .map(|diagnostic| diagnostic.pointer.as_str())
+        .collect();
+    assert_eq!(pointers, vec!["/limit", "/limit"]);
+    assert!(form.diagnostics[0].message.contains("incompatible types"));
+    assert!(form.diagnostics[1].message.contains("empty range"));
+}
+
+#[test]
+fn every_all_of_conditional_and_dependency_is_kept() {
+    let schema = json!({
+        "type": "object",
+        "properties": {
+            "db": {
+                "type": "object",
+                "properties": {
+                    "engine": {"type": "string", "enum": ["postgres", "sqlite"]},
+                    "tls": {"type": "boolean"},
+                    "user": {"type": "string"},
+                    "password": {"type": "string"}
+                },
+                "allOf": [
+                    {
+                        "if": {"properties": {"engine": {"const": "postgres"}}},
+                        "then": {"properties": {"host": {"type": "string"}}},
+                        "dependentRequired": {"user": ["password"]}
+                    },
+                    {
+                        "if": {"properties": {"engine": {"const": "sqlite"}}},
+                        "then": {"properties": {"file": {"type": "string"}}},
+                        "dependentRequired": {"tls": ["password"]}
+                    }
+                ]
+            }
+        }
+    });
+    let form = build_form_schema(&schema).expect("schema parsed");
+    assert!(form.diagnostics.is_empty(), "{:?}", form.diagnostics);
+    let section = &form.roots[0].sections[0];
+    let field = |name: &str| {
+        section
+            .fields
+            .iter()
+            .find(|field| field.name == name)
+            .unwrap_or_else(|| panic!("missing {name}"))
+    };
+    assert_eq!(field("host").visible_when.len(), 1);
+    assert_eq!(field("file").visible_when.len(), 1);
+    assert_ne!(
+        field("host").visible_when[0].when,
+        field("file").visible_when[0].when
+    );
+    assert_eq!(field("password").required_when.len(), 2);
+}
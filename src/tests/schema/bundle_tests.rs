@@ -0,0 +1,120 @@
+use std::{collections::HashMap, fs};
+
+use anyhow::{Result, anyhow};
+use serde_json::{Value, json};
+use url::Url;
+
+use crate::{
+    domain::FieldKind,
+    schema::{
+        bundle::bundle_external_refs,
+        layout::build_form_schema,
+        retriever::{FileSystemRetriever, SchemaRetriever},
+    },
+};
+
+struct MemoryRetriever(HashMap<String, Value>);
+
+impl SchemaRetriever for MemoryRetriever {
+    fn retrieve(&self, uri: &str) -> Result<Value> {
+        self.0
+            .get(uri)
+            .cloned()
+            .ok_or_else(|| anyhow!("no document at {uri}"))
+    }
+}
+
+#[test]
+fn external_refs_are_bundled_with_their_own_definitions() {
+    let retriever = MemoryRetriever(HashMap::from([(
+        "https://schemas.example.com/common.json".to_string(),
+        json!({
+            "definitions": {
+                "Port": {"$ref": "#/definitions/Number"},
+                "Number": {"type": "integer", "minimum": 1}
+            }
+        }),
+    )]));
+    let schema = json!({
+        "type": "object",
+        "properties": {
+            "port": {"$ref": "common.json#/definitions/Port"}
+        }
+    });
+    let base = Url::parse("https://schemas.example.com/app.json").unwrap();
+    let bundled = bundle_external_refs(&schema, Some(&base), &retriever).expect("bundled");
+    assert_eq!(
+        bundled.pointer("/properties/port/$ref"),
+        Some(&json!("#/definitions/common.json/definitions/Port"))
+    );
+    assert_eq!(
+        bundled.pointer("/definitions/common.json/definitions/Port/$ref"),
+        Some(&json!("#/definitions/common.json/definitions/Number"))
+    );
+
+    let form = build_form_schema(&bundled).expect("layout");
+    let field = &form.roots[0].sections[0].fields[0];
+    assert!(matches!(field.kind, FieldKind::Integer));
+
+    let validator = jsonschema::validator_for(&bundled).expect("validator");
+    assert!(validator.is_valid(&json!({"port": 8080})));
+    assert!(!validator.is_valid(&json!({"port": 0})));
+}
+
+#[test]
+fn relative_refs_need_a_schema_location() {
+    let schema = json!({
+        "type": "object",
+        "properties": {"port": {"$ref": "common.json#/definitions/Port"}}
+    });
+    let err = bundle_external_refs(&schema, None, &FileSystemRetriever).unwrap_err();
+    assert!(err.to_string().contains("requires a schema location"));
+}
+
+#[test]
+fn filesystem_retriever_reads_sibling_files() {
+    let dir = std::env::temp_dir().join(format!("schemaui-bundle-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(
+        dir.join("common.schema.json"),
+        r#"{"definitions": {"Host": {"type": "string", "title": "Host name"}}}"#,
+    )
+    .unwrap();
+    let root_path = dir.join("app.schema.json");
+    let schema = json!({
+        "type": "object",
+        "properties": {"host": {"$ref": "common.schema.json#/definitions/Host"}}
+    });
+    fs::write(&root_path, schema.to_string()).unwrap();
+
+    let base = Url::from_file_path(&root_path).unwrap();
+    let bundled = bundle_external_refs(&schema, Some(&base), &FileSystemRetriever);
+    fs::remove_dir_all(&dir).ok();
+
+    let form = build_form_schema(&bundled.expect("bundled")).expect("layout");
+    let field = &form.roots[0].sections[0].fields[0];
+    assert_eq!(field.title, "Host name");
+}
+
+#[cfg(feature = "remote_refs")]
+#[test]
+fn http_cache_keeps_similar_urls_apart() {
+    use crate::schema::retriever::HttpRetriever;
+
+    let dir = std::env::temp_dir().join(format!("schemaui-http-cache-{}", std::process::id()));
+    let retriever = HttpRetriever::new(&dir);
+    let first = "https://a.com/x_y.json";
+    let second = "https://a.com/x/y.json";
+    assert_ne!(retriever.cache_path(first), retriever.cache_path(second));
+
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(retriever.cache_path(first), r#"{"title": "first"}"#).unwrap();
+    fs::write(retriever.cache_path(second), r#"{"title": "second"}"#).unwrap();
+    assert_eq!(retriever.retrieve(first).unwrap()["title"], "first");
+    assert_eq!(retriever.retrieve(second).unwrap()["title"], "second");
+
+    let long = format!("https://a.com/{}.json", "segment/".repeat(100));
+    let name = retriever.cache_path(&long);
+    assert!(name.file_name().unwrap().len() < 100);
+    fs::remove_dir_all(&dir).ok();
+}
@@ -0,0 +1,84 @@
+use serde_json::{Value, json};
+
+use crate::{
+    SchemaUI,
+    domain::{FieldKind, FormSchema},
+    form::FormState,
+    schema::layout::build_form_schema,
+};
+
+fn schema() -> Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "name": {"type": "string", "not": {"const": "root"}},
+            "nothing": {"type": "null"},
+            "choice": {"oneOf": [{"type": "string"}, {"type": "integer"}]},
+            "tags": {"type": "array", "items": {"type": "string"}, "contains": {"const": "x"}},
+            "server": {
+                "type": "object",
+                "minProperties": 1,
+                "properties": {"host": {"type": "string"}}
+            }
+        }
+    })
+}
+
+fn field_kind<'a>(form: &'a FormSchema, pointer: &str) -> &'a FieldKind {
+    form.roots
+        .iter()
+        .flat_map(|root| &root.sections)
+        .flat_map(|section| &section.fields)
+        .find(|field| field.pointer == pointer)
+        .map(|field| &field.kind)
+        .unwrap_or_else(|| panic!("field {pointer} missing"))
+}
+
+#[test]
+fn unsupported_fields_fall_back_to_json_instead_of_failing() {
+    let form = build_form_schema(&schema()).expect("layout does not abort");
+    assert_eq!(field_kind(&form, "/nothing"), &FieldKind::Json);
+    assert_eq!(field_kind(&form, "/choice"), &FieldKind::Json);
+    assert_eq!(field_kind(&form, "/name"), &FieldKind::String);
+
+    let mut state = FormState::from_schema(&form);
+    state.seed_from_value(&json!({"nothing": null, "choice": 3}));
+    let value = state.try_build_value().expect("value");
+    assert_eq!(value["nothing"], Value::Null);
+    assert_eq!(value["choice"], json!(3));
+}
+
+#[test]
+fn every_unsupported_or_ignored_keyword_is_reported_with_its_pointer() {
+    let form = build_form_schema(&schema()).expect("layout");
+    let report = form
+        .diagnostics
+        .iter()
+        .map(|diagnostic| (diagnostic.pointer.as_str(), diagnostic.message.as_str()))
+        .collect::<Vec<_>>();
+    let has = |pointer: &str, needle: &str| {
+        report
+            .iter()
+            .any(|(at, message)| *at == pointer && message.contains(needle))
+    };
+    assert!(has("/name", "`not`"), "{report:?}");
+    assert!(has("/nothing", "raw JSON editor"), "{report:?}");
+    assert!(has("/choice", "raw JSON editor"), "{report:?}");
+    assert!(has("/tags", "`contains`"), "{report:?}");
+    assert!(has("/server", "`minProperties`"), "{report:?}");
+    assert_eq!(report.len(), 5, "{report:?}");
+}
+
+#[test]
+fn schema_ui_exposes_the_report() {
+    let diagnostics = SchemaUI::new(schema()).diagnostics().expect("report");
+    assert_eq!(diagnostics.len(), 5);
+    assert!(
+        diagnostics[0]
+            .to_string()
+            .starts_with("#/choice: unsupported schema")
+    );
+
+    let clean = json!({"type": "object", "properties": {"a": {"type": "string"}}});
+    assert!(SchemaUI::new(clean).diagnostics().unwrap().is_empty());
+}
@@ -0,0 +1,141 @@
+use serde_json::{Value, json};
+
+use crate::{
+    domain::FieldKind,
+    schema::{
+        bundle::bundle_external_refs, layout::build_form_schema, retriever::FileSystemRetriever,
+    },
+};
+
+#[test]
+fn defs_and_anchor_refs_resolve() {
+    let schema = json!({
+        "$schema": "https://json-schema.org/draft/2020-12/schema",
+        "type": "object",
+        "$defs": {
+            "port": {"$anchor": "port", "type": "integer", "title": "Port"},
+            "host": {"type": "string", "title": "Host"}
+        },
+        "properties": {
+            "host": {"$ref": "#/$defs/host"},
+            "port": {"$ref": "#port"}
+        }
+    });
+    let form = build_form_schema(&schema).expect("schema parsed");
+    let fields = &form.roots[0].sections[0].fields;
+    let titles: Vec<_> = fields.iter().map(|f| f.title.as_str()).collect();
+    assert_eq!(titles, vec!["Host", "Port"]);
+    assert!(matches!(fields[1].kind, FieldKind::Integer));
+}
+
+fn nested_id_schema() -> Value {
+    json!({
+        "$id": "https://example.com/schemas/app.json",
+        "type": "object",
+        "properties": {
+            "billing": {"$ref": "address.json"},
+            "shipping": {"$ref": "https://example.com/schemas/address.json#/properties/zip"}
+        },
+        "definitions": {
+            "address": {
+                "$id": "address.json",
+                "type": "object",
+                "properties": {
+                    "zip": {"$ref": "#/definitions/code"}
+                },
+                "definitions": {
+                    "code": {"type": "string", "title": "Postal code"}
+                }
+            }
+        }
+    })
+}
+
+#[test]
+fn nested_id_sets_base_for_relative_refs() {
+    let form = build_form_schema(&nested_id_schema()).expect("schema parsed");
+    let billing = form
+        .roots
+        .iter()
+        .find(|root| root.id == "billing")
+        .expect("billing root");
+    assert_eq!(billing.sections[0].fields[0].title, "Postal code");
+    let general = &form.roots[0].sections[0].fields;
+    assert_eq!(general[0].name, "shipping");
+    assert_eq!(general[0].title, "Postal code");
+}
+
+#[test]
+fn bundled_nested_id_schema_compiles_a_validator() {
+    let bundled =
+        bundle_external_refs(&nested_id_schema(), None, &FileSystemRetriever).expect("bundled");
+    assert!(bundled["definitions"]["address"].get("$id").is_none());
+    assert_eq!(bundled["$id"], "https://example.com/schemas/app.json");
+    let validator = jsonschema::validator_for(&bundled).expect("validator compiles");
+    assert!(validator.is_valid(&json!({"billing": {"zip": "12345"}, "shipping": "6789"})));
+    assert!(!validator.is_valid(&json!({"billing": {"zip": 12345}})));
+}
+
+#[test]
+fn dynamic_ref_uses_outermost_dynamic_anchor() {
+    let schema = json!({
+        "$schema": "https://json-schema.org/draft/2020-12/schema",
+        "type": "object",
+        "$defs": {
+            "item": {"$dynamicAnchor": "item", "type": "string", "title": "Item"},
+            "list": {
+                "type": "array",
+                "items": {"$dynamicRef": "#item"}
+            }
+        },
+        "properties": {
+            "tags": {"$ref": "#/$defs/list"}
+        }
+    });
+    let form = build_form_schema(&schema).expect("schema parsed");
+    let field = &form.roots[0].sections[0].fields[0];
+    assert_eq!(field.kind, FieldKind::Array(Box::new(FieldKind::String)));
+}
+
+#[test]
+fn defs_are_carried_into_sub_schemas() {
+    let schema = json!({
+        "type": "object",
+        "$defs": {
+            "endpoint": {
+                "type": "object",
+                "properties": {"url": {"type": "string"}}
+            }
+        },
+        "properties": {
+            "target": {
+                "oneOf": [
+                    {"type": "object", "properties": {"primary": {"$ref": "#/$defs/endpoint"}}}
+                ]
+            },
+            "mirrors": {
+                "type": "object",
+                "additionalProperties": {"$ref": "#/$defs/endpoint"}
+            }
+        }
+    });
+    let form = build_form_schema(&schema).expect("schema parsed");
+    let fields = &form.roots[0].sections[0].fields;
+    let kind_of = |name: &str| {
+        &fields
+            .iter()
+            .find(|field| field.name == name)
+            .expect("field")
+            .kind
+    };
+    let FieldKind::Composite(composite) = kind_of("target") else {
+        panic!("expected composite");
+    };
+    assert!(composite.variants[0].schema.get("$defs").is_some());
+    let FieldKind::KeyValue(map) = kind_of("mirrors") else {
+        panic!("expected key/value");
+    };
+    assert!(map.entry_schema.get("$defs").is_some());
+    jsonschema::validator_for(&composite.variants[0].schema).expect("variant compiles");
+    jsonschema::validator_for(&map.entry_schema).expect("entry compiles");
+}
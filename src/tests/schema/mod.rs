@@ -1,2 +1,8 @@
+mod all_of_tests;
+mod bundle_tests;
+mod diagnostics_tests;
+mod draft_tests;
 mod layout_tests;
+mod public_layout_tests;
+mod recursive_tests;
 mod resolver_tests;
@@ -0,0 +1,107 @@
+use std::collections::HashMap;
+
+use serde_json::json;
+
+use crate::layout::{FieldKind, FormSchema, build_form_schema};
+
+fn schema() -> serde_json::Value {
+    json!({
+        "title": "Service",
+        "type": "object",
+        "required": ["name"],
+        "properties": {
+            "name": {
+                "type": "string",
+                "default": "api",
+                "x-help": "Service name",
+                "x-owner": "platform"
+            },
+            "started": {"type": "string", "format": "date-time"},
+            "ports": {
+                "type": "array",
+                "items": {"type": "integer"},
+                "maxItems": 4
+            },
+            "database": {
+                "type": "object",
+                "properties": {
+                    "mode": {"enum": ["primary", "replica"]}
+                }
+            }
+        }
+    })
+}
+
+#[test]
+fn exposes_pointers_kinds_defaults_and_required_flags() {
+    let form = build_form_schema(&schema()).expect("layout");
+    assert_eq!(form.title.as_deref(), Some("Service"));
+    let fields = form
+        .roots
+        .iter()
+        .flat_map(|root| &root.sections)
+        .flat_map(|section| &section.fields)
+        .collect::<Vec<_>>();
+
+    let name = fields
+        .iter()
+        .find(|field| field.pointer == "/name")
+        .unwrap();
+    assert!(name.required);
+    assert_eq!(name.default, Some(json!("api")));
+    assert_eq!(name.kind, FieldKind::String { format: None });
+    assert_eq!(
+        name.metadata,
+        HashMap::from([("x-owner".to_string(), json!("platform"))]),
+        "only the keys schemaui does not read are passed through"
+    );
+
+    let started = fields.iter().find(|field| field.name == "started").unwrap();
+    assert!(!started.required);
+    assert_eq!(
+        started.kind,
+        FieldKind::String {
+            format: Some("date-time".into())
+        }
+    );
+
+    let ports = fields.iter().find(|field| field.name == "ports").unwrap();
+    assert_eq!(
+        ports.kind,
+        FieldKind::Array {
+            items: Box::new(FieldKind::Integer),
+            min_items: None,
+            max_items: Some(4),
+            unique_items: false,
+        }
+    );
+
+    let database = form
+        .roots
+        .iter()
+        .find(|root| root.id == "database")
+        .expect("database tab");
+    assert_eq!(database.sections[0].pointer, "/database");
+    assert_eq!(database.sections[0].fields[0].pointer, "/database/mode");
+}
+
+#[test]
+fn serializes_with_tagged_kinds_and_round_trips() {
+    let form = build_form_schema(&schema()).expect("layout");
+    let value = serde_json::to_value(&form).expect("serialize");
+    let ports = value["roots"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .flat_map(|root| root["sections"].as_array().unwrap())
+        .flat_map(|section| section["fields"].as_array().unwrap())
+        .find(|field| field["name"] == "ports")
+        .unwrap();
+    assert_eq!(
+        ports["kind"],
+        json!({"type": "array", "items": {"type": "integer"}, "max_items": 4, "unique_items": false})
+    );
+
+    let back: FormSchema = serde_json::from_value(value).expect("deserialize");
+    assert_eq!(back, form);
+}
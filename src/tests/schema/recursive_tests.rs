@@ -0,0 +1,142 @@
+use serde_json::{Value, json};
+
+use crate::{
+    domain::{FieldKind, FormSchema},
+    form::FormState,
+    schema::layout::build_form_schema,
+};
+
+fn rule_schema() -> Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "rule": {"$ref": "#/definitions/Rule"}
+        },
+        "definitions": {
+            "Rule": {
+                "type": "object",
+                "properties": {
+                    "name": {"type": "string"},
+                    "children": {
+                        "type": "array",
+                        "items": {"$ref": "#/definitions/Rule"}
+                    },
+                    "next": {"$ref": "#/definitions/Rule"}
+                }
+            }
+        }
+    })
+}
+
+fn field_kind<'a>(form: &'a FormSchema, pointer: &str) -> &'a FieldKind {
+    form.roots
+        .iter()
+        .flat_map(|root| &root.sections)
+        .flat_map(|section| &section.fields)
+        .find(|field| field.pointer == pointer)
+        .map(|field| &field.kind)
+        .unwrap_or_else(|| panic!("field {pointer} missing"))
+}
+
+#[test]
+fn self_reference_becomes_lazy_sub_form() {
+    let form = build_form_schema(&rule_schema()).expect("recursive schema lays out");
+
+    let FieldKind::Composite(next) = field_kind(&form, "/rule/next") else {
+        panic!("next should be a lazy sub-form");
+    };
+    assert!(next.recursive);
+    assert_eq!(next.variants[0].title, "Rule");
+    assert_eq!(next.variants[0].schema["$ref"], "#/definitions/Rule");
+
+    let FieldKind::Array(inner) = field_kind(&form, "/rule/children") else {
+        panic!("children should be a list");
+    };
+    assert!(matches!(inner.as_ref(), FieldKind::Composite(entry) if !entry.recursive));
+}
+
+#[test]
+fn detached_sub_forms_expand_one_level_at_a_time() {
+    let form = build_form_schema(&rule_schema()).expect("schema");
+    let FieldKind::Composite(next) = field_kind(&form, "/rule/next") else {
+        panic!("next should be a lazy sub-form");
+    };
+
+    let nested = build_form_schema(&next.variants[0].schema).expect("nested schema");
+    assert!(matches!(
+        field_kind(&nested, "/next"),
+        FieldKind::Composite(field) if field.recursive
+    ));
+}
+
+#[test]
+fn recursive_values_round_trip_to_any_depth() {
+    let form = build_form_schema(&rule_schema()).expect("schema");
+    let mut state = FormState::from_schema(&form);
+    assert_eq!(
+        state.try_build_value().expect("value"),
+        json!({"rule": {"children": []}})
+    );
+
+    let value = json!({
+        "rule": {
+            "name": "a",
+            "children": [{"name": "b", "children": [{"name": "c", "children": []}]}],
+            "next": {"name": "d", "children": [], "next": {"name": "e", "children": []}}
+        }
+    });
+    state.seed_from_value(&value);
+    assert_eq!(state.try_build_value().expect("value"), value);
+}
+
+#[test]
+fn root_self_reference_is_lazy() {
+    let schema = json!({
+        "type": "object",
+        "title": "Node",
+        "properties": {
+            "label": {"type": "string"},
+            "child": {"$ref": "#"}
+        }
+    });
+    let form = build_form_schema(&schema).expect("schema");
+    let FieldKind::Composite(child) = field_kind(&form, "/child") else {
+        panic!("child should be a lazy sub-form");
+    };
+    assert!(child.recursive);
+    assert_eq!(child.variants[0].title, "Node");
+}
+
+#[test]
+fn recursive_arrays_stop_at_json() {
+    let schema = json!({
+        "type": "object",
+        "properties": {
+            "tree": {"$ref": "#/definitions/Tree"}
+        },
+        "definitions": {
+            "Tree": {"type": "array", "items": {"$ref": "#/definitions/Tree"}}
+        }
+    });
+    let form = build_form_schema(&schema).expect("schema");
+    assert_eq!(
+        field_kind(&form, "/tree"),
+        &FieldKind::Array(Box::new(FieldKind::Array(Box::new(FieldKind::Json))))
+    );
+}
+
+#[test]
+fn circular_aliases_are_rejected() {
+    let schema = json!({
+        "type": "object",
+        "properties": {
+            "a": {"$ref": "#/definitions/A"}
+        },
+        "definitions": {
+            "A": {"$ref": "#/definitions/B"},
+            "B": {"$ref": "#/definitions/A"}
+        }
+    });
+    let err = build_form_schema(&schema).expect_err("alias cycle");
+    assert!(format!("{err:#}").contains("circular $ref"));
+}